@@ -1,4 +1,4 @@
-use cardpack::BridgeBoard;
+use cardpack::{BridgeBoard, DiagramOptions};
 
 /// This is a complex example, and gives an idea of some of the work planned around Bridge.
 fn main() {
@@ -23,4 +23,13 @@ fn main() {
     println!("[Deal \"{}\"]\n", deal);
 
     board.demo();
+    println!();
+
+    println!("Boards can also be shown as a hand diagram:");
+    let options = DiagramOptions {
+        header: true,
+        color: true,
+        trick_table: None,
+    };
+    print!("{}", board.diagram(&options));
 }
@@ -21,7 +21,7 @@ fn display(deck: &cardpack::Pile) {
         let suitname = card.suit.name.long_default();
 
         let rankname = card.rank.name.long_default();
-        if suitname == "Major Arcana".to_string() {
+        if suitname == "Major Arcana" {
             println!("      {}", rankname);
         } else {
             println!("      {} of {} ", rankname, suitname);
@@ -0,0 +1,330 @@
+use std::fmt;
+
+use crate::cards::decks::bridge::contract::{Contract, Doubling, Strain};
+use crate::cards::decks::bridge::BridgeDirection;
+
+/// A call in the auction: a pass, a double, a redouble or a bid of a level and a strain.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Call {
+    PASS,
+    DOUBLE,
+    REDOUBLE,
+    BID(usize, Strain),
+}
+
+impl Call {
+    /// Parses a call as written in Portable Bridge Notation, such as `Pass`, `X`, `XX`,
+    /// `1NT` or `4S`.
+    pub fn from_pbn(s: &str) -> Option<Call> {
+        let s = s.trim().to_uppercase();
+        match s.as_str() {
+            "PASS" | "P" | "AP" => Some(Call::PASS),
+            "X" | "DBL" => Some(Call::DOUBLE),
+            "XX" | "RDBL" => Some(Call::REDOUBLE),
+            _ => {
+                let level = s.get(..1)?.parse::<usize>().ok()?;
+                if !(1..=7).contains(&level) {
+                    return None;
+                }
+                Some(Call::BID(level, Strain::to(s.get(1..)?)?))
+            }
+        }
+    }
+
+    pub fn to_pbn(&self) -> String {
+        match self {
+            Call::PASS => "Pass".to_string(),
+            Call::DOUBLE => "X".to_string(),
+            Call::REDOUBLE => "XX".to_string(),
+            Call::BID(level, strain) => format!("{}{}", level, strain.to_pbn()),
+        }
+    }
+
+    pub fn is_bid(&self) -> bool {
+        matches!(self, Call::BID(_, _))
+    }
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_pbn())
+    }
+}
+
+/// The calls made on a board, starting with the dealer, checked against the rules of
+/// bidding as they are made.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{Auction, BridgeDirection, Call, Strain};
+///
+/// let mut auction = Auction::new(BridgeDirection::N);
+///
+/// assert!(auction.call(Call::BID(1, Strain::S)));
+/// assert!(!auction.call(Call::BID(1, Strain::H)));
+/// assert!(auction.call(Call::DOUBLE));
+/// assert!(auction.call(Call::BID(4, Strain::S)));
+/// assert!(auction.call(Call::PASS));
+/// assert!(auction.call(Call::PASS));
+/// assert!(auction.call(Call::PASS));
+///
+/// assert!(auction.is_complete());
+/// assert_eq!("4S", auction.contract().unwrap().to_pbn());
+/// assert_eq!(BridgeDirection::N, auction.contract().unwrap().declarer);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Auction {
+    pub dealer: BridgeDirection,
+    calls: Vec<Call>,
+}
+
+impl Auction {
+    /// Starts an auction with the dealer, who is taken to be North if unknown.
+    pub fn new(dealer: BridgeDirection) -> Auction {
+        let dealer = match dealer {
+            BridgeDirection::UNKNOWN => BridgeDirection::N,
+            dealer => dealer,
+        };
+        Auction {
+            dealer,
+            calls: Vec::new(),
+        }
+    }
+
+    pub fn calls(&self) -> &Vec<Call> {
+        &self.calls
+    }
+
+    /// Returns the seat that made the call at the passed in position in the auction.
+    pub fn caller(&self, position: usize) -> BridgeDirection {
+        self.dealer.clockwise()[position % 4]
+    }
+
+    /// Returns the seat whose turn it is to call.
+    pub fn next_caller(&self) -> BridgeDirection {
+        self.caller(self.calls.len())
+    }
+
+    /// The last bid made, along with the seat that made it.
+    pub fn last_bid(&self) -> Option<(usize, Strain, BridgeDirection)> {
+        self.calls
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(position, call)| match call {
+                Call::BID(level, strain) => Some((*level, *strain, self.caller(position))),
+                _ => None,
+            })
+    }
+
+    /// Returns true if the call can be made by the next player.
+    pub fn is_legal(&self, call: &Call) -> bool {
+        if self.is_complete() {
+            return false;
+        }
+        let caller = self.next_caller();
+        let last = self
+            .calls
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, call)| **call != Call::PASS);
+        let by_opponent = |position: usize| !self.caller(position).is_partner_of(&caller);
+        match call {
+            Call::PASS => true,
+            Call::BID(level, strain) => {
+                (1..=7).contains(level)
+                    && self
+                        .last_bid()
+                        .map_or(true, |(last_level, last_strain, _)| {
+                            (*level, *strain) > (last_level, last_strain)
+                        })
+            }
+            Call::DOUBLE => {
+                matches!(last, Some((position, Call::BID(_, _))) if by_opponent(position))
+            }
+            Call::REDOUBLE => {
+                matches!(last, Some((position, Call::DOUBLE)) if by_opponent(position))
+            }
+        }
+    }
+
+    /// Makes the call for the next player, returning false if it isn't legal.
+    pub fn call(&mut self, call: Call) -> bool {
+        if !self.is_legal(&call) {
+            return false;
+        }
+        self.calls.push(call);
+        true
+    }
+
+    /// An auction is over after four passes to start with, or three passes after a bid.
+    pub fn is_complete(&self) -> bool {
+        let passes = self
+            .calls
+            .iter()
+            .rev()
+            .take_while(|call| **call == Call::PASS)
+            .count();
+        if self.last_bid().is_some() {
+            passes >= 3
+        } else {
+            passes >= 4
+        }
+    }
+
+    pub fn is_passed_out(&self) -> bool {
+        self.is_complete() && self.last_bid().is_none()
+    }
+
+    /// The final contract once the auction is complete. Declarer is the player of the
+    /// declaring side who first named the strain.
+    pub fn contract(&self) -> Option<Contract> {
+        if !self.is_complete() {
+            return None;
+        }
+        let (level, strain, last_bidder) = self.last_bid()?;
+        let declarer = self
+            .calls
+            .iter()
+            .enumerate()
+            .find(|(position, call)| {
+                matches!(call, Call::BID(_, s) if *s == strain)
+                    && self.caller(*position).side() == last_bidder.side()
+            })
+            .map(|(position, _)| self.caller(position))?;
+
+        let mut contract = Contract::new(level, strain, declarer);
+        for call in self.calls.iter().rev() {
+            match call {
+                Call::DOUBLE => {
+                    contract.doubling = Doubling::DOUBLED;
+                    break;
+                }
+                Call::REDOUBLE => {
+                    contract.doubling = Doubling::REDOUBLED;
+                    break;
+                }
+                Call::BID(_, _) => break,
+                Call::PASS => {}
+            }
+        }
+        Some(contract)
+    }
+}
+
+/// Displays the calls separated by spaces, such as `1S X 4S Pass Pass Pass`.
+impl fmt::Display for Auction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let calls: Vec<String> = self.calls.iter().map(|c| c.to_pbn()).collect();
+        write!(f, "{}", calls.join(" "))
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_auction_tests {
+    use super::*;
+
+    fn auction(dealer: BridgeDirection, calls: &str) -> Auction {
+        let mut auction = Auction::new(dealer);
+        for call in calls.split_whitespace() {
+            assert!(auction.call(Call::from_pbn(call).unwrap()), "{}", call);
+        }
+        auction
+    }
+
+    #[test]
+    fn call__from_pbn() {
+        assert_eq!(Some(Call::PASS), Call::from_pbn("Pass"));
+        assert_eq!(Some(Call::DOUBLE), Call::from_pbn("x"));
+        assert_eq!(Some(Call::REDOUBLE), Call::from_pbn("XX"));
+        assert_eq!(Some(Call::BID(1, Strain::NT)), Call::from_pbn("1NT"));
+        assert_eq!(Some(Call::BID(7, Strain::C)), Call::from_pbn("7c"));
+        assert_eq!(None, Call::from_pbn("8S"));
+        assert_eq!(None, Call::from_pbn("0H"));
+        assert_eq!(None, Call::from_pbn("3Z"));
+        assert_eq!("3NT", Call::BID(3, Strain::NT).to_string());
+    }
+
+    #[test]
+    fn next_caller() {
+        let auction = auction(BridgeDirection::E, "Pass 1H");
+
+        assert_eq!(BridgeDirection::W, auction.next_caller());
+        assert_eq!(BridgeDirection::S, auction.caller(1));
+    }
+
+    #[test]
+    fn is_legal() {
+        let auction = auction(BridgeDirection::N, "1H");
+
+        assert!(auction.is_legal(&Call::BID(1, Strain::S)));
+        assert!(auction.is_legal(&Call::BID(2, Strain::C)));
+        assert!(!auction.is_legal(&Call::BID(1, Strain::D)));
+        assert!(!auction.is_legal(&Call::BID(1, Strain::H)));
+        assert!(auction.is_legal(&Call::DOUBLE));
+        assert!(!auction.is_legal(&Call::REDOUBLE));
+    }
+
+    #[test]
+    fn is_legal__doubles() {
+        let partner_bid = auction(BridgeDirection::N, "1H Pass");
+        let doubled = auction(BridgeDirection::N, "1H X");
+        let doubled_by_partner = auction(BridgeDirection::N, "1H X Pass");
+
+        assert!(!partner_bid.is_legal(&Call::DOUBLE));
+        assert!(!doubled.is_legal(&Call::DOUBLE));
+        assert!(doubled.is_legal(&Call::REDOUBLE));
+        assert!(!doubled_by_partner.is_legal(&Call::REDOUBLE));
+        assert!(auction(BridgeDirection::N, "1H Pass Pass").is_legal(&Call::DOUBLE));
+        assert!(!Auction::new(BridgeDirection::N).is_legal(&Call::DOUBLE));
+    }
+
+    #[test]
+    fn is_complete() {
+        assert!(!auction(BridgeDirection::N, "Pass Pass Pass").is_complete());
+        assert!(auction(BridgeDirection::N, "Pass Pass Pass Pass").is_passed_out());
+        assert!(auction(BridgeDirection::N, "Pass 1C Pass Pass Pass").is_complete());
+        assert!(!auction(BridgeDirection::N, "1C Pass Pass Pass").is_legal(&Call::PASS));
+    }
+
+    #[test]
+    fn contract() {
+        let auction = auction(
+            BridgeDirection::S,
+            "1H Pass 2D Pass 2S Pass 4H X Pass Pass XX Pass Pass Pass",
+        );
+
+        let contract = auction.contract().unwrap();
+
+        // South opened hearts, so South declares even though North bid game.
+        assert_eq!(BridgeDirection::S, contract.declarer);
+        assert_eq!("4HXX", contract.to_pbn());
+    }
+
+    #[test]
+    fn contract__doubled_after_passes() {
+        let auction = auction(BridgeDirection::W, "1NT Pass Pass X Pass Pass Pass");
+
+        assert_eq!("1NTX", auction.contract().unwrap().to_pbn());
+        assert_eq!(BridgeDirection::W, auction.contract().unwrap().declarer);
+    }
+
+    #[test]
+    fn contract__incomplete_or_passed_out() {
+        assert!(auction(BridgeDirection::N, "1C Pass").contract().is_none());
+        assert!(auction(BridgeDirection::N, "Pass Pass Pass Pass")
+            .contract()
+            .is_none());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            "1S X 4S Pass",
+            auction(BridgeDirection::N, "1S X 4S Pass").to_string()
+        );
+    }
+}
@@ -0,0 +1,577 @@
+use std::fmt;
+
+use crate::cards::decks::bridge::auction::{Auction, Call};
+use crate::cards::decks::bridge::generator::{HandCondition, ScriptError};
+use crate::cards::pile::Pile;
+
+/// The rules of a standard natural system: five card majors, a 15-17 no trump with Stayman
+/// and Jacoby transfers, a strong two clubs, weak twos and Blackwood.
+pub const STANDARD_RULES: &str = "\
+# Openings
+- : 2C : hcp >= 22 : Strong and artificial, 22 or more points
+- : 2NT : hcp >= 20 && hcp <= 21 && balanced : 20 to 21 points and a balanced hand
+- : 1NT : hcp >= 15 && hcp <= 17 && balanced : 15 to 17 points and a balanced hand
+- : 1S : hcp >= 12 && spades >= 5 && spades >= hearts : 12 or more points and five or more spades
+- : 1H : hcp >= 12 && hearts >= 5 : 12 or more points and five or more hearts
+- : 1D : hcp >= 12 && diamonds >= 4 && diamonds >= clubs : 12 or more points and four or more diamonds
+- : 1C : hcp >= 12 : 12 or more points without a five card major
+- : 2S : hcp >= 6 && hcp <= 10 && spades == 6 : Weak two, 6 to 10 points and six spades
+- : 2H : hcp >= 6 && hcp <= 10 && hearts == 6 : Weak two, 6 to 10 points and six hearts
+- : 2D : hcp >= 6 && hcp <= 10 && diamonds == 6 : Weak two, 6 to 10 points and six diamonds
+
+# Responses to 1NT
+1NT : 2D : hearts >= 5 : Jacoby transfer, five or more hearts
+1NT : 2H : spades >= 5 : Jacoby transfer, five or more spades
+1NT : 2C : hcp >= 8 && (spades == 4 || hearts == 4) : Stayman, asking for a four card major
+1NT : 7NT : hcp >= 22 : Grand slam
+1NT : 6NT : hcp >= 18 && hcp <= 21 : Small slam
+1NT : 4NT : hcp >= 16 && hcp <= 17 : Quantitative, inviting a small slam
+1NT : 3NT : hcp >= 10 && hcp <= 15 : Enough for game
+1NT : 2NT : hcp >= 8 && hcp <= 9 : Invitational to game
+1NT : Pass : hcp <= 7 : Not enough for game
+1NT 2C : 2H : hearts >= 4 : Four hearts
+1NT 2C : 2S : spades >= 4 : Four spades and not four hearts
+1NT 2C : 2D : : No four card major
+1NT 2D : 2H : : Completes the transfer
+1NT 2H : 2S : : Completes the transfer
+1NT 2C 2H : 4H : hearts == 4 && hcp >= 10 : Game with a heart fit
+1NT 2C 2H : 3H : hearts == 4 : Invitational with a heart fit
+1NT 2C 2S : 4S : spades == 4 && hcp >= 10 : Game with a spade fit
+1NT 2C 2S : 3S : spades == 4 : Invitational with a spade fit
+1NT 2C * : 3NT : hcp >= 10 : Game without a major fit
+1NT 2C * : 2NT : : Invitational without a major fit
+1NT 2D 2H : 4H : hearts >= 6 && hcp >= 10 : Game with six or more hearts
+1NT 2D 2H : 3NT : hcp >= 10 : Game, offering a choice with five hearts
+1NT 2D 2H : 2NT : hcp >= 8 : Invitational with five hearts
+1NT 2D 2H : Pass : : Signs off in hearts
+1NT 2H 2S : 4S : spades >= 6 && hcp >= 10 : Game with six or more spades
+1NT 2H 2S : 3NT : hcp >= 10 : Game, offering a choice with five spades
+1NT 2H 2S : 2NT : hcp >= 8 : Invitational with five spades
+1NT 2H 2S : Pass : : Signs off in spades
+1NT 4NT : 6NT : hcp >= 17 : Accepts the slam invitation with a maximum
+1NT 4NT : Pass : : Declines the slam invitation with a minimum
+
+# Opener's answers to invitations and choices of game after 1NT
+1NT 2NT : 3NT : hcp >= 16 : Accepts the invitation with a maximum
+1NT 2NT : Pass : : Declines the invitation with a minimum
+1NT 2C 2H 3H : 4H : hcp >= 16 : Accepts the invitation with a maximum
+1NT 2C 2H 3H : Pass : : Declines the invitation with a minimum
+1NT 2C 2S 3S : 4S : hcp >= 16 : Accepts the invitation with a maximum
+1NT 2C 2S 3S : Pass : : Declines the invitation with a minimum
+1NT 2C * 2NT : 3NT : hcp >= 16 : Accepts the invitation with a maximum
+1NT 2C * 2NT : Pass : : Declines the invitation with a minimum
+1NT 2D 2H 2NT : 4H : hearts >= 3 && hcp >= 16 : Accepts the invitation with a maximum and a heart fit
+1NT 2D 2H 2NT : 3NT : hcp >= 16 : Accepts the invitation with a maximum and two hearts
+1NT 2D 2H 2NT : 3H : hearts >= 3 : Declines the invitation with a minimum and a heart fit
+1NT 2D 2H 2NT : Pass : : Declines the invitation with a minimum and two hearts
+1NT 2D 2H 3NT : 4H : hearts >= 3 : Chooses hearts with a fit
+1NT 2D 2H 3NT : Pass : : Chooses no trump with two hearts
+1NT 2H 2S 2NT : 4S : spades >= 3 && hcp >= 16 : Accepts the invitation with a maximum and a spade fit
+1NT 2H 2S 2NT : 3NT : hcp >= 16 : Accepts the invitation with a maximum and two spades
+1NT 2H 2S 2NT : 3S : spades >= 3 : Declines the invitation with a minimum and a spade fit
+1NT 2H 2S 2NT : Pass : : Declines the invitation with a minimum and two spades
+1NT 2H 2S 3NT : 4S : spades >= 3 : Chooses spades with a fit
+1NT 2H 2S 3NT : Pass : : Chooses no trump with two spades
+
+# Responses to one of a major
+1H : 4NT : hearts >= 4 && hcp >= 16 : Blackwood, with four hearts and slam values
+1H : 4H : hearts >= 4 && hcp >= 13 && hcp <= 15 : Game raise, four hearts and 13 to 15 points
+1H : 3H : hearts >= 4 && hcp >= 10 && hcp <= 12 : Limit raise, four hearts and 10 to 12 points
+1H : 2H : hearts >= 3 && hcp >= 6 && hcp <= 9 : Single raise, 6 to 9 points
+1H : 1S : spades >= 4 && hcp >= 6 : Four or more spades, forcing
+1H : 1NT : hcp >= 6 && hcp <= 9 : 6 to 9 points without a fit
+1H : 2C : clubs >= 4 && hcp >= 10 : Four or more clubs and 10 or more points
+1H : 2D : diamonds >= 4 && hcp >= 10 : Four or more diamonds and 10 or more points
+1S : 4NT : spades >= 4 && hcp >= 16 : Blackwood, with four spades and slam values
+1S : 4S : spades >= 4 && hcp >= 13 && hcp <= 15 : Game raise, four spades and 13 to 15 points
+1S : 3S : spades >= 4 && hcp >= 10 && hcp <= 12 : Limit raise, four spades and 10 to 12 points
+1S : 2S : spades >= 3 && hcp >= 6 && hcp <= 9 : Single raise, 6 to 9 points
+1S : 1NT : hcp >= 6 && hcp <= 9 : 6 to 9 points without a fit
+1S : 2H : hearts >= 5 && hcp >= 10 : Five or more hearts and 10 or more points
+1S : 2C : clubs >= 4 && hcp >= 10 : Four or more clubs and 10 or more points
+1S : 2D : diamonds >= 4 && hcp >= 10 : Four or more diamonds and 10 or more points
+
+# Strong two clubs
+2C : 2D : : Waiting
+
+# Responses to one of a minor
+1C : 1H : hearts >= 4 && hcp >= 6 : Four or more hearts
+1C : 1S : spades >= 4 && hcp >= 6 : Four or more spades and not four hearts
+1C : 1D : diamonds >= 4 && hcp >= 6 : Four or more diamonds
+1D : 1H : hearts >= 4 && hcp >= 6 : Four or more hearts
+1D : 1S : spades >= 4 && hcp >= 6 : Four or more spades and not four hearts
+* : 3NT : hcp >= 13 && hcp <= 15 && balanced : 13 to 15 points and a balanced hand
+* : 2NT : hcp >= 11 && hcp <= 12 && balanced : 11 to 12 points and a balanced hand
+* : 1NT : hcp >= 6 && hcp <= 10 : 6 to 10 points
+
+# Slam tries after a raise, and Blackwood
+1H 3H : 4NT : hcp >= 19 : Blackwood, asking for aces
+1H 3H : 4H : hcp >= 14 : Accepts the invitation
+1S 3S : 4NT : hcp >= 19 : Blackwood, asking for aces
+1S 3S : 4S : hcp >= 14 : Accepts the invitation
+... 4NT : 5C : hascard(AS) + hascard(AH) + hascard(AD) + hascard(AC) == 0 : No aces, or all four
+... 4NT : 5C : hascard(AS) + hascard(AH) + hascard(AD) + hascard(AC) == 4 : No aces, or all four
+... 4NT : 5D : hascard(AS) + hascard(AH) + hascard(AD) + hascard(AC) == 1 : One ace
+... 4NT : 5H : hascard(AS) + hascard(AH) + hascard(AD) + hascard(AC) == 2 : Two aces
+... 4NT : 5S : hascard(AS) + hascard(AH) + hascard(AD) + hascard(AC) == 3 : Three aces
+1H 3H 4NT 5S : 6H : : Small slam, missing an ace
+1H 3H 4NT 5H : 6H : : Small slam, missing an ace
+1H 3H 4NT * : 5H : : Signs off, missing two aces
+1S 3S 4NT 5S : 6S : : Small slam, missing an ace
+1S 3S 4NT 5H : 6S : : Small slam, missing an ace
+1S 3S 4NT * : 5S : : Signs off, missing two aces
+1H 4NT 5S : 6H : : Small slam, missing an ace
+1H 4NT 5H : 6H : : Small slam, missing an ace
+1H 4NT * : 5H : : Signs off, missing two aces
+1S 4NT 5S : 6S : : Small slam, missing an ace
+1S 4NT 5H : 6S : : Small slam, missing an ace
+1S 4NT * : 5S : : Signs off, missing two aces
+";
+
+/// One rule of a bidding system: in an auction that matches, the call is chosen if the hand
+/// meets the condition.
+///
+/// A rule is written on one line as `auction : call : condition : explanation`. The
+/// auction is the calls made by the bidder's side so far, leaving out any passes before the
+/// opening bid, with `-` for none, `*` matching any single call and a leading `...` matching
+/// anything before the calls that follow. The condition uses the deal generator's language
+/// without seats, such as `hcp >= 15 && balanced && spades <= 4`, and an empty condition
+/// always holds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BiddingRule {
+    /// The calls to match, with None for `*`.
+    auction: Vec<Option<Call>>,
+    any_before: bool,
+    pub call: Call,
+    condition: Option<HandCondition>,
+    condition_text: String,
+    pub explanation: String,
+}
+
+impl BiddingRule {
+    pub fn parse(line: &str) -> Result<BiddingRule, String> {
+        let fields: Vec<&str> = line.splitn(4, ':').map(str::trim).collect();
+        if fields.len() != 4 {
+            return Err("expected auction : call : condition : explanation".to_string());
+        }
+
+        let mut any_before = false;
+        let mut auction = Vec::new();
+        for (i, token) in fields[0].split_whitespace().enumerate() {
+            match token {
+                "-" if fields[0] == "-" => {}
+                "..." if i == 0 => any_before = true,
+                "*" => auction.push(None),
+                _ => auction.push(Some(
+                    Call::from_pbn(token).ok_or(format!("unknown call {}", token))?,
+                )),
+            }
+        }
+        let call = Call::from_pbn(fields[1]).ok_or(format!("unknown call {}", fields[1]))?;
+        let condition = match fields[2] {
+            "" => None,
+            text => Some(HandCondition::parse(text)?),
+        };
+
+        Ok(BiddingRule {
+            auction,
+            any_before,
+            call,
+            condition,
+            condition_text: fields[2].to_string(),
+            explanation: fields[3].to_string(),
+        })
+    }
+
+    /// Returns true if the rule applies to the calls made by the bidder's side.
+    pub fn matches(&self, calls: &[Call], hand: &Pile) -> bool {
+        let pattern = self.auction.len();
+        let fits = if self.any_before {
+            calls.len() >= pattern
+        } else {
+            calls.len() == pattern
+        };
+        fits && self
+            .auction
+            .iter()
+            .zip(calls[calls.len() - pattern..].iter())
+            .all(|(expected, call)| expected.map_or(true, |expected| expected == *call))
+            && self.condition.as_ref().map_or(true, |c| c.matches(hand))
+    }
+}
+
+/// Writes the rule back out as a line of a rules file.
+impl fmt::Display for BiddingRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut auction: Vec<String> = self
+            .auction
+            .iter()
+            .map(|call| call.map_or("*".to_string(), |c| c.to_pbn()))
+            .collect();
+        if self.any_before {
+            auction.insert(0, "...".to_string());
+        }
+        if auction.is_empty() {
+            auction.push("-".to_string());
+        }
+        write!(
+            f,
+            "{} : {} : {} : {}",
+            auction.join(" "),
+            self.call,
+            self.condition_text,
+            self.explanation
+        )
+    }
+}
+
+/// A call chosen by a BiddingSystem, along with why.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BidChoice {
+    pub call: Call,
+    pub explanation: String,
+}
+
+/// A computer bidder that follows a set of rules, for teaching. The rules are plain text, one
+/// per line as described in BiddingRule, so that they can be edited without touching the
+/// code; blank lines and lines starting with `#` are ignored.
+///
+/// The first rule that matches the auction and the hand, and whose call is legal, is chosen.
+/// When no rule matches, or the opponents have done anything other than pass, the bidder
+/// passes. `BiddingSystem::standard()` has the rules in `STANDARD_RULES`.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{Auction, BiddingSystem, BridgeBoard, BridgeDirection, Call, Strain};
+///
+/// let board = BridgeBoard::from_pbn_deal(
+///     "N:AK93.KQ5.A843.73 QT5.T97.K962.862 J872.AJ8.QT.KJ95 64.6432.J75.AQT4",
+/// );
+/// let system = BiddingSystem::standard();
+/// let mut auction = Auction::new(BridgeDirection::N);
+///
+/// let choice = system.choose(&board.north, &auction);
+/// assert_eq!(Call::BID(1, Strain::NT), choice.call);
+/// assert_eq!("15 to 17 points and a balanced hand", choice.explanation);
+///
+/// auction.call(choice.call);
+/// auction.call(Call::PASS);
+/// assert_eq!(Call::BID(2, Strain::C), system.choose(&board.south, &auction).call);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BiddingSystem {
+    rules: Vec<BiddingRule>,
+}
+
+impl BiddingSystem {
+    pub fn new() -> BiddingSystem {
+        BiddingSystem::default()
+    }
+
+    pub fn standard() -> BiddingSystem {
+        BiddingSystem::from_rules(STANDARD_RULES).expect("the standard rules parse")
+    }
+
+    /// Reads a system from its rules, reporting the line of the first rule that can't be
+    /// parsed.
+    pub fn from_rules(rules: &str) -> Result<BiddingSystem, ScriptError> {
+        let mut system = BiddingSystem::new();
+        for (i, line) in rules.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let rule = BiddingRule::parse(line).map_err(|message| ScriptError {
+                line: i + 1,
+                message,
+            })?;
+            system.rules.push(rule);
+        }
+        Ok(system)
+    }
+
+    pub fn rules(&self) -> &Vec<BiddingRule> {
+        &self.rules
+    }
+
+    /// Adds a rule after the existing ones.
+    pub fn add_rule(&mut self, rule: BiddingRule) {
+        self.rules.push(rule);
+    }
+
+    /// Adds a rule that is tried before the existing ones.
+    pub fn insert_rule(&mut self, rule: BiddingRule) {
+        self.rules.insert(0, rule);
+    }
+
+    /// Chooses the next call in the auction for a player holding the hand.
+    pub fn choose(&self, hand: &Pile, auction: &Auction) -> BidChoice {
+        let pass = |explanation: &str| BidChoice {
+            call: Call::PASS,
+            explanation: explanation.to_string(),
+        };
+        let bidder = auction.next_caller();
+        let mut ours = Vec::new();
+        for (position, call) in auction.calls().iter().enumerate() {
+            if auction.caller(position).side() == bidder.side() {
+                if !ours.is_empty() || *call != Call::PASS {
+                    ours.push(*call);
+                }
+            } else if *call != Call::PASS {
+                return pass("The opponents have entered the auction, which no rule covers");
+            }
+        }
+
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(&ours, hand) && auction.is_legal(&rule.call))
+            .map(|rule| BidChoice {
+                call: rule.call,
+                explanation: rule.explanation.clone(),
+            })
+            .unwrap_or_else(|| pass("No rule applies"))
+    }
+}
+
+/// Writes out the rules, one per line.
+impl fmt::Display for BiddingSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for rule in self.rules.iter() {
+            writeln!(f, "{}", rule)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_bidding_tests {
+    use super::*;
+    use crate::cards::decks::bridge::contract::Strain;
+    use crate::cards::decks::bridge::{BridgeBoard, BridgeDirection};
+
+    fn auction(dealer: BridgeDirection, calls: &str) -> Auction {
+        let mut auction = Auction::new(dealer);
+        for call in calls.split_whitespace() {
+            assert!(auction.call(Call::from_pbn(call).unwrap()), "{}", call);
+        }
+        auction
+    }
+
+    fn hand(hand: &str) -> Pile {
+        BridgeBoard::from_pbn_deal(&format!("N:{} - - -", hand)).north
+    }
+
+    fn choose(hand_text: &str, calls: &str) -> Call {
+        BiddingSystem::standard()
+            .choose(&hand(hand_text), &auction(BridgeDirection::N, calls))
+            .call
+    }
+
+    #[test]
+    fn standard() {
+        let system = BiddingSystem::standard();
+
+        assert!(system.rules().len() > 50);
+        assert_eq!(
+            system,
+            BiddingSystem::from_rules(&system.to_string()).unwrap()
+        );
+    }
+
+    #[test]
+    fn rule__parse() {
+        let rule = BiddingRule::parse("... 1NT * : 3NT : hcp >= 10 : Game").unwrap();
+
+        assert_eq!(Call::BID(3, Strain::NT), rule.call);
+        assert_eq!("Game", rule.explanation);
+        assert_eq!("... 1NT * : 3NT : hcp >= 10 : Game", rule.to_string());
+        assert!(BiddingRule::parse("- : 8S : : Too high").is_err());
+        assert!(BiddingRule::parse("- : 1S : hcp(north) > 1 : Seat").is_err());
+        assert!(BiddingRule::parse("- : 1S").is_err());
+    }
+
+    #[test]
+    fn rule__matches() {
+        let rule = BiddingRule::parse("... 4NT : 5H : : Two aces").unwrap();
+        let opening = BiddingRule::parse("- : 1C : : Anything").unwrap();
+        let calls: Vec<Call> = "1S 3S 4NT"
+            .split_whitespace()
+            .map(|c| Call::from_pbn(c).unwrap())
+            .collect();
+        let hand = Pile::default();
+
+        assert!(rule.matches(&calls, &hand));
+        assert!(!rule.matches(&calls[..2], &hand));
+        assert!(opening.matches(&[], &hand));
+        assert!(!opening.matches(&calls, &hand));
+    }
+
+    #[test]
+    fn from_rules__errors() {
+        let error =
+            BiddingSystem::from_rules("# Openings\n- : 1C : : Fine\n- : 1Z : : Oops").unwrap_err();
+
+        assert_eq!(3, error.line);
+    }
+
+    #[test]
+    fn choose__openings() {
+        assert_eq!("1NT", choose("AK93.KQ5.A843.J7", "").to_string());
+        assert_eq!("1S", choose("AKJ93.K5.A843.J7", "").to_string());
+        assert_eq!("1H", choose("AK9.KQJ75.843.J7", "Pass Pass").to_string());
+        assert_eq!("1D", choose("AK9.K75.Q843.J73", "").to_string());
+        assert_eq!("1C", choose("AK9.K75.984.QJ73", "").to_string());
+        assert_eq!("2C", choose("AKQ9.AK5.AQ4.KJ7", "").to_string());
+        assert_eq!("2H", choose("93.KQJ975.843.J7", "").to_string());
+        assert_eq!("Pass", choose("93.K9875.843.J72", "").to_string());
+    }
+
+    #[test]
+    fn choose__no_trump_responses() {
+        assert_eq!("2D", choose("93.KQ975.843.J72", "1NT Pass").to_string());
+        assert_eq!("2C", choose("K932.KQ5.843.J72", "1NT Pass").to_string());
+        assert_eq!("3NT", choose("K93.KQ5.A843.J72", "1NT Pass").to_string());
+        assert_eq!(
+            "2S",
+            choose("AK93.Q5.A843.KJ7", "1NT Pass 2C Pass").to_string()
+        );
+        assert_eq!(
+            "2H",
+            choose("AK9.Q53.A843.KJ7", "1NT Pass 2D Pass").to_string()
+        );
+        assert_eq!(
+            "4S",
+            choose("K932.KQ5.843.AJ2", "1NT Pass 2C Pass 2S Pass").to_string()
+        );
+        assert_eq!(
+            "3NT",
+            choose("K932.KQ5.843.AJ2", "1NT Pass 2C Pass 2D Pass").to_string()
+        );
+    }
+
+    #[test]
+    fn choose__no_trump_invitations() {
+        let maximum = "AK93.KQ5.A84.J73";
+        let minimum = "AK93.KQ5.Q84.J73";
+
+        assert_eq!("3NT", choose(maximum, "1NT Pass 2NT Pass").to_string());
+        assert_eq!("Pass", choose(minimum, "1NT Pass 2NT Pass").to_string());
+        assert_eq!(
+            "4S",
+            choose(maximum, "1NT Pass 2C Pass 2S Pass 3S Pass").to_string()
+        );
+        assert_eq!(
+            "Pass",
+            choose(minimum, "1NT Pass 2C Pass 2S Pass 3S Pass").to_string()
+        );
+        assert_eq!(
+            "4H",
+            choose("AK9.KQ5.A843.J73", "1NT Pass 2D Pass 2H Pass 2NT Pass").to_string()
+        );
+        assert_eq!(
+            "3NT",
+            choose("AK93.K5.AQ84.J73", "1NT Pass 2D Pass 2H Pass 2NT Pass").to_string()
+        );
+        assert_eq!(
+            "3S",
+            choose(minimum, "1NT Pass 2H Pass 2S Pass 2NT Pass").to_string()
+        );
+        assert_eq!(
+            "4S",
+            choose(minimum, "1NT Pass 2H Pass 2S Pass 3NT Pass").to_string()
+        );
+        assert_eq!(
+            "Pass",
+            choose("AK.KQ53.Q843.J73", "1NT Pass 2H Pass 2S Pass 3NT Pass").to_string()
+        );
+    }
+
+    #[test]
+    fn choose__strong_responses() {
+        let system = BiddingSystem::standard();
+        let strong = system.choose(
+            &hand("AKQ.KQ5.A432.K72"),
+            &auction(BridgeDirection::N, "1NT Pass"),
+        );
+
+        assert_eq!("6NT", strong.call.to_string());
+        assert_eq!("Small slam", strong.explanation);
+        assert_eq!("4NT", choose("AK3.KQ5.Q432.K72", "1NT Pass").to_string());
+        assert_eq!("7NT", choose("AKQ.AQ5.A432.K72", "1NT Pass").to_string());
+        assert_eq!("Pass", choose("Q93.J95.8643.J72", "1NT Pass").to_string());
+        assert_eq!(
+            "6NT",
+            choose("AK93.KQ5.A84.J73", "1NT Pass 4NT Pass").to_string()
+        );
+        assert_eq!(
+            "Pass",
+            choose("AK93.KQ5.Q84.J73", "1NT Pass 4NT Pass").to_string()
+        );
+
+        let slam = system.choose(
+            &hand("A92.KQ93.AQ4.K72"),
+            &auction(BridgeDirection::N, "1H Pass"),
+        );
+        assert_eq!("4NT", slam.call.to_string());
+        assert_eq!(
+            "Blackwood, with four hearts and slam values",
+            slam.explanation
+        );
+        assert_eq!("4H", choose("A92.KQ93.Q84.K72", "1H Pass").to_string());
+        assert_eq!("4S", choose("KQ93.A92.Q84.K72", "1S Pass").to_string());
+        assert_eq!(
+            "6H",
+            choose("A92.KQ93.AQ4.K72", "1H Pass 4NT Pass 5H Pass").to_string()
+        );
+        assert_eq!(
+            "5H",
+            choose("A92.KQ93.AQ4.K72", "1H Pass 4NT Pass 5D Pass").to_string()
+        );
+    }
+
+    #[test]
+    fn choose__blackwood() {
+        assert_eq!(
+            "4NT",
+            choose("AKJ93.AK5.KQ4.J7", "1S Pass 3S Pass").to_string()
+        );
+        assert_eq!(
+            "5H",
+            choose("Q842.A95.A843.72", "1S Pass 3S Pass 4NT Pass").to_string()
+        );
+        assert_eq!(
+            "6S",
+            choose("AKJ93.K5.KQ4.KJ7", "1S Pass 3S Pass 4NT Pass 5H Pass").to_string()
+        );
+    }
+
+    #[test]
+    fn choose__explanation() {
+        let system = BiddingSystem::standard();
+        let hand = hand("93.KQ975.843.J72");
+
+        let transfer = system.choose(&hand, &auction(BridgeDirection::N, "1NT Pass"));
+        let contested = system.choose(&hand, &auction(BridgeDirection::N, "1NT 2S"));
+
+        assert_eq!("Jacoby transfer, five or more hearts", transfer.explanation);
+        assert_eq!(Call::PASS, contested.call);
+        assert!(contested.explanation.contains("opponents"));
+    }
+
+    #[test]
+    fn choose__custom_rules() {
+        let mut system = BiddingSystem::standard();
+        system.insert_rule(
+            BiddingRule::parse("- : 3H : hearts >= 7 && hcp <= 10 : Preempt").unwrap(),
+        );
+
+        let choice = system.choose(
+            &hand("9.KQJ9752.843.J7"),
+            &auction(BridgeDirection::N, "Pass"),
+        );
+
+        assert_eq!(Call::BID(3, Strain::H), choice.call);
+        assert_eq!("Preempt", choice.explanation);
+    }
+}
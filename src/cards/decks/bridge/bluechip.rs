@@ -0,0 +1,928 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::cards::card::Card;
+use crate::cards::decks::bridge::auction::{Auction, Call};
+use crate::cards::decks::bridge::contract::{Contract, Strain};
+use crate::cards::decks::bridge::evaluation::HandEvaluation;
+use crate::cards::decks::bridge::play::{BridgePlay, Trick};
+use crate::cards::decks::bridge::{
+    card_from_chars, holding_text, seat_index, split_once, suit_char_index, suit_index,
+    BridgeBoard, BridgeDirection, BridgeSide, Vulnerability, SUIT_CHARS,
+};
+use crate::cards::pile::Pile;
+
+const PROTOCOL_VERSION: usize = 18;
+
+/// How long the table manager waits for a player to send or take a message by default.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often the table manager checks for a player connecting.
+const ACCEPT_POLL: Duration = Duration::from_millis(10);
+
+/// The outcome of one board at the table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableResult {
+    pub board: usize,
+    pub auction: Auction,
+    /// The final contract, or None if the board was passed out.
+    pub contract: Option<Contract>,
+    pub declarer_tricks: usize,
+    /// The score from North-South's point of view.
+    pub score: isize,
+}
+
+/// The table manager, or server, side of the Blue Chip Bridge protocol.
+///
+/// Four clients connect, one for each seat, and the table manager deals them each board in
+/// turn, passes on every call and card to the other players, shows dummy after the opening
+/// lead and checks that everything played is legal. The conversation for each board goes:
+///
+/// ```text
+/// North ready to start              Start of board
+/// North ready for deal              Board number 1. Dealer North. Neither vulnerable.
+/// North ready for cards             North's cards : S A K 3. H 4 2. D -. C A Q J T 9 8 7 2.
+/// North bids 1C                     (sent to the others when they are ready for it)
+/// East ready for North's bid        North bids 1C
+/// East plays 5D                     (the opening lead)
+/// South ready for dummy             Dummy's cards : S Q J. H ...
+/// North ready for dummy's card to trick 1
+/// ```
+///
+/// Declarer sends the cards dummy plays. After the last board every client is told
+/// `End of session`.
+///
+/// A seat that hasn't connected, or a player that hasn't sent or taken a message, within the
+/// timeout, a minute unless changed with `set_timeout()`, ends the session with a `TimedOut`
+/// error.
+///
+/// # Usage:
+/// ```
+/// use std::thread;
+/// use cardpack::{BasicRobot, BlueChipClient, BridgeBoard, BridgeDirection, TableManager};
+///
+/// let manager = TableManager::bind("127.0.0.1:0").unwrap();
+/// let address = manager.local_addr().unwrap();
+///
+/// let players: Vec<_> = BridgeDirection::seats()
+///     .into_iter()
+///     .map(|seat| {
+///         thread::spawn(move || {
+///             let mut client =
+///                 BlueChipClient::connect(address, seat, "Robots", BasicRobot).unwrap();
+///             client.run().unwrap()
+///         })
+///     })
+///     .collect();
+///
+/// let results = manager.run(&[BridgeBoard::deal_board(1)]).unwrap();
+///
+/// for player in players {
+///     assert_eq!(1, player.join().unwrap());
+/// }
+/// assert_eq!(1, results.len());
+/// ```
+#[derive(Debug)]
+pub struct TableManager {
+    listener: TcpListener,
+    timeout: Option<Duration>,
+}
+
+impl TableManager {
+    pub fn bind<A: ToSocketAddrs>(address: A) -> io::Result<TableManager> {
+        Ok(TableManager {
+            listener: TcpListener::bind(address)?,
+            timeout: Some(DEFAULT_TIMEOUT),
+        })
+    }
+
+    /// Sets how long to wait for each player to connect and for each message to or from a
+    /// player, or None to wait for ever.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Waits for the next player to connect, polling so that it can give up with a
+    /// `TimedOut` error once the timeout has passed.
+    fn accept(&self) -> io::Result<TcpStream> {
+        let timeout = match self.timeout {
+            Some(timeout) => timeout,
+            None => return self.listener.accept().map(|(stream, _)| stream),
+        };
+        let deadline = Instant::now() + timeout;
+        self.listener.set_nonblocking(true)?;
+        let accepted = loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => break Ok(stream),
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                    if Instant::now() >= deadline {
+                        break Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            "no player connected in time",
+                        ));
+                    }
+                    thread::sleep(ACCEPT_POLL);
+                }
+                Err(error) => break Err(error),
+            }
+        };
+        self.listener.set_nonblocking(false)?;
+        let stream = accepted?;
+        stream.set_nonblocking(false)?;
+        Ok(stream)
+    }
+
+    /// Waits for a player in each seat, plays the boards and returns their results.
+    pub fn run(&self, boards: &[BridgeBoard]) -> io::Result<Vec<TableResult>> {
+        if boards.iter().any(|board| !board.is_valid()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "every board must be a complete deal",
+            ));
+        }
+
+        let mut table: Vec<Option<(Connection, String)>> = vec![None, None, None, None];
+        for _ in 0..4 {
+            let stream = self.accept()?;
+            let mut connection = Connection::new(stream, self.timeout)?;
+            let line = connection.receive()?;
+            let (seat, team) = parse_connecting(&line)
+                .ok_or_else(|| invalid(format!("unexpected message: {}", line)))?;
+            let index = seat_index(&seat).unwrap_or(0);
+            if table[index].is_some() {
+                return Err(invalid(format!("{} is already taken", seat_name(&seat))));
+            }
+            connection.send(&format!("{} (\"{}\") seated", seat_name(&seat), team))?;
+            table[index] = Some((connection, team));
+        }
+
+        let mut teams: Vec<String> = Vec::new();
+        let mut connections: Vec<Connection> = Vec::new();
+        for (connection, team) in table.into_iter().flatten() {
+            connections.push(connection);
+            teams.push(team);
+        }
+        let teams = format!("Teams : N/S : \"{}\" E/W : \"{}\"", teams[0], teams[1]);
+        for (seat, connection) in BridgeDirection::seats().iter().zip(connections.iter_mut()) {
+            connection.expect(&format!("{} ready for teams", seat_name(seat)))?;
+            connection.send(&teams)?;
+        }
+
+        let mut results = Vec::new();
+        for board in boards.iter() {
+            results.push(play_board(&mut connections, board)?);
+        }
+        for (seat, connection) in BridgeDirection::seats().iter().zip(connections.iter_mut()) {
+            connection.expect(&format!("{} ready to start", seat_name(seat)))?;
+            connection.send("End of session")?;
+        }
+        Ok(results)
+    }
+}
+
+/// What a player can see at the table: their own cards, dummy once it is shown, the
+/// auction and the tricks played so far.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableView {
+    pub seat: BridgeDirection,
+    pub board: usize,
+    pub dealer: BridgeDirection,
+    pub vulnerability: Vulnerability,
+    pub hand: Pile,
+    pub dummy: Option<Pile>,
+    pub auction: Auction,
+    pub tricks: Vec<Trick>,
+}
+
+impl TableView {
+    pub fn contract(&self) -> Option<Contract> {
+        self.auction.contract()
+    }
+
+    /// The seat whose turn it is to play, once the auction has ended in a contract.
+    pub fn to_play(&self) -> Option<BridgeDirection> {
+        let contract = self.contract()?;
+        match self.tricks.last() {
+            None => Some(contract.opening_leader()),
+            Some(trick) if trick.is_complete() => trick.winner(&contract.strain),
+            Some(trick) => Some(trick.player(trick.cards.len())),
+        }
+    }
+
+    /// The cards that may be played from the hand whose turn it is, if this player can see
+    /// it.
+    pub fn legal_plays(&self) -> Pile {
+        let to_play = match self.to_play() {
+            Some(seat) => seat,
+            None => return Pile::default(),
+        };
+        let hand = if to_play == self.seat {
+            &self.hand
+        } else {
+            match (&self.dummy, self.contract()) {
+                (Some(dummy), Some(contract)) if contract.dummy() == to_play => dummy,
+                _ => return Pile::default(),
+            }
+        };
+        let led = self
+            .tricks
+            .last()
+            .filter(|trick| !trick.is_complete())
+            .and_then(|trick| trick.led_suit_index());
+        let following: Vec<Card> = hand
+            .values()
+            .filter(|card| led.is_some() && suit_index(card) == led)
+            .cloned()
+            .collect();
+        if following.is_empty() {
+            hand.clone()
+        } else {
+            Pile::new_from_vector(following)
+        }
+    }
+
+    fn completed_tricks(&self) -> usize {
+        self.tricks
+            .iter()
+            .filter(|trick| trick.is_complete())
+            .count()
+    }
+
+    fn record(&mut self, seat: &BridgeDirection, card: &Card) {
+        if self.tricks.last().map_or(true, |trick| trick.is_complete()) {
+            self.tricks.push(Trick::new(*seat));
+        }
+        if let Some(trick) = self.tricks.last_mut() {
+            trick.cards.push(card.clone());
+        }
+        if *seat == self.seat {
+            self.hand.remove_card(card);
+        } else if let Some(dummy) = self.dummy.as_mut() {
+            dummy.remove_card(card);
+        }
+    }
+}
+
+/// A player that decides the calls and plays for a client.
+pub trait Robot {
+    fn call(&mut self, view: &TableView) -> Call;
+
+    /// Picks a card for the seat returned by `view.to_play()`, which is either the robot's
+    /// own seat or, when declaring, dummy.
+    fn play(&mut self, view: &TableView) -> Card;
+}
+
+/// A very simple robot: it opens one of its longest suit with 12 or more points if nobody
+/// has bid yet, otherwise passes, and always plays the first legal card.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BasicRobot;
+
+impl Robot for BasicRobot {
+    fn call(&mut self, view: &TableView) -> Call {
+        if view.auction.last_bid().is_some() || view.hand.hcp() < 12 {
+            return Call::PASS;
+        }
+        let lengths = view.hand.suit_lengths();
+        let longest = (0..4).max_by_key(|i| (lengths[*i], 4 - i)).unwrap_or(0);
+        match Strain::from_suit_index(longest) {
+            Some(strain) => Call::BID(1, strain),
+            None => Call::PASS,
+        }
+    }
+
+    fn play(&mut self, view: &TableView) -> Card {
+        view.legal_plays()
+            .first()
+            .cloned()
+            .unwrap_or_else(|| view.hand.first().cloned().unwrap_or_default())
+    }
+}
+
+/// The client side of the Blue Chip Bridge protocol, which plays one seat for a robot.
+#[derive(Debug)]
+pub struct BlueChipClient<R: Robot> {
+    connection: Connection,
+    seat: BridgeDirection,
+    robot: R,
+}
+
+impl<R: Robot> BlueChipClient<R> {
+    /// Connects to a table manager and takes a seat.
+    pub fn connect<A: ToSocketAddrs>(
+        address: A,
+        seat: BridgeDirection,
+        team: &str,
+        robot: R,
+    ) -> io::Result<BlueChipClient<R>> {
+        let mut connection = Connection::new(TcpStream::connect(address)?, None)?;
+        let name = seat_name(&seat);
+        connection.send(&format!(
+            "Connecting \"{}\" as {} using protocol version {}",
+            team, name, PROTOCOL_VERSION
+        ))?;
+        connection.expect(&format!("{} (\"{}\") seated", name, team))?;
+        connection.send(&format!("{} ready for teams", name))?;
+        let teams = connection.receive()?;
+        if !teams.starts_with("Teams") {
+            return Err(invalid(format!("unexpected message: {}", teams)));
+        }
+        Ok(BlueChipClient {
+            connection,
+            seat,
+            robot,
+        })
+    }
+
+    /// Plays boards until the table manager ends the session, returning how many were
+    /// played.
+    pub fn run(&mut self) -> io::Result<usize> {
+        let mut boards = 0;
+        while self.play_board()? {
+            boards += 1;
+        }
+        Ok(boards)
+    }
+
+    fn play_board(&mut self) -> io::Result<bool> {
+        let me = seat_name(&self.seat);
+        self.connection.send(&format!("{} ready to start", me))?;
+        let line = self.connection.receive()?;
+        if line == "End of session" {
+            return Ok(false);
+        }
+        if line != "Start of board" {
+            return Err(invalid(format!("unexpected message: {}", line)));
+        }
+
+        self.connection.send(&format!("{} ready for deal", me))?;
+        let line = self.connection.receive()?;
+        let (board, dealer, vulnerability) =
+            parse_deal(&line).ok_or_else(|| invalid(format!("unexpected message: {}", line)))?;
+        self.connection.send(&format!("{} ready for cards", me))?;
+        let line = self.connection.receive()?;
+        let prefix = format!("{}'s cards : ", me);
+        let hand = line
+            .strip_prefix(&prefix)
+            .and_then(parse_hand)
+            .ok_or_else(|| invalid(format!("unexpected message: {}", line)))?;
+
+        let mut view = TableView {
+            seat: self.seat,
+            board,
+            dealer,
+            vulnerability,
+            hand,
+            dummy: None,
+            auction: Auction::new(dealer),
+            tricks: Vec::new(),
+        };
+
+        while !view.auction.is_complete() {
+            let bidder = view.auction.next_caller();
+            if bidder == self.seat {
+                let mut call = self.robot.call(&view);
+                if !view.auction.is_legal(&call) {
+                    call = Call::PASS;
+                }
+                self.connection.send(&call_message(&bidder, &call))?;
+                view.auction.call(call);
+            } else {
+                self.connection
+                    .send(&format!("{} ready for {}'s bid", me, seat_name(&bidder)))?;
+                let line = self.connection.receive()?;
+                match parse_call(&line) {
+                    Some((seat, call)) if seat == bidder && view.auction.call(call) => {}
+                    _ => return Err(invalid(format!("unexpected message: {}", line))),
+                }
+            }
+        }
+
+        let contract = match view.contract() {
+            Some(contract) => contract,
+            None => return Ok(true),
+        };
+        for card in 0..52 {
+            let player = view
+                .to_play()
+                .ok_or_else(|| invalid("lost track of the play".to_string()))?;
+            let controller = if player == contract.dummy() {
+                contract.declarer
+            } else {
+                player
+            };
+            if controller == self.seat {
+                let mut choice = self.robot.play(&view);
+                let legal = view.legal_plays();
+                if !legal.contains(&choice) {
+                    choice = legal.first().cloned().unwrap_or(choice);
+                }
+                self.connection.send(&play_message(&player, &choice))?;
+                view.record(&player, &choice);
+            } else {
+                let about = if player == contract.dummy() {
+                    "dummy".to_string()
+                } else {
+                    seat_name(&player).to_string()
+                };
+                self.connection.send(&format!(
+                    "{} ready for {}'s card to trick {}",
+                    me,
+                    about,
+                    view.completed_tricks() + 1
+                ))?;
+                let line = self.connection.receive()?;
+                match parse_play(&line) {
+                    Some((seat, played)) if seat == player => view.record(&player, &played),
+                    _ => return Err(invalid(format!("unexpected message: {}", line))),
+                }
+            }
+
+            if card == 0 && self.seat != contract.dummy() {
+                self.connection.send(&format!("{} ready for dummy", me))?;
+                let line = self.connection.receive()?;
+                view.dummy = line.strip_prefix("Dummy's cards : ").and_then(parse_hand);
+                if view.dummy.is_none() {
+                    return Err(invalid(format!("unexpected message: {}", line)));
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
+fn play_board(connections: &mut [Connection], board: &BridgeBoard) -> io::Result<TableResult> {
+    let seats = BridgeDirection::seats();
+    for (seat, connection) in seats.iter().zip(connections.iter_mut()) {
+        let name = seat_name(seat);
+        connection.expect(&format!("{} ready to start", name))?;
+        connection.send("Start of board")?;
+        connection.expect(&format!("{} ready for deal", name))?;
+        connection.send(&deal_message(board))?;
+        connection.expect(&format!("{} ready for cards", name))?;
+        let hand = board.get_hand(seat).cloned().unwrap_or_default();
+        connection.send(&format!("{}'s cards : {}", name, hand_text(&hand)))?;
+    }
+
+    let mut auction = Auction::new(board.dealer);
+    while !auction.is_complete() {
+        let bidder = auction.next_caller();
+        let line = connection(connections, &bidder).receive()?;
+        let call = match parse_call(&line) {
+            Some((seat, call)) if seat == bidder && auction.call(call) => call,
+            _ => return Err(invalid(format!("unexpected call: {}", line))),
+        };
+        let message = call_message(&bidder, &call);
+        for other in bidder.clockwise().iter().skip(1) {
+            let connection = connection(connections, other);
+            connection.expect(&format!(
+                "{} ready for {}'s bid",
+                seat_name(other),
+                seat_name(&bidder)
+            ))?;
+            connection.send(&message)?;
+        }
+    }
+
+    let contract = auction.contract();
+    let mut declarer_tricks = 0;
+    let mut score = 0;
+    if let Some(contract) = contract {
+        let dummy = contract.dummy();
+        let dummy_hand = board.get_hand(&dummy).cloned().unwrap_or_default();
+        let mut play = BridgePlay::new(board, contract);
+        while !play.is_complete() {
+            let player = play.to_play();
+            let controller = if player == dummy {
+                contract.declarer
+            } else {
+                player
+            };
+            let trick = play.tricks().iter().filter(|t| t.is_complete()).count() + 1;
+            let line = connection(connections, &controller).receive()?;
+            let card = match parse_play(&line) {
+                Some((seat, card)) if seat == player && play.play(&card) => card,
+                _ => return Err(invalid(format!("unexpected card: {}", line))),
+            };
+            let about = if player == dummy {
+                "dummy"
+            } else {
+                seat_name(&player)
+            };
+            let message = play_message(&player, &card);
+            for other in seats.iter().filter(|s| **s != controller) {
+                let connection = connection(connections, other);
+                connection.expect(&format!(
+                    "{} ready for {}'s card to trick {}",
+                    seat_name(other),
+                    about,
+                    trick
+                ))?;
+                connection.send(&message)?;
+            }
+
+            if play.tricks().len() == 1 && play.tricks()[0].cards.len() == 1 {
+                let dummy_message = format!("Dummy's cards : {}", hand_text(&dummy_hand));
+                for other in seats.iter().filter(|s| **s != dummy) {
+                    let connection = connection(connections, other);
+                    connection.expect(&format!("{} ready for dummy", seat_name(other)))?;
+                    connection.send(&dummy_message)?;
+                }
+            }
+        }
+        declarer_tricks = play.declarer_tricks();
+        let vulnerable = board.is_vulnerable(&contract.declarer);
+        score = contract.score(declarer_tricks, vulnerable);
+        if contract.declarer.side() != Some(BridgeSide::NS) {
+            score = -score;
+        }
+    }
+
+    Ok(TableResult {
+        board: board.number,
+        auction,
+        contract,
+        declarer_tricks,
+        score,
+    })
+}
+
+fn connection<'a>(connections: &'a mut [Connection], seat: &BridgeDirection) -> &'a mut Connection {
+    &mut connections[seat_index(seat).unwrap_or(0)]
+}
+
+/// A line based connection, with each message ended by a carriage return and line feed.
+#[derive(Debug)]
+struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    /// Wraps the stream, giving up on any read or write that takes longer than the timeout.
+    fn new(stream: TcpStream, timeout: Option<Duration>) -> io::Result<Connection> {
+        stream.set_read_timeout(timeout)?;
+        stream.set_write_timeout(timeout)?;
+        Ok(Connection {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+
+    fn send(&mut self, message: &str) -> io::Result<()> {
+        write!(self.writer, "{}\r\n", message)
+            .and_then(|_| self.writer.flush())
+            .map_err(timed_out)
+    }
+
+    fn receive(&mut self) -> io::Result<String> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line).map_err(timed_out)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "connection closed",
+            ));
+        }
+        Ok(line.trim_end().to_string())
+    }
+
+    fn expect(&mut self, expected: &str) -> io::Result<()> {
+        let line = self.receive()?;
+        if line == expected {
+            Ok(())
+        } else {
+            Err(invalid(format!(
+                "expected \"{}\", got \"{}\"",
+                expected, line
+            )))
+        }
+    }
+}
+
+/// Reports a read or write that ran out of time as `TimedOut`, which some platforms give as
+/// `WouldBlock`.
+fn timed_out(error: io::Error) -> io::Error {
+    match error.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+            io::Error::new(io::ErrorKind::TimedOut, "the player didn't respond in time")
+        }
+        _ => error,
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn seat_name(seat: &BridgeDirection) -> &'static str {
+    match seat {
+        BridgeDirection::N => "North",
+        BridgeDirection::E => "East",
+        BridgeDirection::S => "South",
+        BridgeDirection::W => "West",
+        BridgeDirection::UNKNOWN => "Unknown",
+    }
+}
+
+fn parse_seat(name: &str) -> Option<BridgeDirection> {
+    BridgeDirection::seats()
+        .into_iter()
+        .find(|seat| seat_name(seat) == name)
+}
+
+fn parse_connecting(line: &str) -> Option<(BridgeDirection, String)> {
+    let rest = line.strip_prefix("Connecting \"")?;
+    let (team, rest) = split_once(rest, "\"")?;
+    let rest = rest.strip_prefix(" as ")?;
+    let (seat, _) = split_once(rest, " ")?;
+    Some((parse_seat(seat)?, team.to_string()))
+}
+
+fn deal_message(board: &BridgeBoard) -> String {
+    let vulnerability = match board.vulnerability {
+        Vulnerability::NONE => "Neither",
+        Vulnerability::NS => "N/S",
+        Vulnerability::EW => "E/W",
+        Vulnerability::BOTH => "Both",
+    };
+    format!(
+        "Board number {}. Dealer {}. {} vulnerable.",
+        board.number,
+        seat_name(&board.dealer),
+        vulnerability
+    )
+}
+
+fn parse_deal(line: &str) -> Option<(usize, BridgeDirection, Vulnerability)> {
+    let rest = line.strip_prefix("Board number ")?;
+    let (number, rest) = split_once(rest, ". Dealer ")?;
+    let (dealer, rest) = split_once(rest, ". ")?;
+    let vulnerability = match rest.strip_suffix(" vulnerable.")? {
+        "Neither" => Vulnerability::NONE,
+        "N/S" => Vulnerability::NS,
+        "E/W" => Vulnerability::EW,
+        "Both" => Vulnerability::BOTH,
+        _ => return None,
+    };
+    Some((number.parse().ok()?, parse_seat(dealer)?, vulnerability))
+}
+
+/// A hand as each suit's ranks separated by spaces, such as `S A K 3. H 4 2. D -. C A Q.`
+fn hand_text(hand: &Pile) -> String {
+    let mut text = Vec::new();
+    for (i, letter) in SUIT_CHARS.chars().enumerate() {
+        let ranks = holding_text(hand, i);
+        let ranks = if ranks.is_empty() {
+            "-".to_string()
+        } else {
+            ranks
+                .chars()
+                .map(|c| c.to_string())
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        text.push(format!("{} {}.", letter, ranks));
+    }
+    text.join(" ")
+}
+
+fn parse_hand(text: &str) -> Option<Pile> {
+    let mut cards = Vec::new();
+    for part in text.split('.').map(str::trim).filter(|p| !p.is_empty()) {
+        let mut tokens = part.split_whitespace();
+        let letter = tokens.next()?.chars().next()?;
+        suit_char_index(letter)?;
+        for rank in tokens.filter(|t| *t != "-") {
+            cards.push(card_from_chars(letter, rank.parse().ok()?)?);
+        }
+    }
+    Some(Pile::new_from_vector(cards).sort())
+}
+
+fn call_message(seat: &BridgeDirection, call: &Call) -> String {
+    let name = seat_name(seat);
+    match call {
+        Call::PASS => format!("{} passes", name),
+        Call::DOUBLE => format!("{} doubles", name),
+        Call::REDOUBLE => format!("{} redoubles", name),
+        Call::BID(level, Strain::NT) => format!("{} bids {}NT", name, level),
+        Call::BID(_, _) => format!("{} bids {}", name, call.to_pbn()),
+    }
+}
+
+/// Parses a call, ignoring anything after it such as an alert.
+fn parse_call(line: &str) -> Option<(BridgeDirection, Call)> {
+    let mut words = line.split_whitespace();
+    let seat = parse_seat(words.next()?)?;
+    let call = match words.next()? {
+        "passes" => Call::PASS,
+        "doubles" => Call::DOUBLE,
+        "redoubles" => Call::REDOUBLE,
+        "bids" => Call::from_pbn(words.next()?.trim_end_matches('.'))?,
+        _ => return None,
+    };
+    Some((seat, call))
+}
+
+fn play_message(seat: &BridgeDirection, card: &Card) -> String {
+    format!("{} plays {}", seat_name(seat), card.index)
+}
+
+fn parse_play(line: &str) -> Option<(BridgeDirection, Card)> {
+    let (seat, card) = split_once(line, " plays ")?;
+    let mut chars = card.trim().chars();
+    let rank = chars.next()?;
+    Some((parse_seat(seat)?, card_from_chars(chars.next()?, rank)?))
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_bluechip_tests {
+    use super::*;
+    use std::thread;
+
+    const TOP_CARDS: &str = "N:AKQJ.AKQ.AKQ.AKQ T987.JT9.JT9.JT9 6543.876.876.876 2.5432.5432.5432";
+    const TEN_EACH: &str = "N:AKQJ.T98.T98.T98 T98.AKQJ.765.765 6543.76.AKQJ.432 72.5432.432.AKQJ";
+
+    fn seat_players(address: SocketAddr) -> Vec<thread::JoinHandle<io::Result<usize>>> {
+        BridgeDirection::seats()
+            .into_iter()
+            .map(|seat| {
+                thread::spawn(move || {
+                    let team = match seat.side() {
+                        Some(BridgeSide::NS) => "Norths",
+                        _ => "Easts",
+                    };
+                    BlueChipClient::connect(address, seat, team, BasicRobot)?.run()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hand_text() {
+        let board = BridgeBoard::from_pbn_deal(TOP_CARDS);
+
+        let text = super::hand_text(&board.west);
+
+        assert_eq!("S 2. H 5 4 3 2. D 5 4 3 2. C 5 4 3 2.", text);
+        assert_eq!(Some(board.west.clone()), parse_hand(&text));
+        assert_eq!(
+            "S -. H A K Q J T 9 8 7 6 5 4 3 2. D -. C -.",
+            super::hand_text(
+                &BridgeBoard::from_pbn_deal(
+                    "N:.AKQJT98765432.. AKQJT98765432... ..AKQJT98765432. ...AKQJT98765432"
+                )
+                .north
+            )
+        );
+    }
+
+    #[test]
+    fn messages() {
+        let mut board = BridgeBoard::from_pbn_deal(TOP_CARDS);
+        board.set_number(7);
+
+        assert_eq!(
+            "Board number 7. Dealer South. Both vulnerable.",
+            deal_message(&board)
+        );
+        assert_eq!(
+            Some((7, BridgeDirection::S, Vulnerability::BOTH)),
+            parse_deal(&deal_message(&board))
+        );
+        assert_eq!(
+            Some((BridgeDirection::E, "Team A".to_string())),
+            parse_connecting("Connecting \"Team A\" as East using protocol version 18")
+        );
+        assert_eq!(
+            "West bids 3NT",
+            call_message(&BridgeDirection::W, &Call::BID(3, Strain::NT))
+        );
+        assert_eq!(
+            Some((BridgeDirection::W, Call::BID(2, Strain::H))),
+            parse_call("West bids 2H Alert.")
+        );
+        assert_eq!(
+            Some((BridgeDirection::N, Call::REDOUBLE)),
+            parse_call("North redoubles")
+        );
+        assert_eq!(None, parse_call("North bids 8S"));
+        let card = card_from_chars('C', 'T').unwrap();
+        assert_eq!("South plays TC", play_message(&BridgeDirection::S, &card));
+        assert_eq!(
+            Some((BridgeDirection::S, card)),
+            parse_play("South plays TC")
+        );
+    }
+
+    #[test]
+    fn basic_robot__call() {
+        let board = BridgeBoard::from_pbn_deal(TOP_CARDS);
+        let mut view = TableView {
+            seat: BridgeDirection::N,
+            board: 1,
+            dealer: BridgeDirection::N,
+            vulnerability: Vulnerability::NONE,
+            hand: board.north.clone(),
+            dummy: None,
+            auction: Auction::new(BridgeDirection::N),
+            tricks: Vec::new(),
+        };
+
+        assert_eq!(Call::BID(1, Strain::S), BasicRobot.call(&view));
+
+        view.hand = board.east.clone();
+        assert_eq!(Call::PASS, BasicRobot.call(&view));
+    }
+
+    #[test]
+    fn table_manager() {
+        let manager = TableManager::bind("127.0.0.1:0").unwrap();
+        let players = seat_players(manager.local_addr().unwrap());
+        let mut passed_out = BridgeBoard::from_pbn_deal(TEN_EACH);
+        passed_out.set_number(2);
+        let mut top_cards = BridgeBoard::from_pbn_deal(TOP_CARDS);
+        top_cards.set_number(1);
+
+        let results = manager.run(&[top_cards, passed_out]).unwrap();
+
+        for player in players {
+            assert_eq!(2, player.join().unwrap().unwrap());
+        }
+        assert_eq!(2, results.len());
+        assert_eq!("1S Pass Pass Pass", results[0].auction.to_string());
+        assert_eq!("1S", results[0].contract.unwrap().to_pbn());
+        assert_eq!(13, results[0].declarer_tricks);
+        assert_eq!(260, results[0].score);
+        assert_eq!(2, results[1].board);
+        assert!(results[1].auction.is_passed_out());
+        assert_eq!(None, results[1].contract);
+        assert_eq!(0, results[1].score);
+    }
+
+    #[test]
+    fn table_manager__invalid_board() {
+        let manager = TableManager::bind("127.0.0.1:0").unwrap();
+
+        let result = manager.run(&[BridgeBoard::default()]);
+
+        assert_eq!(io::ErrorKind::InvalidInput, result.unwrap_err().kind());
+    }
+
+    #[test]
+    fn table_manager__timeout() {
+        let mut manager = TableManager::bind("127.0.0.1:0").unwrap();
+        manager.set_timeout(Some(Duration::from_millis(100)));
+        let address = manager.local_addr().unwrap();
+        // Connects but never says anything, and hangs on until the table manager gives up.
+        let silent = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            io::copy(&mut stream, &mut io::sink()).is_ok()
+        });
+
+        let result = manager.run(&[BridgeBoard::deal_board(1)]);
+
+        assert_eq!(io::ErrorKind::TimedOut, result.unwrap_err().kind());
+        drop(manager);
+        assert!(silent.join().unwrap());
+    }
+
+    #[test]
+    fn table_manager__no_players() {
+        let mut manager = TableManager::bind("127.0.0.1:0").unwrap();
+        manager.set_timeout(Some(Duration::from_millis(100)));
+
+        let result = manager.run(&[BridgeBoard::deal_board(1)]);
+
+        assert_eq!(io::ErrorKind::TimedOut, result.unwrap_err().kind());
+    }
+
+    #[test]
+    fn table_manager__seat_taken() {
+        let manager = TableManager::bind("127.0.0.1:0").unwrap();
+        let address = manager.local_addr().unwrap();
+        let players: Vec<_> = (0..2)
+            .map(|_| {
+                thread::spawn(move || {
+                    BlueChipClient::connect(address, BridgeDirection::N, "Twins", BasicRobot)
+                        .map(|_| ())
+                })
+            })
+            .collect();
+
+        let result = manager.run(&[BridgeBoard::deal_board(1)]);
+
+        assert_eq!(io::ErrorKind::InvalidData, result.unwrap_err().kind());
+        // Neither client gets a table: the second is turned away and the first loses its
+        // connection when the table manager gives up.
+        for player in players {
+            assert!(player.join().unwrap().is_err());
+        }
+    }
+}
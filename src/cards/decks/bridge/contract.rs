@@ -0,0 +1,391 @@
+use std::fmt;
+
+use crate::cards::decks::bridge::{BridgeDirection, BRIDGE_SUITS};
+use crate::cards::suit::Suit;
+
+/// The denomination of a Bridge contract: one of the four suits or no trump.
+///
+/// Strains are ordered from lowest to highest as they rank in the auction.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Strain {
+    C,
+    D,
+    H,
+    S,
+    NT,
+}
+
+impl Strain {
+    /// Returns the strains in trick table order: spades, hearts, diamonds, clubs, no trump.
+    pub fn all() -> Vec<Strain> {
+        vec![Strain::S, Strain::H, Strain::D, Strain::C, Strain::NT]
+    }
+
+    pub fn to(s: &str) -> Option<Strain> {
+        match s.trim().to_uppercase().as_str() {
+            "S" => Some(Strain::S),
+            "H" => Some(Strain::H),
+            "D" => Some(Strain::D),
+            "C" => Some(Strain::C),
+            "N" | "NT" => Some(Strain::NT),
+            _ => None,
+        }
+    }
+
+    /// Returns the strain for a position in `BRIDGE_SUITS`.
+    pub fn from_suit_index(index: usize) -> Option<Strain> {
+        Strain::all().into_iter().filter(|s| s.is_suit()).nth(index)
+    }
+
+    /// Position of the strain in trick table order, which for the suits is the same as their
+    /// position in `BRIDGE_SUITS`.
+    pub fn index(&self) -> usize {
+        match self {
+            Strain::S => 0,
+            Strain::H => 1,
+            Strain::D => 2,
+            Strain::C => 3,
+            Strain::NT => 4,
+        }
+    }
+
+    /// Position of the trump suit in `BRIDGE_SUITS`, or None for no trump.
+    pub fn suit_index(&self) -> Option<usize> {
+        match self {
+            Strain::NT => None,
+            _ => Some(self.index()),
+        }
+    }
+
+    /// Returns the trump Suit, or None for no trump.
+    pub fn suit(&self) -> Option<Suit> {
+        self.suit_index().map(|i| Suit::new(BRIDGE_SUITS[i]))
+    }
+
+    pub fn is_suit(&self) -> bool {
+        *self != Strain::NT
+    }
+
+    pub fn is_major(&self) -> bool {
+        *self == Strain::S || *self == Strain::H
+    }
+
+    pub fn is_minor(&self) -> bool {
+        *self == Strain::D || *self == Strain::C
+    }
+
+    /// Returns the letters used for the strain in Portable Bridge Notation.
+    pub fn to_pbn(&self) -> &'static str {
+        match self {
+            Strain::S => "S",
+            Strain::H => "H",
+            Strain::D => "D",
+            Strain::C => "C",
+            Strain::NT => "NT",
+        }
+    }
+}
+
+/// Displays the Suit symbol for a trump strain, and `NT` for no trump.
+impl fmt::Display for Strain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.suit() {
+            Some(suit) => write!(f, "{}", suit),
+            None => write!(f, "NT"),
+        }
+    }
+}
+
+/// Whether a contract has been doubled or redoubled.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Doubling {
+    NONE,
+    DOUBLED,
+    REDOUBLED,
+}
+
+impl Doubling {
+    /// Returns the `X` or `XX` suffix used in Portable Bridge Notation contracts.
+    pub fn to_pbn(&self) -> &'static str {
+        match self {
+            Doubling::NONE => "",
+            Doubling::DOUBLED => "X",
+            Doubling::REDOUBLED => "XX",
+        }
+    }
+}
+
+/// The final contract of an auction: a level from one to seven, a strain, whether it has
+/// been doubled, and the declarer.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeDirection, Contract, Strain};
+///
+/// let contract = Contract::from_pbn("4HX", BridgeDirection::S).unwrap();
+///
+/// assert_eq!(Strain::H, contract.strain);
+/// assert_eq!(10, contract.tricks_needed());
+/// assert_eq!(BridgeDirection::W, contract.opening_leader());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Contract {
+    pub level: usize,
+    pub strain: Strain,
+    pub doubling: Doubling,
+    pub declarer: BridgeDirection,
+}
+
+impl Contract {
+    pub fn new(level: usize, strain: Strain, declarer: BridgeDirection) -> Contract {
+        Contract {
+            level,
+            strain,
+            doubling: Doubling::NONE,
+            declarer,
+        }
+    }
+
+    /// Parses the contract part of a Portable Bridge Notation `Contract` tag, such as `3NT`
+    /// or `4SX`. A passed out board (`Pass`) has no contract and returns None.
+    pub fn from_pbn(s: &str, declarer: BridgeDirection) -> Option<Contract> {
+        let s = s.trim().to_uppercase();
+        let level = s.chars().next()?.to_digit(10)? as usize;
+        if !(1..=7).contains(&level) {
+            return None;
+        }
+        let rest = &s[1..];
+        let doubles = rest.chars().rev().take_while(|c| *c == 'X').count();
+        let doubling = match doubles {
+            0 => Doubling::NONE,
+            1 => Doubling::DOUBLED,
+            2 => Doubling::REDOUBLED,
+            _ => return None,
+        };
+        let strain = Strain::to(&rest[..rest.len() - doubles])?;
+
+        Some(Contract {
+            level,
+            strain,
+            doubling,
+            declarer,
+        })
+    }
+
+    pub fn to_pbn(&self) -> String {
+        format!(
+            "{}{}{}",
+            self.level,
+            self.strain.to_pbn(),
+            self.doubling.to_pbn()
+        )
+    }
+
+    /// The number of tricks declarer needs to make the contract.
+    pub fn tricks_needed(&self) -> usize {
+        self.level + 6
+    }
+
+    pub fn dummy(&self) -> BridgeDirection {
+        self.declarer.partner()
+    }
+
+    /// The player on declarer's left makes the opening lead.
+    pub fn opening_leader(&self) -> BridgeDirection {
+        self.declarer.lho()
+    }
+
+    /// The duplicate score for declarer's side when declarer takes the passed in number of
+    /// tricks. Contracts that go down score a negative number.
+    pub fn score(&self, tricks: usize, vulnerable: bool) -> isize {
+        let needed = self.tricks_needed();
+        if tricks >= needed {
+            self.making_score(tricks - needed, vulnerable)
+        } else {
+            -self.penalty(needed - tricks, vulnerable)
+        }
+    }
+
+    fn making_score(&self, overtricks: usize, vulnerable: bool) -> isize {
+        let multiplier = match self.doubling {
+            Doubling::NONE => 1,
+            Doubling::DOUBLED => 2,
+            Doubling::REDOUBLED => 4,
+        };
+        let per_trick = if self.strain.is_minor() { 20 } else { 30 };
+        let first_trick = if self.strain == Strain::NT {
+            40
+        } else {
+            per_trick
+        };
+        let trick_score = (first_trick + per_trick * (self.level as isize - 1)) * multiplier;
+
+        let mut score = trick_score;
+        score += match (trick_score >= 100, vulnerable) {
+            (false, _) => 50,
+            (true, false) => 300,
+            (true, true) => 500,
+        };
+        score += match (self.level, vulnerable) {
+            (6, false) => 500,
+            (6, true) => 750,
+            (7, false) => 1000,
+            (7, true) => 1500,
+            _ => 0,
+        };
+
+        let overtricks = overtricks as isize;
+        score += match (self.doubling, vulnerable) {
+            (Doubling::NONE, _) => overtricks * per_trick,
+            (Doubling::DOUBLED, false) => 50 + overtricks * 100,
+            (Doubling::DOUBLED, true) => 50 + overtricks * 200,
+            (Doubling::REDOUBLED, false) => 100 + overtricks * 200,
+            (Doubling::REDOUBLED, true) => 100 + overtricks * 400,
+        };
+        score
+    }
+
+    fn penalty(&self, undertricks: usize, vulnerable: bool) -> isize {
+        let undertricks = undertricks as isize;
+        let doubled = match (vulnerable, undertricks) {
+            (true, _) => 300 * undertricks - 100,
+            (false, 1) => 100,
+            (false, 2) | (false, 3) => 200 * undertricks - 100,
+            (false, _) => 300 * undertricks - 400,
+        };
+        match self.doubling {
+            Doubling::NONE if vulnerable => 100 * undertricks,
+            Doubling::NONE => 50 * undertricks,
+            Doubling::DOUBLED => doubled,
+            Doubling::REDOUBLED => doubled * 2,
+        }
+    }
+}
+
+/// Displays the contract and declarer, such as `4♥X by S`.
+impl fmt::Display for Contract {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{} by {}",
+            self.level,
+            self.strain,
+            self.doubling.to_pbn(),
+            self.declarer
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_contract_tests {
+    use super::*;
+
+    #[test]
+    fn ordering() {
+        assert!(Strain::C < Strain::D);
+        assert!(Strain::H < Strain::S);
+        assert!(Strain::S < Strain::NT);
+    }
+
+    #[test]
+    fn to() {
+        assert_eq!(Some(Strain::S), Strain::to("s"));
+        assert_eq!(Some(Strain::NT), Strain::to("NT"));
+        assert_eq!(Some(Strain::NT), Strain::to("N"));
+        assert_eq!(None, Strain::to("X"));
+    }
+
+    #[test]
+    fn from_suit_index() {
+        assert_eq!(Some(Strain::S), Strain::from_suit_index(0));
+        assert_eq!(Some(Strain::C), Strain::from_suit_index(3));
+        assert_eq!(None, Strain::from_suit_index(4));
+    }
+
+    #[test]
+    fn index() {
+        for (i, strain) in Strain::all().iter().enumerate() {
+            assert_eq!(i, strain.index());
+        }
+        assert_eq!(None, Strain::NT.suit_index());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("♥", Strain::H.to_string());
+        assert_eq!("NT", Strain::NT.to_string());
+    }
+
+    #[test]
+    fn contract__from_pbn() {
+        let contract = Contract::from_pbn("3NT", BridgeDirection::N).unwrap();
+
+        assert_eq!(3, contract.level);
+        assert_eq!(Strain::NT, contract.strain);
+        assert_eq!(Doubling::NONE, contract.doubling);
+        assert_eq!(
+            Doubling::REDOUBLED,
+            Contract::from_pbn("2cxx", BridgeDirection::E)
+                .unwrap()
+                .doubling
+        );
+        assert_eq!(None, Contract::from_pbn("Pass", BridgeDirection::N));
+        assert_eq!(None, Contract::from_pbn("8S", BridgeDirection::N));
+        assert_eq!(None, Contract::from_pbn("4HXXX", BridgeDirection::N));
+    }
+
+    #[test]
+    fn contract__to_pbn() {
+        for pbn in ["1C", "3NT", "4SX", "7DXX"].iter() {
+            assert_eq!(
+                *pbn,
+                Contract::from_pbn(pbn, BridgeDirection::S)
+                    .unwrap()
+                    .to_pbn()
+            );
+        }
+    }
+
+    #[test]
+    fn contract__seats() {
+        let contract = Contract::new(4, Strain::S, BridgeDirection::E);
+
+        assert_eq!(BridgeDirection::W, contract.dummy());
+        assert_eq!(BridgeDirection::S, contract.opening_leader());
+        assert_eq!("4♠ by E", contract.to_string());
+    }
+
+    #[test]
+    fn contract__score__making() {
+        let contract = |s: &str| Contract::from_pbn(s, BridgeDirection::S).unwrap();
+
+        assert_eq!(420, contract("4S").score(10, false));
+        assert_eq!(620, contract("4S").score(10, true));
+        assert_eq!(450, contract("4S").score(11, false));
+        assert_eq!(400, contract("3NT").score(9, false));
+        assert_eq!(110, contract("2H").score(8, false));
+        assert_eq!(90, contract("1NT").score(7, false));
+        assert_eq!(130, contract("3C").score(10, true));
+        assert_eq!(990, contract("6NT").score(12, false));
+        assert_eq!(2220, contract("7NT").score(13, true));
+        assert_eq!(470, contract("2SX").score(8, false));
+        assert_eq!(870, contract("2SX").score(9, true));
+        assert_eq!(1560, contract("1NTXX").score(9, true));
+    }
+
+    #[test]
+    fn contract__score__penalties() {
+        let contract = |s: &str| Contract::from_pbn(s, BridgeDirection::S).unwrap();
+
+        assert_eq!(-50, contract("4S").score(9, false));
+        assert_eq!(-200, contract("4S").score(8, true));
+        assert_eq!(-100, contract("4SX").score(9, false));
+        assert_eq!(-500, contract("4SX").score(7, false));
+        assert_eq!(-800, contract("4SX").score(6, false));
+        assert_eq!(-1100, contract("4SX").score(6, true));
+        assert_eq!(-1000, contract("4SXX").score(7, false));
+    }
+}
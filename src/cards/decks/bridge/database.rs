@@ -0,0 +1,577 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::cards::decks::bridge::evaluation::{is_balanced_lengths, HandEvaluation};
+use crate::cards::decks::bridge::generator::Predicate;
+use crate::cards::decks::bridge::{seat_index, BridgeBoard, BridgeDirection, Vulnerability};
+
+const MAGIC: &[u8; 4] = b"BDB1";
+
+/// The size of each record: the 13 byte packed deal, a two byte board number, the dealer,
+/// the vulnerability, and the high card points and four suit lengths of each seat.
+pub const RECORD_SIZE: usize = 37;
+
+const FEATURES: usize = 17;
+
+const VULNERABILITIES: [Vulnerability; 4] = [
+    Vulnerability::NONE,
+    Vulnerability::NS,
+    Vulnerability::EW,
+    Vulnerability::BOTH,
+];
+
+/// A query on a DealDatabase. Every requirement must hold, and the dealer and vulnerability
+/// must match when they are set.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DealQuery {
+    requirements: Vec<(usize, Predicate)>,
+    pub dealer: Option<BridgeDirection>,
+    pub vulnerability: Option<Vulnerability>,
+}
+
+impl DealQuery {
+    pub fn new() -> DealQuery {
+        DealQuery::default()
+    }
+
+    /// Requires the hand held by the seat to match the predicate.
+    pub fn require(&mut self, direction: &BridgeDirection, predicate: Predicate) {
+        if let Some(seat) = seat_index(direction) {
+            self.requirements.push((seat, predicate));
+        }
+    }
+}
+
+/// A compact store of complete BridgeBoards, kept as fixed size records that can be saved
+/// to and loaded from disk.
+///
+/// Each record holds the deal packed into 13 bytes along with the board's number, dealer
+/// and vulnerability, and the high card points and suit lengths of every hand. Queries on
+/// points, suit lengths, shape and balance are answered from those features alone, and the
+/// boards are indexed by each seat's suit lengths, so a shape requirement only looks at the
+/// boards with matching hands. Only queries on particular cards unpack the deals.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeBoard, BridgeDirection, DealDatabase, DealQuery, Predicate, ShapePattern};
+///
+/// let mut database = DealDatabase::new();
+/// database.add(&BridgeBoard::from_pbn_deal(
+///     "N:AK93.KQ52.8543.7 QT5.T97.K962.862 J872.AJ8.QT.KJ95 64.643.AJ7.AQT43",
+/// ));
+/// database.add(&BridgeBoard::deal_board(2));
+///
+/// let mut query = DealQuery::new();
+/// query.require(&BridgeDirection::N, Predicate::SHAPE(ShapePattern::to("4441").unwrap()));
+/// query.require(&BridgeDirection::N, Predicate::HCP(11, 15));
+///
+/// assert_eq!(2, database.len());
+/// assert_eq!(0, database.query(&query)[0]);
+/// assert_eq!(12, database.hcp(0, &BridgeDirection::N).unwrap());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DealDatabase {
+    records: Vec<[u8; RECORD_SIZE]>,
+    /// For each seat, the records where its hand has each set of suit lengths.
+    shapes: Vec<HashMap<[u8; 4], Vec<usize>>>,
+}
+
+impl DealDatabase {
+    pub fn new() -> DealDatabase {
+        DealDatabase {
+            records: Vec::new(),
+            shapes: vec![HashMap::new(); 4],
+        }
+    }
+
+    /// Adds a board, returning false unless it is a complete deal with a board number below
+    /// 65536.
+    pub fn add(&mut self, board: &BridgeBoard) -> bool {
+        match pack_record(board) {
+            Some(record) => {
+                self.push(record);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds every complete deal in the boards, returning how many were added.
+    pub fn add_all(&mut self, boards: &[BridgeBoard]) -> usize {
+        boards.iter().filter(|board| self.add(board)).count()
+    }
+
+    /// Imports the deals in Portable Bridge Notation text, with the board number, dealer
+    /// and vulnerability from each game's `Board`, `Dealer` and `Vulnerable` tags. Games
+    /// without a complete deal, or whose `Deal` tag can't be read, are skipped. Returns how
+    /// many boards were added.
+    pub fn import_pbn(&mut self, pbn: &str) -> usize {
+        let mut added = 0;
+        let mut tags: HashMap<String, String> = HashMap::new();
+        for line in pbn.lines().chain(std::iter::once("")) {
+            let line = line.trim();
+            if line.is_empty() {
+                if let Some(board) = board_from_tags(&tags) {
+                    if self.add(&board) {
+                        added += 1;
+                    }
+                }
+                tags.clear();
+            } else if let Some((name, value)) = parse_tag(line) {
+                tags.insert(name, value);
+            }
+        }
+        added
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Unpacks the board at the index.
+    pub fn board(&self, index: usize) -> Option<BridgeBoard> {
+        unpack_record(self.records.get(index)?)
+    }
+
+    pub fn hcp(&self, index: usize, direction: &BridgeDirection) -> Option<usize> {
+        let record = self.records.get(index)?;
+        Some(record[FEATURES + seat_index(direction)? * 5] as usize)
+    }
+
+    /// The suit lengths of the seat's hand, in `BRIDGE_SUITS` order.
+    pub fn suit_lengths(&self, index: usize, direction: &BridgeDirection) -> Option<[usize; 4]> {
+        let record = self.records.get(index)?;
+        Some(lengths(record, seat_index(direction)?))
+    }
+
+    /// Returns the indexes of the boards that match the query, in the order they were added.
+    pub fn query(&self, query: &DealQuery) -> Vec<usize> {
+        let candidates = self.candidates(query);
+        let matches = |index: &usize| {
+            let record = &self.records[*index];
+            if query.dealer.map_or(false, |d| d != dealer(record))
+                || query
+                    .vulnerability
+                    .map_or(false, |v| v != VULNERABILITIES[record[16] as usize])
+            {
+                return false;
+            }
+            let mut board = None;
+            query.requirements.iter().all(|(seat, predicate)| {
+                match matches_features(predicate, record, *seat) {
+                    Some(result) => result,
+                    None => {
+                        if board.is_none() {
+                            board = self.board(*index);
+                        }
+                        board
+                            .as_ref()
+                            .and_then(|b| b.get_hand(&BridgeDirection::seats()[*seat]))
+                            .map_or(false, |hand| predicate.matches(hand))
+                    }
+                }
+            })
+        };
+        match candidates {
+            Some(candidates) => candidates.into_iter().filter(matches).collect(),
+            None => (0..self.records.len()).filter(matches).collect(),
+        }
+    }
+
+    /// Returns the boards that match the query.
+    pub fn find(&self, query: &DealQuery) -> Vec<BridgeBoard> {
+        self.query(query)
+            .into_iter()
+            .filter_map(|index| self.board(index))
+            .collect()
+    }
+
+    /// The database as bytes: a four byte header followed by the records.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(MAGIC.len() + self.records.len() * RECORD_SIZE);
+        bytes.extend_from_slice(MAGIC);
+        for record in self.records.iter() {
+            bytes.extend_from_slice(record);
+        }
+        bytes
+    }
+
+    /// Reads a database written by `to_bytes()`, returning an error if the header is wrong
+    /// or any record is damaged: its deal doesn't unpack, or the high card points and suit
+    /// lengths stored with it don't match the deal.
+    pub fn from_bytes(bytes: &[u8]) -> Result<DealDatabase, String> {
+        if !bytes.starts_with(&MAGIC[..]) {
+            return Err("not a deal database".to_string());
+        }
+        let body = &bytes[MAGIC.len()..];
+        if body.len() % RECORD_SIZE != 0 {
+            return Err("the database ends part way through a record".to_string());
+        }
+        let mut database = DealDatabase::new();
+        for (i, chunk) in body.chunks(RECORD_SIZE).enumerate() {
+            let mut record = [0; RECORD_SIZE];
+            record.copy_from_slice(chunk);
+            if unpack_record(&record).and_then(|board| pack_record(&board)) != Some(record) {
+                return Err(format!("record {} is damaged", i + 1));
+            }
+            database.push(record);
+        }
+        Ok(database)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<DealDatabase> {
+        DealDatabase::from_bytes(&fs::read(path)?)
+            .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))
+    }
+
+    fn push(&mut self, record: [u8; RECORD_SIZE]) {
+        let index = self.records.len();
+        for (seat, shapes) in self.shapes.iter_mut().enumerate() {
+            let offset = FEATURES + seat * 5 + 1;
+            let mut key = [0; 4];
+            key.copy_from_slice(&record[offset..offset + 4]);
+            shapes.entry(key).or_default().push(index);
+        }
+        self.records.push(record);
+    }
+
+    /// Uses the shape index to narrow down the boards for the first requirement on suit
+    /// lengths, or returns None if there isn't one.
+    fn candidates(&self, query: &DealQuery) -> Option<Vec<usize>> {
+        let (seat, predicate) = query.requirements.iter().find(|(_, predicate)| {
+            matches!(
+                predicate,
+                Predicate::LENGTH(_, _, _) | Predicate::SHAPE(_) | Predicate::BALANCED
+            )
+        })?;
+        let mut candidates: Vec<usize> = self.shapes[*seat]
+            .iter()
+            .filter(|(key, _)| {
+                let lengths = [
+                    key[0] as usize,
+                    key[1] as usize,
+                    key[2] as usize,
+                    key[3] as usize,
+                ];
+                matches_lengths(predicate, &lengths) == Some(true)
+            })
+            .flat_map(|(_, indexes)| indexes.iter().copied())
+            .collect();
+        candidates.sort_unstable();
+        Some(candidates)
+    }
+}
+
+impl Default for DealDatabase {
+    fn default() -> Self {
+        DealDatabase::new()
+    }
+}
+
+/// Packs a board into a record, or returns None unless it is a complete deal with a board
+/// number below 65536.
+fn pack_record(board: &BridgeBoard) -> Option<[u8; RECORD_SIZE]> {
+    let deal = board.to_bytes()?;
+    if board.number > u16::MAX as usize {
+        return None;
+    }
+
+    let mut record = [0; RECORD_SIZE];
+    record[..13].copy_from_slice(&deal);
+    record[13..15].copy_from_slice(&(board.number as u16).to_le_bytes());
+    record[15] = seat_index(&board.dealer).unwrap_or(4) as u8;
+    record[16] = VULNERABILITIES
+        .iter()
+        .position(|v| *v == board.vulnerability)
+        .unwrap_or(0) as u8;
+    for (seat, direction) in BridgeDirection::seats().iter().enumerate() {
+        let hand = board.get_hand(direction)?;
+        let offset = FEATURES + seat * 5;
+        record[offset] = hand.hcp() as u8;
+        for (i, length) in hand.suit_lengths().iter().enumerate() {
+            record[offset + 1 + i] = *length as u8;
+        }
+    }
+    Some(record)
+}
+
+fn unpack_record(record: &[u8; RECORD_SIZE]) -> Option<BridgeBoard> {
+    let mut deal = [0; 13];
+    deal.copy_from_slice(&record[..13]);
+    let mut board = BridgeBoard::from_bytes(&deal)?;
+    board.number = u16::from_le_bytes([record[13], record[14]]) as usize;
+    board.dealer = dealer(record);
+    board.vulnerability = *VULNERABILITIES.get(record[16] as usize)?;
+    Some(board)
+}
+
+fn dealer(record: &[u8; RECORD_SIZE]) -> BridgeDirection {
+    BridgeDirection::seats()
+        .get(record[15] as usize)
+        .copied()
+        .unwrap_or(BridgeDirection::UNKNOWN)
+}
+
+fn lengths(record: &[u8; RECORD_SIZE], seat: usize) -> [usize; 4] {
+    let offset = FEATURES + seat * 5 + 1;
+    let mut lengths = [0; 4];
+    for (i, length) in lengths.iter_mut().enumerate() {
+        *length = record[offset + i] as usize;
+    }
+    lengths
+}
+
+/// Tests a predicate against the precomputed features of a seat, or returns None if the
+/// predicate needs the cards themselves.
+fn matches_features(
+    predicate: &Predicate,
+    record: &[u8; RECORD_SIZE],
+    seat: usize,
+) -> Option<bool> {
+    match predicate {
+        Predicate::HCP(min, max) => {
+            Some((*min..=*max).contains(&(record[FEATURES + seat * 5] as usize)))
+        }
+        _ => matches_lengths(predicate, &lengths(record, seat)),
+    }
+}
+
+fn matches_lengths(predicate: &Predicate, lengths: &[usize; 4]) -> Option<bool> {
+    match predicate {
+        Predicate::LENGTH(suit, min, max) => Some(
+            lengths
+                .get(*suit)
+                .map_or(false, |l| (*min..=*max).contains(l)),
+        ),
+        Predicate::SHAPE(pattern) => Some(pattern.matches(lengths)),
+        Predicate::BALANCED => Some(is_balanced_lengths(lengths)),
+        _ => None,
+    }
+}
+
+fn parse_tag(line: &str) -> Option<(String, String)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (name, value) = inner.split_at(inner.find(char::is_whitespace)?);
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    Some((name.to_string(), value.to_string()))
+}
+
+fn board_from_tags(tags: &HashMap<String, String>) -> Option<BridgeBoard> {
+    let mut board = BridgeBoard::try_from_pbn_deal(tags.get("Deal")?)?;
+    if let Some(number) = tags.get("Board").and_then(|n| n.trim().parse().ok()) {
+        board.set_number(number);
+    }
+    if let Some(dealer) = tags.get("Dealer").and_then(|d| d.trim().chars().next()) {
+        board.dealer = BridgeDirection::to(dealer);
+    }
+    if let Some(vulnerability) = tags
+        .get("Vulnerable")
+        .and_then(|v| Vulnerability::from_pbn(v))
+    {
+        board.vulnerability = vulnerability;
+    }
+    Some(board)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_database_tests {
+    use super::*;
+    use crate::cards::decks::bridge::generator::ShapePattern;
+
+    const PBN_TEST_STRING: &str =
+        "S:Q42.Q52.AQT943.Q 97.AT93.652.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982";
+    const FOUR_FOUR_FOUR_ONE: &str =
+        "N:AK93.KQ52.8543.7 QT5.T97.K962.862 J872.AJ8.QT.KJ95 64.643.AJ7.AQT43";
+
+    const PBN_FILE: &str = r#"[Event "Club game"]
+[Board "7"]
+[Dealer "S"]
+[Vulnerable "All"]
+[Deal "S:Q42.Q52.AQT943.Q 97.AT93.652.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982"]
+[Contract "4S"]
+
+[Event "Club game"]
+[Board "8"]
+[Dealer "W"]
+[Vulnerable "None"]
+[Deal "N:AK93.KQ52.8543.7 QT5.T97.K962.862 J872.AJ8.QT.KJ95 64.643.AJ7.AQT43"]
+
+[Event "Unfinished"]
+[Board "9"]
+[Deal "N:AK93.KQ52.A843.7 - - -"]
+"#;
+
+    fn database() -> DealDatabase {
+        let mut database = DealDatabase::new();
+        database.import_pbn(PBN_FILE);
+        database
+    }
+
+    #[test]
+    fn add() {
+        let mut database = DealDatabase::new();
+        let mut board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+        board.set_number(22);
+
+        assert!(database.add(&board));
+        assert!(!database.add(&BridgeBoard::default()));
+        assert_eq!(1, database.len());
+        assert_eq!(Some(board), database.board(0));
+        assert_eq!(None, database.board(1));
+    }
+
+    #[test]
+    fn features() {
+        let database = database();
+
+        assert_eq!(Some(12), database.hcp(1, &BridgeDirection::N));
+        assert_eq!(
+            Some([4, 4, 4, 1]),
+            database.suit_lengths(1, &BridgeDirection::N)
+        );
+        assert_eq!(
+            Some([5, 3, 2, 3]),
+            database.suit_lengths(0, &BridgeDirection::N)
+        );
+        assert_eq!(None, database.hcp(0, &BridgeDirection::UNKNOWN));
+    }
+
+    #[test]
+    fn import_pbn() {
+        let database = database();
+
+        assert_eq!(2, database.len());
+        let board = database.board(0).unwrap();
+        assert_eq!(7, board.number);
+        assert_eq!(BridgeDirection::S, board.dealer);
+        assert_eq!(Vulnerability::BOTH, board.vulnerability);
+        assert_eq!(
+            BridgeBoard::from_pbn_deal(PBN_TEST_STRING).south,
+            board.south
+        );
+        let board = database.board(1).unwrap();
+        assert_eq!(BridgeDirection::W, board.dealer);
+        assert_eq!(Vulnerability::NONE, board.vulnerability);
+    }
+
+    #[test]
+    fn import_pbn__malformed_deal() {
+        let mut database = DealDatabase::new();
+        let malformed = r#"[Board "1"]
+[Deal "N:AKQ JT9 876 5432"]
+
+[Board "2"]
+[Deal "garbage"]
+"#;
+        let pbn = format!("{}\n{}", malformed, PBN_FILE);
+
+        assert_eq!(2, database.import_pbn(&pbn));
+        assert_eq!(7, database.board(0).unwrap().number);
+    }
+
+    #[test]
+    fn query() {
+        let database = database();
+        let mut query = DealQuery::new();
+        query.require(
+            &BridgeDirection::N,
+            Predicate::SHAPE(ShapePattern::to("4441").unwrap()),
+        );
+        query.require(&BridgeDirection::N, Predicate::HCP(11, 15));
+
+        assert_eq!(vec![1], database.query(&query));
+        assert_eq!(
+            FOUR_FOUR_FOUR_ONE,
+            database.find(&query)[0].to_pbn_deal_from(&BridgeDirection::N)
+        );
+    }
+
+    #[test]
+    fn query__features() {
+        let database = database();
+        let mut balanced = DealQuery::new();
+        balanced.require(&BridgeDirection::W, Predicate::BALANCED);
+        let mut long_diamonds = DealQuery::new();
+        long_diamonds.require(&BridgeDirection::S, Predicate::LENGTH(2, 6, 13));
+        let mut weak = DealQuery::new();
+        weak.require(&BridgeDirection::N, Predicate::HCP(0, 10));
+
+        assert_eq!(vec![0, 1], database.query(&balanced));
+        assert_eq!(vec![0], database.query(&long_diamonds));
+        assert!(database.query(&weak).is_empty());
+        assert_eq!(vec![0, 1], database.query(&DealQuery::new()));
+    }
+
+    #[test]
+    fn query__dealer_vulnerability_and_cards() {
+        let database = database();
+        let mut dealer = DealQuery::new();
+        dealer.dealer = Some(BridgeDirection::W);
+        let mut vulnerable = DealQuery::new();
+        vulnerable.vulnerability = Some(Vulnerability::BOTH);
+        let mut holds = DealQuery::new();
+        let ace = BridgeBoard::from_pbn_deal(PBN_TEST_STRING)
+            .south
+            .card_by_index("AD")
+            .unwrap()
+            .clone();
+        holds.require(&BridgeDirection::S, Predicate::HOLDS(ace));
+
+        assert_eq!(vec![1], database.query(&dealer));
+        assert_eq!(vec![0], database.query(&vulnerable));
+        assert_eq!(vec![0], database.query(&holds));
+    }
+
+    #[test]
+    fn to_bytes() {
+        let database = database();
+
+        let bytes = database.to_bytes();
+
+        assert_eq!(4 + 2 * RECORD_SIZE, bytes.len());
+        assert_eq!(Ok(database), DealDatabase::from_bytes(&bytes));
+        assert!(DealDatabase::from_bytes(&bytes[1..]).is_err());
+        assert!(DealDatabase::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn from_bytes__damaged() {
+        let bytes = database().to_bytes();
+
+        let mut hcp = bytes.clone();
+        hcp[MAGIC.len() + FEATURES] += 1;
+        let mut length = bytes.clone();
+        length[MAGIC.len() + FEATURES + 1] += 1;
+        let mut deal = bytes.clone();
+        deal[MAGIC.len()] ^= 0xff;
+        for damaged in [hcp, length, deal].iter() {
+            assert_eq!(
+                Err("record 1 is damaged".to_string()),
+                DealDatabase::from_bytes(damaged)
+            );
+        }
+    }
+
+    #[test]
+    fn save_and_open() {
+        let database = database();
+        let path = std::env::temp_dir().join(format!("cardpack-{}.bdb", std::process::id()));
+
+        database.save(&path).unwrap();
+        let opened = DealDatabase::open(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(database, opened);
+    }
+}
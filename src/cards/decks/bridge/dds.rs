@@ -0,0 +1,1440 @@
+use std::cmp::{max, min};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasherDefault, Hasher};
+
+use crate::cards::card::Card;
+use crate::cards::decks::bridge::contract::Strain;
+use crate::cards::decks::bridge::{
+    rank_index, seat_index, suit_index, BridgeBoard, BridgeDirection, BRIDGE_RANKS, BRIDGE_SUITS,
+};
+use crate::cards::pile::Pile;
+
+const NO_TRUMP: usize = 4;
+const NO_SUIT: u8 = 4;
+/// The most entries the transposition table holds before it is cleared.
+const TABLE_LIMIT: usize = 1 << 20;
+/// Bits for each suit in a layout, two for each card.
+const SUIT_BITS: u32 = 26;
+/// The bucket for transposition table entries that leave the top card of some suit open,
+/// which no holders of the top cards can equal.
+const GENERAL: u128 = u128::MAX;
+const ACE_BIT: u16 = 1 << 14;
+const DEUCE_BIT: u16 = 1 << 2;
+
+/// Converts a Card into its position in `BRIDGE_SUITS` and a rank bit, where the deuce is
+/// bit 2 and the ace bit 14, so that higher cards have higher bits.
+pub(crate) fn card_to_bit(card: &Card) -> Option<(usize, u16)> {
+    match (suit_index(card), rank_index(card)) {
+        (Some(suit), Some(rank)) => Some((suit, 1 << (14 - rank))),
+        _ => None,
+    }
+}
+
+/// The inverse of `card_to_bit`.
+pub(crate) fn bit_to_card(suit: usize, bit: u16) -> Card {
+    let rank = 14 - bit.trailing_zeros() as usize;
+    Card::new(BRIDGE_RANKS[rank], BRIDGE_SUITS[suit])
+}
+
+/// Returns the rank bits held in each suit of a hand.
+pub(crate) fn pile_to_masks(pile: &Pile) -> [u16; 4] {
+    let mut masks = [0; 4];
+    for (suit, bit) in pile.values().filter_map(card_to_bit) {
+        masks[suit] |= bit;
+    }
+    masks
+}
+
+/// The number of tricks each declarer can take in each strain with all four hands visible.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeBoard, BridgeDirection, Strain};
+///
+/// let board = BridgeBoard::from_pbn_deal(
+///     "N:AKQJ.AKQ.AKQ.AKQ T987.JT9.JT9.JT9 6543.876.876.876 2.5432.5432.5432",
+/// );
+/// let table = board.double_dummy().unwrap();
+///
+/// assert_eq!(13, table.get(&Strain::NT, &BridgeDirection::N));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TrickTable([[usize; 4]; 5]);
+
+impl TrickTable {
+    /// Takes the tricks indexed by `Strain::index()` and then by the declarer's position in
+    /// `BridgeDirection::seats()`.
+    pub fn new(tricks: [[usize; 4]; 5]) -> TrickTable {
+        TrickTable(tricks)
+    }
+
+    pub fn get(&self, strain: &Strain, declarer: &BridgeDirection) -> usize {
+        match seat_index(declarer) {
+            Some(seat) => self.0[strain.index()][seat],
+            None => 0,
+        }
+    }
+
+    pub fn set(&mut self, strain: &Strain, declarer: &BridgeDirection, tricks: usize) {
+        if let Some(seat) = seat_index(declarer) {
+            self.0[strain.index()][seat] = tricks;
+        }
+    }
+}
+
+/// Displays a row for each declarer with the tricks for each strain, spades through no trump.
+impl fmt::Display for TrickTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, " ")?;
+        for strain in Strain::all() {
+            write!(f, " {:>2}", strain.to_pbn())?;
+        }
+        for declarer in BridgeDirection::seats() {
+            writeln!(f)?;
+            write!(f, "{}", declarer)?;
+            for strain in Strain::all() {
+                write!(f, " {:>2}", self.get(&strain, &declarer))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The result of solving a position: the most tricks the side on play can take from here,
+/// including the trick in progress, and every card that achieves it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DoubleDummySolution {
+    pub player: BridgeDirection,
+    pub tricks: usize,
+    pub cards: Pile,
+}
+
+/// A double dummy solver for a BridgeBoard in a given strain.
+///
+/// The search is an alpha-beta minimax over the cards played, probed with null windows to
+/// find the exact number of tricks. Touching cards are treated as equivalent, moves are
+/// ordered so that cheap winners, low cards and leads that recently cut off the search are
+/// tried first, and sure tricks and master trumps are used to cut off the search early.
+///
+/// Results at the start of each trick are kept in a transposition table of bounded size.
+/// Each entry records only the cards whose ranks decided the result, so it also answers for
+/// positions that differ in the small cards. The table is shared by all the declarers and
+/// strains a solver is asked about.
+///
+/// The hands on the board can be partly played out, which allows solving positions in the
+/// middle of the play. A solver can be reused for positions from the same board.
+pub struct DoubleDummy {
+    hands: [[u16; 4]; 4],
+    trump: usize,
+    leader: usize,
+    trick: [(usize, usize, u16); 4],
+    trick_len: usize,
+    in_play: [u16; 4],
+    killers: [[[u8; 2]; 4]; 14],
+    guess: i32,
+    table: Table,
+}
+
+impl DoubleDummy {
+    pub fn new(board: &BridgeBoard, strain: Strain) -> DoubleDummy {
+        let mut hands = [[0; 4]; 4];
+        for (i, direction) in BridgeDirection::seats().iter().enumerate() {
+            if let Some(hand) = board.get_hand(direction) {
+                hands[i] = pile_to_masks(hand);
+            }
+        }
+        DoubleDummy {
+            hands,
+            trump: strain.suit_index().unwrap_or(NO_TRUMP),
+            leader: 0,
+            trick: [(0, 0, 0); 4],
+            trick_len: 0,
+            in_play: [0; 4],
+            killers: [[[NO_SUIT; 2]; 4]; 14],
+            guess: 7,
+            table: Table::default(),
+        }
+    }
+
+    /// Solves all five strains for all four declarers. Returns None unless the hands hold
+    /// the same number of cards, at least one each, with no card in two hands.
+    pub fn trick_table(board: &BridgeBoard) -> Option<TrickTable> {
+        let mut solver = DoubleDummy::new(board, Strain::NT);
+        let total = solver.hand_len(0);
+        if total == 0 || !solver.is_playable(0, &[]) {
+            return None;
+        }
+        let mut table = TrickTable::default();
+        for strain in Strain::all() {
+            solver.trump = strain.suit_index().unwrap_or(NO_TRUMP);
+            for declarer in BridgeDirection::seats() {
+                let defence = solver.tricks(&declarer.lho());
+                table.set(&strain, &declarer, total - defence);
+            }
+        }
+        Some(table)
+    }
+
+    /// Returns the number of tricks the leader's side can take when the leader is on lead to
+    /// the next trick.
+    pub fn tricks(&mut self, leader: &BridgeDirection) -> usize {
+        let leader = match seat_index(leader) {
+            Some(seat) => seat,
+            None => return 0,
+        };
+        self.leader = leader;
+        self.trick_len = 0;
+        self.reset_in_play();
+        let ns = self.exact();
+        let tricks = if is_north_south(leader) {
+            ns
+        } else {
+            self.hand_len(leader) as i32 - ns
+        };
+        tricks as usize
+    }
+
+    /// Solves the position with the leader on lead and the passed in cards already played to
+    /// the current trick, which must no longer be in the hands.
+    ///
+    /// Returns None if the position isn't one that can be played from: a card is played
+    /// twice or is still in a hand, a card is in more than one hand, a player didn't follow
+    /// suit when they could have, or the hands aren't the right sizes for the trick in
+    /// progress.
+    pub fn solve(
+        &mut self,
+        leader: &BridgeDirection,
+        played: &[Card],
+    ) -> Option<DoubleDummySolution> {
+        self.set_position(leader, played)?;
+
+        let seat = self.to_move();
+        let total = self.tricks_remaining();
+        let mut best = -1;
+        let mut cards: Vec<Card> = Vec::new();
+        let groups = self.groups(seat);
+        for &(_, suit, run) in groups.list[..groups.len].iter() {
+            let ns = self.exact_play(seat, suit, highest_bit(run));
+            let tricks = if is_north_south(seat) { ns } else { total - ns };
+            if tricks > best {
+                best = tricks;
+                cards.clear();
+            }
+            if tricks == best {
+                let mut bit = ACE_BIT;
+                while bit >= DEUCE_BIT {
+                    if run & bit != 0 {
+                        cards.push(bit_to_card(suit, bit));
+                    }
+                    bit >>= 1;
+                }
+            }
+        }
+        self.trick_len = 0;
+
+        if best < 0 {
+            return None;
+        }
+        Some(DoubleDummySolution {
+            player: BridgeDirection::seats()[seat],
+            tricks: best as usize,
+            cards: Pile::new_from_vector(cards).sort(),
+        })
+    }
+
+    /// Checks whether the player's side can take at least the passed in number of tricks,
+    /// including the trick in progress, from the same positions as `solve`. This takes a
+    /// single null window search, so is quicker than solving the position.
+    ///
+    /// Returns None if the position isn't one that can be played from.
+    pub fn can_take(
+        &mut self,
+        leader: &BridgeDirection,
+        played: &[Card],
+        player: &BridgeDirection,
+        tricks: usize,
+    ) -> Option<bool> {
+        let player = seat_index(player)?;
+        self.set_position(leader, played)?;
+
+        let total = self.tricks_remaining();
+        let tricks = tricks as i32;
+        let result = if tricks == 0 {
+            true
+        } else if tricks > total {
+            false
+        } else if is_north_south(player) {
+            self.search(tricks - 1, tricks).0 >= tricks
+        } else {
+            self.search(total - tricks, total - tricks + 1).0 <= total - tricks
+        };
+        self.trick_len = 0;
+        Some(result)
+    }
+
+    /// Sets up the position with the leader on lead and the passed in cards already played
+    /// to the current trick, or returns None if it isn't one that can be played from.
+    fn set_position(&mut self, leader: &BridgeDirection, played: &[Card]) -> Option<()> {
+        let leader = seat_index(leader)?;
+        if played.len() > 3 {
+            return None;
+        }
+        let mut trick = [(0, 0, 0); 4];
+        for (i, card) in played.iter().enumerate() {
+            let (suit, bit) = card_to_bit(card)?;
+            trick[i] = ((leader + i) % 4, suit, bit);
+        }
+        if !self.is_playable(leader, &trick[..played.len()]) {
+            return None;
+        }
+
+        self.leader = leader;
+        self.trick = trick;
+        self.trick_len = played.len();
+        self.reset_in_play();
+        Some(())
+    }
+
+    /// Checks that the cards played to the trick in progress, as seat, suit and rank bit,
+    /// could have been played from the hands.
+    fn is_playable(&self, leader: usize, trick: &[(usize, usize, u16)]) -> bool {
+        let mut seen = [0u16; 4];
+        for hand in self.hands.iter() {
+            for (suit, mask) in hand.iter().enumerate() {
+                if seen[suit] & mask != 0 {
+                    return false;
+                }
+                seen[suit] |= mask;
+            }
+        }
+        for &(seat, suit, bit) in trick.iter() {
+            if seen[suit] & bit != 0 {
+                return false;
+            }
+            seen[suit] |= bit;
+            let led = trick[0].1;
+            if suit != led && self.hands[seat][led] != 0 {
+                return false;
+            }
+        }
+
+        let cards = self.hand_len((leader + trick.len()) % 4);
+        if cards == 0 {
+            return trick.is_empty();
+        }
+        (0..4).all(|i| {
+            let expected = if i < trick.len() { cards - 1 } else { cards };
+            self.hand_len((leader + i) % 4) == expected
+        })
+    }
+
+    fn length(&self, seat: usize, suit: usize) -> i32 {
+        self.hands[seat][suit].count_ones() as i32
+    }
+
+    fn hand_len(&self, seat: usize) -> usize {
+        self.hands[seat]
+            .iter()
+            .map(|m| m.count_ones() as usize)
+            .sum()
+    }
+
+    fn to_move(&self) -> usize {
+        (self.leader + self.trick_len) % 4
+    }
+
+    /// Tricks left to play, including the one in progress.
+    fn tricks_remaining(&self) -> i32 {
+        let in_progress = if self.trick_len > 0 { 1 } else { 0 };
+        (self.hand_len(self.leader) + in_progress) as i32
+    }
+
+    /// Finds the exact number of tricks North-South take from the current position with a
+    /// series of null window searches, starting from the result of the previous solve.
+    fn exact(&mut self) -> i32 {
+        let (mut lo, mut hi) = (0, self.tricks_remaining());
+        let mut guess = min(max(self.guess, lo), hi);
+        while lo < hi {
+            let beta = if guess == lo { guess + 1 } else { guess };
+            guess = self.search(beta - 1, beta).0;
+            if guess < beta {
+                hi = guess;
+            } else {
+                lo = guess;
+            }
+        }
+        self.guess = lo;
+        lo
+    }
+
+    fn exact_play(&mut self, seat: usize, suit: usize, bit: u16) -> i32 {
+        let (mut lo, mut hi) = (0, self.tricks_remaining());
+        while lo < hi {
+            let target = (lo + hi + 1) / 2;
+            if self.play(seat, suit, bit, target - 1, target).0 >= target {
+                lo = target;
+            } else {
+                hi = target - 1;
+            }
+        }
+        lo
+    }
+
+    /// Returns a fail-soft alpha-beta value for the tricks North-South take from here,
+    /// along with the cards whose ranks it depends on.
+    fn search(&mut self, alpha: i32, beta: i32) -> (i32, Ranks) {
+        if self.trick_len > 0 {
+            return self.search_moves(alpha, beta);
+        }
+
+        let remaining = self.hand_len(self.leader) as i32;
+        if beta <= 0 {
+            return (0, [0; 4]);
+        }
+        if alpha >= remaining {
+            return (remaining, [0; 4]);
+        }
+        if remaining <= 1 {
+            return self.last_trick();
+        }
+
+        let key = self.key();
+        let layout = self.layout();
+        let tops = self.tops();
+        if let Some((bound, mask)) = self.table.probe(key, layout, tops, alpha, beta) {
+            return (bound, self.top_cards(mask));
+        }
+
+        let (mut sure, mut sure_ranks) = self.quick_tricks();
+        let (trumps, trump_ranks) = self.master_trumps(self.leader);
+        if trumps > sure {
+            sure = trumps;
+            sure_ranks = trump_ranks;
+        }
+        let (kept, kept_ranks) = self.master_trumps((self.leader + 1) % 4);
+        if is_north_south(self.leader) {
+            if sure >= beta {
+                return (sure, sure_ranks);
+            }
+            if remaining - kept <= alpha {
+                return (remaining - kept, kept_ranks);
+            }
+        } else {
+            if remaining - sure <= alpha {
+                return (remaining - sure, sure_ranks);
+            }
+            if kept >= beta {
+                return (kept, kept_ranks);
+            }
+        }
+
+        let (value, ranks) = self.search_moves(alpha, beta);
+        let (lo, hi) = if value <= alpha {
+            (0, value)
+        } else if value >= beta {
+            (value, remaining)
+        } else {
+            (value, value)
+        };
+        self.table
+            .store(key, tops, Entry::new(layout, &self.in_play, &ranks, lo, hi));
+        (value, ranks)
+    }
+
+    /// With one card left in each hand the play is forced.
+    fn last_trick(&mut self) -> (i32, Ranks) {
+        if self.hand_len(self.leader) == 0 {
+            return (0, [0; 4]);
+        }
+        for i in 0..4 {
+            let seat = (self.leader + i) % 4;
+            let suit = (0..4).find(|s| self.hands[seat][*s] != 0).unwrap_or(0);
+            self.trick[i] = (seat, suit, self.hands[seat][suit]);
+        }
+        self.trick_len = 4;
+        let winner = self.trick_winner();
+        let ranks = self.winning_rank();
+        self.trick_len = 0;
+        (if is_north_south(winner) { 1 } else { 0 }, ranks)
+    }
+
+    fn search_moves(&mut self, mut alpha: i32, mut beta: i32) -> (i32, Ranks) {
+        let seat = self.to_move();
+        let maximizing = is_north_south(seat);
+        let mut best = if maximizing { -1 } else { i32::MAX };
+        let mut ranks = [0; 4];
+
+        let moves = self.ordered_moves(seat);
+        for &(_, suit, bit) in moves.list[..moves.len].iter() {
+            let (value, child) = self.play(seat, suit, bit, alpha, beta);
+            if maximizing {
+                best = max(best, value);
+                if best >= beta {
+                    self.add_killer(seat, suit);
+                    return (best, child);
+                }
+                alpha = max(alpha, best);
+            } else {
+                best = min(best, value);
+                if best <= alpha {
+                    self.add_killer(seat, suit);
+                    return (best, child);
+                }
+                beta = min(beta, best);
+            }
+            for (all, card) in ranks.iter_mut().zip(child.iter()) {
+                *all |= card;
+            }
+        }
+        (best, ranks)
+    }
+
+    fn play(&mut self, seat: usize, suit: usize, bit: u16, alpha: i32, beta: i32) -> (i32, Ranks) {
+        self.hands[seat][suit] &= !bit;
+        self.trick[self.trick_len] = (seat, suit, bit);
+        self.trick_len += 1;
+
+        let result = if self.trick_len == 4 {
+            let winner = self.trick_winner();
+            let won = if is_north_south(winner) { 1 } else { 0 };
+            let winning_rank = self.winning_rank();
+            let (leader, trick) = (self.leader, self.trick);
+            for &(_, suit, bit) in trick.iter() {
+                self.in_play[suit] &= !bit;
+            }
+            self.leader = winner;
+            self.trick_len = 0;
+            let (value, mut ranks) = self.search(alpha - won, beta - won);
+            self.leader = leader;
+            self.trick = trick;
+            self.trick_len = 4;
+            for &(_, suit, bit) in trick.iter() {
+                self.in_play[suit] |= bit;
+            }
+            for (all, card) in ranks.iter_mut().zip(winning_rank.iter()) {
+                *all |= card;
+            }
+            (won + value, ranks)
+        } else {
+            self.search(alpha, beta)
+        };
+
+        self.trick_len -= 1;
+        self.hands[seat][suit] |= bit;
+        result
+    }
+
+    /// The winning card of a complete trick, if it won by being higher than another card of
+    /// its suit rather than by being the only one.
+    fn winning_rank(&self) -> Ranks {
+        let (_, (suit, bit)) = self.current_winner();
+        let mut ranks = [0; 4];
+        if self.trick[..4]
+            .iter()
+            .filter(|(_, s, _)| *s == suit)
+            .count()
+            > 1
+        {
+            ranks[suit] = bit;
+        }
+        ranks
+    }
+
+    fn beats(&self, card: (usize, u16), best: (usize, u16)) -> bool {
+        if card.0 == best.0 {
+            card.1 > best.1
+        } else {
+            card.0 == self.trump
+        }
+    }
+
+    /// Returns the seat currently winning the trick along with its card.
+    fn current_winner(&self) -> (usize, (usize, u16)) {
+        let (mut seat, suit, bit) = self.trick[0];
+        let mut best = (suit, bit);
+        for &(s, suit, bit) in self.trick[1..self.trick_len].iter() {
+            if self.beats((suit, bit), best) {
+                best = (suit, bit);
+                seat = s;
+            }
+        }
+        (seat, best)
+    }
+
+    fn trick_winner(&self) -> usize {
+        self.current_winner().0
+    }
+
+    /// Works out the cards still in play in each suit, including those on the table, which
+    /// the search then keeps up to date as tricks are completed.
+    fn reset_in_play(&mut self) {
+        let mut cards = [0; 4];
+        for hand in self.hands.iter() {
+            for (cards, mask) in cards.iter_mut().zip(hand.iter()) {
+                *cards |= mask;
+            }
+        }
+        for &(_, suit, bit) in self.trick[..self.trick_len].iter() {
+            cards[suit] |= bit;
+        }
+        self.in_play = cards;
+    }
+
+    /// Returns the legal plays for the seat, grouped into runs of equivalent cards. Each run
+    /// is returned as its suit and a mask of the cards in it.
+    fn groups(&self, seat: usize) -> Moves {
+        let hand = self.hands[seat];
+        let led = if self.trick_len > 0 && hand[self.trick[0].1] != 0 {
+            Some(self.trick[0].1)
+        } else {
+            None
+        };
+
+        let mut groups = Moves::default();
+        for (suit, holding) in hand.iter().enumerate() {
+            if *holding == 0 || led.map_or(false, |l| l != suit) {
+                continue;
+            }
+            // A card held elsewhere, or already on the table, ends a run.
+            let others = self.in_play[suit] & !holding;
+            let mut left = *holding;
+            while left != 0 {
+                let gap = highest_bit(others & (highest_bit(left) - 1));
+                let run = if gap == 0 {
+                    left
+                } else {
+                    left & !((gap << 1) - 1)
+                };
+                groups.push(0, suit, run);
+                left &= !run;
+            }
+        }
+        groups
+    }
+
+    /// Returns one card from each run of equivalent cards, most promising first. When
+    /// leading, the suits that last caused a cutoff come first.
+    fn ordered_moves(&self, seat: usize) -> Moves {
+        let mut moves = self.groups(seat);
+        let follow = if self.trick_len > 0 {
+            Some(self.follow(seat))
+        } else {
+            None
+        };
+        let killers = match follow {
+            Some(_) => [NO_SUIT; 2],
+            None => self.killers[self.hand_len(seat)][seat],
+        };
+        for play in moves.list[..moves.len].iter_mut() {
+            let (_, suit, run) = *play;
+            let (score, bit) = match &follow {
+                Some(follow) => {
+                    let bit = lowest_bit(run);
+                    (self.follow_score(follow, seat, suit, bit), bit)
+                }
+                None => {
+                    let bit = highest_bit(run);
+                    let killer = if killers[0] == suit as u8 {
+                        2000
+                    } else if killers[1] == suit as u8 {
+                        1000
+                    } else {
+                        0
+                    };
+                    (
+                        killer + self.lead_score(seat, suit, bit) + lowness(bit),
+                        bit,
+                    )
+                }
+            };
+            *play = (score, suit, bit);
+        }
+        moves.list[..moves.len].sort_unstable_by_key(|m| -m.0);
+        moves
+    }
+
+    /// Remembers the suit of a lead that caused a cutoff, to try it early from other
+    /// positions where the seat has as many cards left.
+    fn add_killer(&mut self, seat: usize, suit: usize) {
+        if self.trick_len > 0 {
+            return;
+        }
+        let cards = self.hand_len(seat);
+        let killers = &mut self.killers[cards][seat];
+        if killers[0] != suit as u8 {
+            killers[1] = killers[0];
+            killers[0] = suit as u8;
+        }
+    }
+
+    fn follow(&self, seat: usize) -> Follow {
+        let (winner, best) = self.current_winner();
+        let led = self.trick[0].1;
+        let next_opponent = if self.trick_len < 3 {
+            Some((seat + 1) % 4)
+        } else {
+            None
+        };
+        let partner_winning = winner == (seat + 2) % 4
+            && !next_opponent.map_or(false, |o| self.can_beat(o, best, led));
+        Follow {
+            led,
+            best,
+            partner_winning,
+            next_opponent,
+        }
+    }
+
+    fn follow_score(&self, follow: &Follow, seat: usize, suit: usize, bit: u16) -> i32 {
+        let lowness = lowness(bit);
+        let led = follow.led;
+
+        if follow.partner_winning {
+            // Partner has the trick; play low, and don't waste a trump.
+            return if suit == self.trump && led != self.trump {
+                lowness
+            } else {
+                90 + lowness
+            };
+        }
+        if self.beats((suit, bit), follow.best) {
+            let beaten_later = follow
+                .next_opponent
+                .map_or(false, |o| self.can_beat(o, (suit, bit), led));
+            if !beaten_later {
+                return 80 + lowness;
+            }
+            return 30 + lowness;
+        }
+        if suit != led {
+            // A discard, preferably from a suit with nothing worth keeping.
+            let top = highest_bit(self.in_play[suit]);
+            return if self.hands[seat][suit] & top != 0 {
+                lowness
+            } else {
+                50 + lowness
+            };
+        }
+        60 + lowness
+    }
+
+    fn lead_score(&self, seat: usize, suit: usize, bit: u16) -> i32 {
+        let partner = (seat + 2) % 4;
+        let lho = (seat + 1) % 4;
+        let rho = (seat + 3) % 4;
+        let top = highest_bit(self.in_play[suit]);
+        let ruffs = |s: usize| {
+            self.trump != NO_TRUMP
+                && suit != self.trump
+                && self.hands[s][suit] == 0
+                && self.hands[s][self.trump] != 0
+        };
+
+        if ruffs(lho) || ruffs(rho) {
+            return 0;
+        }
+        if ruffs(partner) {
+            return 70;
+        }
+        if bit == top {
+            return 60;
+        }
+        if self.hands[partner][suit] & top != 0 {
+            return 55;
+        }
+        if self.hands[lho][suit] & top != 0 {
+            return 10;
+        }
+        30
+    }
+
+    /// Whether the seat, playing later to the trick, holds a card that beats the passed in
+    /// card.
+    fn can_beat(&self, seat: usize, card: (usize, u16), led: usize) -> bool {
+        let hand = self.hands[seat];
+        if hand[led] != 0 {
+            return card.0 == led && hand[led] > card.1 && highest_bit(hand[led]) > card.1;
+        }
+        if self.trump == NO_TRUMP || hand[self.trump] == 0 {
+            return false;
+        }
+        card.0 != self.trump || highest_bit(hand[self.trump]) > card.1
+    }
+
+    /// Tricks the leader's side can take off the top: either the leader's own winners, or
+    /// the partner's winners after leading a small card over to them. At no trump, where
+    /// nobody can ruff, the leader can cash their own winners before crossing over. Also
+    /// returns the top cards the count relies on.
+    fn quick_tricks(&self) -> (i32, Ranks) {
+        let leader = self.leader;
+        let partner = (leader + 2) % 4;
+        let mut ranks = self.top_winners(leader);
+        let own = self.cashing_tricks(leader);
+        let crossing = (0..4).any(|suit| {
+            self.hands[leader][suit] != 0
+                && top_sequence(self.in_play[suit], self.hands[partner][suit]) > 0
+                && (self.trump == NO_TRUMP || suit == self.trump || self.opponents_follow(suit))
+        });
+        if !crossing {
+            return (own, ranks);
+        }
+        for (all, card) in ranks.iter_mut().zip(self.top_winners(partner).iter()) {
+            *all |= card;
+        }
+        let partner_tricks = self.cashing_tricks(partner);
+        if self.trump == NO_TRUMP {
+            // Only count the leader's winners that partner can follow to, so that partner
+            // doesn't have to throw away winners of their own.
+            let before_crossing: i32 = (0..4)
+                .zip(self.suit_winners(leader).iter())
+                .map(|(suit, winners)| min(*winners, self.length(partner, suit)))
+                .sum();
+            return (max(own, before_crossing + partner_tricks), ranks);
+        }
+        (max(own, partner_tricks), ranks)
+    }
+
+    /// Tricks the seat's side is sure to take with the trumps at the top of the suit, along
+    /// with those trumps. Whenever one of them is played it wins the trick.
+    fn master_trumps(&self, seat: usize) -> (i32, Ranks) {
+        let mut ranks = [0; 4];
+        if self.trump == NO_TRUMP {
+            return (0, ranks);
+        }
+        let top = highest_bit(self.in_play[self.trump]);
+        for seat in [seat, (seat + 2) % 4].iter() {
+            if self.hands[*seat][self.trump] & top != 0 {
+                ranks[self.trump] = self.top_winners(*seat)[self.trump];
+            }
+        }
+        (ranks[self.trump].count_ones() as i32, ranks)
+    }
+
+    /// The cards at the top of each suit that the seat holds.
+    fn top_winners(&self, seat: usize) -> Ranks {
+        let mut ranks = [0; 4];
+        for (suit, ranks) in ranks.iter_mut().enumerate() {
+            let in_play = self.in_play[suit];
+            let mut bit = ACE_BIT;
+            while bit >= DEUCE_BIT {
+                if in_play & bit != 0 {
+                    if self.hands[seat][suit] & bit == 0 {
+                        break;
+                    }
+                    *ranks |= bit;
+                }
+                bit >>= 1;
+            }
+        }
+        ranks
+    }
+
+    /// Whether both opponents of the leader still hold cards in the suit.
+    fn opponents_follow(&self, suit: usize) -> bool {
+        let leader = self.leader;
+        self.hands[(leader + 1) % 4][suit] != 0 && self.hands[(leader + 3) % 4][suit] != 0
+    }
+
+    /// The tricks the seat can take in each suit by cashing its top cards.
+    fn suit_winners(&self, seat: usize) -> [i32; 4] {
+        let opponents = [(seat + 1) % 4, (seat + 3) % 4];
+        let mut winners = [0; 4];
+        for (suit, winner) in winners.iter_mut().enumerate() {
+            let top = top_sequence(self.in_play[suit], self.hands[seat][suit]);
+            let longest_opponent = max(
+                self.length(opponents[0], suit),
+                self.length(opponents[1], suit),
+            );
+            // Once the opponents are out of the suit the small cards are winners too, as long
+            // as partner has run out as well and can't take over the lead.
+            *winner = if top >= longest_opponent && self.length((seat + 2) % 4, suit) <= top {
+                self.length(seat, suit)
+            } else {
+                top
+            };
+        }
+        winners
+    }
+
+    /// Tricks the seat can take by cashing its top cards once it has the lead.
+    fn cashing_tricks(&self, seat: usize) -> i32 {
+        let opponents = [(seat + 1) % 4, (seat + 3) % 4];
+        let winners = self.suit_winners(seat);
+        if self.trump == NO_TRUMP {
+            return winners.iter().sum();
+        }
+
+        // Cash the top trumps first. An opponent with trumps left after that can ruff, so only
+        // count the rounds of each side suit that they can still follow to.
+        let trumps = winners[self.trump];
+        let can_ruff = |seat: &&usize| self.length(**seat, self.trump) > trumps;
+        let side: i32 = (0..4)
+            .filter(|s| *s != self.trump)
+            .map(|s| {
+                opponents
+                    .iter()
+                    .filter(can_ruff)
+                    .fold(winners[s], |w, o| min(w, self.length(*o, s)))
+            })
+            .sum();
+        trumps + side
+    }
+
+    /// Transposition table key: the leader, the trumps and the length of each suit in each
+    /// hand.
+    fn key(&self) -> u128 {
+        let mut key = (self.leader + 4 * self.trump) as u128;
+        for hand in self.hands.iter() {
+            for mask in hand.iter() {
+                key = (key << 4) | mask.count_ones() as u128;
+            }
+        }
+        key
+    }
+
+    /// Who holds each card still in play, two bits for each card from the top of each suit
+    /// down, with each suit in its own `SUIT_BITS` bits.
+    fn layout(&self) -> u128 {
+        let mut layout = 0;
+        for suit in 0..4 {
+            let held = |seat: usize, bit: u16| (self.hands[seat][suit] & bit != 0) as u32;
+            let mut holders = 0;
+            let mut shift = SUIT_BITS;
+            let mut cards = self.in_play[suit];
+            while cards != 0 {
+                let bit = highest_bit(cards);
+                cards ^= bit;
+                shift -= 2;
+                holders |= (held(1, bit) + 2 * held(2, bit) + 3 * held(3, bit)) << shift;
+            }
+            layout |= u128::from(holders) << (SUIT_BITS * suit as u32);
+        }
+        layout
+    }
+
+    /// The layout bits for the top card of each suit still in play.
+    fn tops(&self) -> u128 {
+        let mut tops = 0;
+        for suit in 0..4 {
+            if self.in_play[suit] != 0 {
+                tops |= 3 << (SUIT_BITS * (suit as u32 + 1) - 2);
+            }
+        }
+        tops
+    }
+
+    /// The cards at the top of each suit whose holders are covered by an entry's mask.
+    fn top_cards(&self, mask: u128) -> Ranks {
+        let mut ranks = [0; 4];
+        for (suit, ranks) in ranks.iter_mut().enumerate() {
+            let in_play = self.in_play[suit];
+            let field = (mask >> (SUIT_BITS * suit as u32)) & ((1 << SUIT_BITS) - 1);
+            let mut left = field.count_ones() / 2;
+            let mut bit = ACE_BIT;
+            while left > 0 && bit >= DEUCE_BIT {
+                if in_play & bit != 0 {
+                    *ranks |= bit;
+                    left -= 1;
+                }
+                bit >>= 1;
+            }
+        }
+        ranks
+    }
+}
+
+/// Rank bits for each suit, used for the cards whose ranks decided a search result.
+type Ranks = [u16; 4];
+
+/// Bounds on the tricks North-South take, which hold for every position with the same key
+/// where the cards covered by `mask` are held by the same seats.
+#[derive(Clone, Copy)]
+struct Entry {
+    mask: u128,
+    holders: u128,
+    lo: i8,
+    hi: i8,
+}
+
+impl Entry {
+    /// An entry for bounds that only depend on the cards in `ranks`, and so on every card
+    /// above the lowest of them in each suit.
+    fn new(layout: u128, in_play: &[u16; 4], ranks: &Ranks, lo: i32, hi: i32) -> Entry {
+        let mut mask = 0;
+        for suit in 0..4 {
+            let lowest = lowest_bit(ranks[suit]);
+            if lowest != 0 {
+                let depth = 2 * (in_play[suit] & !(lowest - 1)).count_ones();
+                let top = SUIT_BITS * (suit as u32 + 1);
+                mask |= ((1u128 << depth) - 1) << (top - depth);
+            }
+        }
+        Entry {
+            mask,
+            holders: layout & mask,
+            lo: lo as i8,
+            hi: hi as i8,
+        }
+    }
+
+    /// Whether the position with this layout has the entry's cards held by the same seats.
+    fn matches(&self, layout: u128) -> bool {
+        layout & self.mask == self.holders
+    }
+
+    /// Whether this entry applies to every position the other does, with bounds at least as
+    /// tight.
+    fn subsumes(&self, other: &Entry) -> bool {
+        self.mask & other.mask == self.mask
+            && self.matches(other.holders)
+            && self.lo >= other.lo
+            && self.hi <= other.hi
+    }
+}
+
+/// The transposition table, cleared once it holds `TABLE_LIMIT` entries.
+#[derive(Default)]
+struct Table {
+    entries: HashMap<(u128, u128), Vec<Entry>, BuildHasherDefault<KeyHasher>>,
+    len: usize,
+}
+
+impl Table {
+    /// Returns a bound for the position that falls outside the window, if there is one,
+    /// along with the mask of the entry that gave it.
+    fn probe(
+        &self,
+        key: u128,
+        layout: u128,
+        tops: u128,
+        alpha: i32,
+        beta: i32,
+    ) -> Option<(i32, u128)> {
+        let general = self.entries.get(&(key, GENERAL));
+        let specific = self.entries.get(&(key, layout & tops));
+        for entry in general.into_iter().chain(specific).flatten() {
+            if !entry.matches(layout) {
+                continue;
+            }
+            let (lo, hi) = (i32::from(entry.lo), i32::from(entry.hi));
+            if lo >= beta {
+                return Some((lo, entry.mask));
+            }
+            if hi <= alpha {
+                return Some((hi, entry.mask));
+            }
+        }
+        None
+    }
+
+    /// Adds an entry, unless one that applies to more positions already has bounds as
+    /// tight. Entries that the new one makes redundant are dropped.
+    ///
+    /// Entries that cover the top card of every suit are kept apart, by who holds those
+    /// cards, so that probes only look through the ones that could match.
+    fn store(&mut self, key: u128, tops: u128, entry: Entry) {
+        if self.len >= TABLE_LIMIT {
+            self.entries.clear();
+            self.len = 0;
+        }
+        let bucket = if entry.mask & tops == tops {
+            entry.holders & tops
+        } else {
+            GENERAL
+        };
+        if bucket != GENERAL {
+            let general = self.entries.get(&(key, GENERAL));
+            if general.into_iter().flatten().any(|e| e.subsumes(&entry)) {
+                return;
+            }
+        }
+        let entries = self.entries.entry((key, bucket)).or_default();
+        if entries.iter().any(|e| e.subsumes(&entry)) {
+            return;
+        }
+        let before = entries.len();
+        entries.retain(|e| !entry.subsumes(e));
+        self.len = self.len + 1 + entries.len() - before;
+        entries.push(entry);
+    }
+}
+
+/// The position keys are already well mixed, so a multiply and fold is all the hashing the
+/// transposition table needs.
+#[derive(Default)]
+struct KeyHasher(u64);
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.write_u64(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.0 = (self.0.rotate_left(5) ^ i).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write_u64(i as u64);
+        self.write_u64((i >> 64) as u64);
+    }
+}
+
+fn is_north_south(seat: usize) -> bool {
+    seat % 2 == 0
+}
+
+/// What a seat following to a trick needs to know about it.
+struct Follow {
+    led: usize,
+    best: (usize, u16),
+    /// Partner is winning the trick, and the opponent still to play can't beat them.
+    partner_winning: bool,
+    /// The opponent still to play after this seat, if there is one.
+    next_opponent: Option<usize>,
+}
+
+/// A fixed size list of plays, each with an ordering score, a suit and the rank bits.
+#[derive(Default)]
+struct Moves {
+    list: [(i32, usize, u16); 13],
+    len: usize,
+}
+
+impl Moves {
+    fn push(&mut self, score: i32, suit: usize, bits: u16) {
+        self.list[self.len] = (score, suit, bits);
+        self.len += 1;
+    }
+}
+
+/// Orders cards from low to high, for playing the cheapest card first.
+fn lowness(bit: u16) -> i32 {
+    16 - bit.trailing_zeros() as i32
+}
+
+fn lowest_bit(mask: u16) -> u16 {
+    mask & mask.wrapping_neg()
+}
+
+fn highest_bit(mask: u16) -> u16 {
+    match mask {
+        0 => 0,
+        _ => 1 << (15 - mask.leading_zeros()),
+    }
+}
+
+/// Counts the cards at the top of the suit that are held in the hand.
+fn top_sequence(in_play: u16, hand: u16) -> i32 {
+    let mut count = 0;
+    let mut bit = ACE_BIT;
+    while bit >= DEUCE_BIT {
+        if in_play & bit != 0 {
+            if hand & bit == 0 {
+                break;
+            }
+            count += 1;
+        }
+        bit >>= 1;
+    }
+    count
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_dds_tests {
+    use super::*;
+    use crate::cards::rank::*;
+    use crate::cards::suit::*;
+
+    const PBN_TEST_STRING: &str =
+        "S:Q42.Q52.AQT943.Q 97.AT93.652.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982";
+
+    #[test]
+    fn card_to_bit__bit_to_card() {
+        let card = Card::new(TEN, HEARTS);
+
+        assert_eq!(Some((1, 1 << 10)), card_to_bit(&card));
+        assert_eq!(card, bit_to_card(1, 1 << 10));
+    }
+
+    #[test]
+    fn top_sequence() {
+        assert_eq!(
+            2,
+            super::top_sequence(0b0111_0000_0000_0100, 0b0110_0000_0000_0000)
+        );
+        assert_eq!(
+            0,
+            super::top_sequence(0b0111_0000_0000_0100, 0b0010_0000_0000_0100)
+        );
+    }
+
+    #[test]
+    fn tricks__all_top_cards() {
+        let board = BridgeBoard::from_pbn_deal(
+            "N:AKQJ.AKQ.AKQ.AKQ T987.JT9.JT9.JT9 6543.876.876.876 2.5432.5432.5432",
+        );
+        let table = board.double_dummy().unwrap();
+
+        for strain in [Strain::S, Strain::NT].iter() {
+            assert_eq!(13, table.get(strain, &BridgeDirection::N));
+            assert_eq!(13, table.get(strain, &BridgeDirection::S));
+            assert_eq!(0, table.get(strain, &BridgeDirection::E));
+        }
+        // West's fourth heart is a trick when hearts are trumps.
+        assert_eq!(12, table.get(&Strain::H, &BridgeDirection::N));
+        assert_eq!(1, table.get(&Strain::H, &BridgeDirection::W));
+    }
+
+    #[test]
+    fn solve__ending() {
+        // West must lead a spade to reach East's winners. Leading a heart lets North win
+        // the ace and give up only one trick.
+        let board = BridgeBoard::from_pbn_deal("N:3.A.. AK... 4.3.. 2.2..");
+        let mut solver = DoubleDummy::new(&board, Strain::NT);
+
+        let solution = solver.solve(&BridgeDirection::W, &[]).unwrap();
+
+        assert_eq!(BridgeDirection::W, solution.player);
+        assert_eq!(2, solution.tricks);
+        assert_eq!(
+            Pile::new_from_vector(vec![Card::new(TWO, SPADES)]),
+            solution.cards
+        );
+    }
+
+    #[test]
+    fn solve__equivalent_cards() {
+        let board = BridgeBoard::from_pbn_deal("N:AK2... 543... QJT... 876...");
+        let mut solver = DoubleDummy::new(&board, Strain::NT);
+
+        let solution = solver.solve(&BridgeDirection::W, &[]).unwrap();
+
+        assert_eq!(0, solution.tricks);
+        assert_eq!(3, solution.cards.len());
+    }
+
+    #[test]
+    fn solve__trump_ending() {
+        // With spades as trumps South's deuce ruffs West's diamond.
+        let board = BridgeBoard::from_pbn_deal("S:2.A.. .K.2. .Q..2 .2.3.");
+        let mut solver = DoubleDummy::new(&board, Strain::S);
+
+        assert_eq!(2, solver.tricks(&BridgeDirection::S));
+        assert_eq!(0, solver.tricks(&BridgeDirection::W));
+    }
+
+    #[test]
+    fn solve__mid_trick() {
+        let board = BridgeBoard::from_pbn_deal("N:A.K.. K.A.. 2.2.. 3...");
+        let mut solver = DoubleDummy::new(&board, Strain::NT);
+
+        // West has led the queen of spades, so North has to win the ace.
+        let solution = solver
+            .solve(&BridgeDirection::W, &[Card::new(QUEEN, SPADES)])
+            .unwrap();
+
+        assert_eq!(BridgeDirection::N, solution.player);
+        assert_eq!(1, solution.tricks);
+        assert_eq!(
+            Pile::new_from_vector(vec![Card::new(ACE, SPADES)]),
+            solution.cards
+        );
+    }
+
+    #[test]
+    fn can_take() {
+        let board = BridgeBoard::from_pbn_deal("N:A.K.. K.A.. 2.2.. 3...");
+        let mut solver = DoubleDummy::new(&board, Strain::NT);
+        let queen = [Card::new(QUEEN, SPADES)];
+        let (west, north) = (BridgeDirection::W, BridgeDirection::N);
+
+        assert_eq!(Some(true), solver.can_take(&west, &queen, &north, 1));
+        assert_eq!(Some(false), solver.can_take(&west, &queen, &north, 2));
+        assert_eq!(Some(true), solver.can_take(&west, &queen, &west, 1));
+        assert_eq!(Some(false), solver.can_take(&west, &queen, &west, 2));
+        assert_eq!(Some(true), solver.can_take(&west, &queen, &west, 0));
+        assert_eq!(None, solver.can_take(&west, &[], &north, 1));
+    }
+
+    #[test]
+    fn solve__invalid() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+        let mut solver = DoubleDummy::new(&board, Strain::NT);
+
+        assert!(solver.solve(&BridgeDirection::UNKNOWN, &[]).is_none());
+    }
+
+    #[test]
+    fn solve__invalid_trick() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+        let mut solver = DoubleDummy::new(&board, Strain::NT);
+        let north = board.get_hand(&BridgeDirection::N).unwrap().clone();
+        let card = north.cards().first().unwrap().clone();
+        let lead = [card.clone()];
+
+        // North still holds the card.
+        assert!(solver.solve(&BridgeDirection::N, &lead).is_none());
+
+        // Once it's out of North's hand it can only be played once.
+        let mut played = board.clone();
+        played
+            .get_hand_mut(&BridgeDirection::N)
+            .unwrap()
+            .remove_card(&card);
+        let mut solver = DoubleDummy::new(&played, Strain::NT);
+        assert!(solver.solve(&BridgeDirection::N, &lead).is_some());
+        assert!(solver
+            .solve(&BridgeDirection::N, &[card.clone(), card.clone()])
+            .is_none());
+        // North is a card short to be on lead, and East can't have led North's card.
+        assert!(solver.solve(&BridgeDirection::N, &[]).is_none());
+        assert!(solver.solve(&BridgeDirection::E, &lead).is_none());
+    }
+
+    #[test]
+    fn solve__revoke() {
+        let led = [Card::new(TWO, SPADES), Card::new(ACE, HEARTS)];
+
+        // North can discard the ace of hearts on West's spade lead only if out of spades.
+        let board = BridgeBoard::from_pbn_deal("N:.2.. KJ... QT... 9...");
+        let mut solver = DoubleDummy::new(&board, Strain::NT);
+        assert!(solver.solve(&BridgeDirection::W, &led).is_some());
+
+        let board = BridgeBoard::from_pbn_deal("N:3... KJ... QT... 9...");
+        let mut solver = DoubleDummy::new(&board, Strain::NT);
+        assert!(solver.solve(&BridgeDirection::W, &led).is_none());
+    }
+
+    /// Plain minimax over every legal card, for checking the solver against.
+    fn brute_force(hands: &mut [Vec<Card>; 4], trump: Option<usize>, leader: usize) -> usize {
+        fn go(
+            hands: &mut [Vec<Card>; 4],
+            trump: Option<usize>,
+            leader: usize,
+            trick: &mut Vec<(usize, Card)>,
+        ) -> usize {
+            if trick.len() == 4 {
+                let led = suit_index(&trick[0].1);
+                let key = |c: &Card| {
+                    let suit = suit_index(c);
+                    let rank = 13 - rank_index(c).unwrap();
+                    if suit == trump {
+                        rank + 100
+                    } else if suit == led {
+                        rank + 50
+                    } else {
+                        0
+                    }
+                };
+                let winner = trick.iter().max_by_key(|(_, c)| key(c)).unwrap().0;
+                let mut next = Vec::new();
+                return is_north_south(winner) as usize + go(hands, trump, winner, &mut next);
+            }
+            let seat = (leader + trick.len()) % 4;
+            if hands[seat].is_empty() {
+                return 0;
+            }
+            let led = trick.first().map(|(_, c)| suit_index(c));
+            let follows = hands[seat].iter().any(|c| Some(suit_index(c)) == led);
+            let mut best: Option<usize> = None;
+            for i in 0..hands[seat].len() {
+                if follows && Some(suit_index(&hands[seat][i])) != led {
+                    continue;
+                }
+                let card = hands[seat].remove(i);
+                trick.push((seat, card.clone()));
+                let value = go(hands, trump, leader, trick);
+                trick.pop();
+                hands[seat].insert(i, card);
+                best = Some(match best {
+                    None => value,
+                    Some(b) if is_north_south(seat) => b.max(value),
+                    Some(b) => b.min(value),
+                });
+            }
+            best.unwrap()
+        }
+        go(hands, trump, leader, &mut Vec::new())
+    }
+
+    #[test]
+    fn tricks__matches_brute_force() {
+        for _ in 0..20 {
+            let mut cards = Pile::french_deck().shuffle();
+            let board = BridgeBoard {
+                north: cards.draw(3).unwrap(),
+                east: cards.draw(3).unwrap(),
+                south: cards.draw(3).unwrap(),
+                west: cards.draw(3).unwrap(),
+                ..Default::default()
+            };
+
+            for strain in Strain::all() {
+                let mut solver = DoubleDummy::new(&board, strain);
+                for (seat, leader) in BridgeDirection::seats().iter().enumerate() {
+                    let mut hands = [
+                        board.north.cards().clone(),
+                        board.east.cards().clone(),
+                        board.south.cards().clone(),
+                        board.west.cards().clone(),
+                    ];
+                    let ns = brute_force(&mut hands, strain.suit_index(), seat);
+                    let expected = if is_north_south(seat) { ns } else { 3 - ns };
+
+                    assert_eq!(expected, solver.tricks(leader));
+                    assert_eq!(Some(true), solver.can_take(leader, &[], leader, expected));
+                    assert_eq!(
+                        Some(false),
+                        solver.can_take(leader, &[], leader, expected + 1)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn trick_table() {
+        let board = BridgeBoard::from_pbn_deal("S:2.A.. .K.2. .Q..2 .2.3.");
+
+        let table = board.double_dummy().unwrap();
+
+        assert_eq!(Some(table), DoubleDummy::trick_table(&board));
+        assert_eq!(2, table.get(&Strain::S, &BridgeDirection::N));
+        for strain in Strain::all() {
+            let mut solver = DoubleDummy::new(&board, strain);
+            for declarer in BridgeDirection::seats() {
+                let defence = solver.tricks(&declarer.lho());
+                assert_eq!(2 - defence, table.get(&strain, &declarer));
+            }
+        }
+    }
+
+    #[test]
+    fn trick_table__incomplete() {
+        let uneven = BridgeBoard::from_pbn_deal("S:2.A.. .K.2. .Q..2 .2..");
+
+        assert_eq!(None, BridgeBoard::default().double_dummy());
+        assert_eq!(None, uneven.double_dummy());
+    }
+
+    #[test]
+    fn trick_table__display() {
+        let mut table = TrickTable::default();
+        table.set(&Strain::NT, &BridgeDirection::N, 9);
+
+        let display = table.to_string();
+
+        assert!(display.starts_with("   S  H  D  C NT"));
+        assert!(display.contains("N  0  0  0  0  9"));
+    }
+}
@@ -0,0 +1,246 @@
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+use crate::cards::decks::bridge::numbering::DEAL_COUNT;
+use crate::cards::decks::bridge::BridgeBoard;
+
+const BOARD_DOMAIN: &[u8] = b"cardpack deal set board";
+const COMMITMENT_DOMAIN: &[u8] = b"cardpack deal set commitment";
+
+/// The size of the seeds made by `DealSet::random()`.
+pub const SEED_SIZE: usize = 32;
+
+/// A numbered set of boards for a tournament that can't be predicted before the event, but
+/// that anyone can check afterwards.
+///
+/// Each board is worked out from a secret seed and its board number with SHA-256: the hash
+/// is turned into a deal number, hashing again with a counter in the rare case that it is
+/// too large, so that every deal is equally likely. Before the event the director publishes
+/// the commitment, a hash of the seed and the number of boards, which gives nothing away
+/// about the deals. Afterwards the seed is revealed, and anyone can check that it matches
+/// the commitment and regenerate the boards that were played.
+///
+/// # Usage:
+/// ```
+/// use cardpack::DealSet;
+///
+/// let set = DealSet::random(28);
+/// let commitment = set.commitment();
+/// let boards = set.boards();
+///
+/// // After the event the seed is revealed.
+/// let revealed = DealSet::from_hex(&set.seed_hex(), 28).unwrap();
+///
+/// assert!(revealed.verify(&commitment, &boards));
+/// assert_eq!(28, boards.len());
+/// assert_eq!(5, boards[4].number);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DealSet {
+    seed: Vec<u8>,
+    boards: usize,
+}
+
+impl DealSet {
+    pub fn new(seed: &[u8], boards: usize) -> DealSet {
+        DealSet {
+            seed: seed.to_vec(),
+            boards,
+        }
+    }
+
+    /// Makes a set with a seed from the operating system's secure random number generator.
+    pub fn random(boards: usize) -> DealSet {
+        let mut seed = [0; SEED_SIZE];
+        OsRng.fill_bytes(&mut seed);
+        DealSet::new(&seed, boards)
+    }
+
+    /// Makes a set from a seed written in hexadecimal, as revealed after the event.
+    pub fn from_hex(seed: &str, boards: usize) -> Option<DealSet> {
+        let seed = seed.trim();
+        if seed.len() % 2 != 0 {
+            return None;
+        }
+        let bytes = (0..seed.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(seed.get(i..i + 2)?, 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        Some(DealSet::new(&bytes, boards))
+    }
+
+    pub fn seed_hex(&self) -> String {
+        to_hex(&self.seed)
+    }
+
+    /// The number of boards in the set.
+    pub fn len(&self) -> usize {
+        self.boards
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boards == 0
+    }
+
+    /// The SHA-256 hash, in hexadecimal, that is published before the event. It covers the
+    /// seed and the number of boards.
+    pub fn commitment(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(COMMITMENT_DOMAIN);
+        hasher.update((self.boards as u64).to_be_bytes());
+        hasher.update(&self.seed);
+        to_hex(&hasher.finalize())
+    }
+
+    /// Returns the board with the number, from one up to the number of boards in the set,
+    /// with the dealer and vulnerability that go with its number.
+    pub fn board(&self, number: usize) -> Option<BridgeBoard> {
+        if number == 0 || number > self.boards {
+            return None;
+        }
+        let mut board = BridgeBoard::from_deal_number(self.deal_number(number))?;
+        board.set_number(number);
+        Some(board)
+    }
+
+    pub fn boards(&self) -> Vec<BridgeBoard> {
+        (1..=self.boards)
+            .filter_map(|number| self.board(number))
+            .collect()
+    }
+
+    /// Returns true if the seed matches a published commitment.
+    pub fn verify_commitment(&self, commitment: &str) -> bool {
+        self.commitment().eq_ignore_ascii_case(commitment.trim())
+    }
+
+    /// Returns true if the seed matches the commitment and every board played is the board
+    /// with its number in the set.
+    pub fn verify(&self, commitment: &str, boards: &[BridgeBoard]) -> bool {
+        self.verify_commitment(commitment)
+            && boards
+                .iter()
+                .all(|board| self.board(board.number).as_ref() == Some(board))
+    }
+
+    /// Hashes the seed, the board number and a counter, taking the first twelve bytes as a
+    /// number below 2^96 and trying the next counter until it is below `DEAL_COUNT`.
+    fn deal_number(&self, number: usize) -> u128 {
+        let mut counter: u32 = 0;
+        loop {
+            let mut hasher = Sha256::new();
+            hasher.update(BOARD_DOMAIN);
+            hasher.update(&self.seed);
+            hasher.update((number as u64).to_be_bytes());
+            hasher.update(counter.to_be_bytes());
+            let hash = hasher.finalize();
+
+            let mut bytes = [0; 16];
+            bytes[4..].copy_from_slice(&hash[..12]);
+            let candidate = u128::from_be_bytes(bytes);
+            if candidate < DEAL_COUNT {
+                return candidate;
+            }
+            counter += 1;
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_dealset_tests {
+    use super::*;
+
+    fn set() -> DealSet {
+        DealSet::new(b"correct horse battery staple", 8)
+    }
+
+    #[test]
+    fn board() {
+        let set = set();
+
+        let board = set.board(3).unwrap();
+
+        assert!(board.is_valid());
+        assert_eq!(3, board.number);
+        assert_eq!(Some(board), set.board(3));
+        assert_ne!(set.board(3), set.board(4));
+        assert_eq!(None, set.board(0));
+        assert_eq!(None, set.board(9));
+    }
+
+    #[test]
+    fn board__known_answer() {
+        // Pins the hashing scheme, so that sets published with one version of the crate can
+        // still be checked with a later one.
+        let board = set().board(1).unwrap();
+
+        assert_eq!(
+            Some(31_000_437_991_037_168_273_684_683_557),
+            board.deal_number()
+        );
+        assert_eq!(
+            "97b1cf8fbb7a820cf5ad6e1a58d2037e9d8bf30ea2f63eb429c5475887d77bbe",
+            set().commitment()
+        );
+    }
+
+    #[test]
+    fn boards__depend_on_seed() {
+        let other = DealSet::new(b"correct horse battery stapler", 8);
+
+        assert_eq!(8, set().boards().len());
+        assert!(set()
+            .boards()
+            .iter()
+            .zip(other.boards().iter())
+            .all(|(a, b)| a != b));
+    }
+
+    #[test]
+    fn commitment() {
+        let set = set();
+
+        assert_eq!(64, set.commitment().len());
+        assert!(set.verify_commitment(&set.commitment().to_uppercase()));
+        assert_ne!(
+            set.commitment(),
+            DealSet::new(b"correct horse battery staple", 9).commitment()
+        );
+        assert_ne!(
+            set.commitment(),
+            DealSet::new(b"another seed", 8).commitment()
+        );
+    }
+
+    #[test]
+    fn verify() {
+        let set = set();
+        let commitment = set.commitment();
+        let mut boards = set.boards();
+
+        assert!(set.verify(&commitment, &boards));
+        assert!(set.verify(&commitment, &boards[2..5]));
+        assert!(!DealSet::new(b"guess", 8).verify(&commitment, &boards));
+
+        boards[0].set_number(2);
+        assert!(!set.verify(&commitment, &boards));
+    }
+
+    #[test]
+    fn from_hex() {
+        let set = DealSet::random(4);
+
+        let revealed = DealSet::from_hex(&set.seed_hex(), 4).unwrap();
+
+        assert_eq!(SEED_SIZE * 2, set.seed_hex().len());
+        assert_eq!(set, revealed);
+        assert_eq!(None, DealSet::from_hex("abc", 4));
+        assert_eq!(None, DealSet::from_hex("zz", 4));
+    }
+}
@@ -0,0 +1,215 @@
+use colored::*;
+
+use crate::cards::decks::bridge::dds::TrickTable;
+use crate::cards::decks::bridge::{
+    holding_text, BridgeBoard, BridgeDirection, BRIDGE_SUITS, SUIT_CHARS,
+};
+use crate::cards::pile::Pile;
+use crate::cards::suit::Suit;
+
+const COLUMN: usize = 18;
+
+/// What to include in a hand diagram, and how to draw it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DiagramOptions {
+    /// Show the board number, dealer and vulnerability above the diagram.
+    pub header: bool,
+    /// Suit symbols, with hearts and diamonds in red. Otherwise suits are shown with plain
+    /// ASCII letters, which is better for logs.
+    pub color: bool,
+    /// A double dummy trick table to show below the diagram.
+    pub trick_table: Option<TrickTable>,
+}
+
+/// Hand diagrams in the usual compass layout, with North at the top, West and East at the
+/// sides and South at the bottom, and one line for each suit.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeBoard, DiagramOptions};
+///
+/// let board = BridgeBoard::from_pbn_deal(
+///     "S:Q42.Q52.AQT943.Q 97.AT93.652.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982",
+/// );
+/// let diagram = board.diagram(&DiagramOptions::default());
+///
+/// assert!(diagram.contains("S: AJT85"));
+/// ```
+impl BridgeBoard {
+    pub fn diagram(&self, options: &DiagramOptions) -> String {
+        let mut lines: Vec<String> = Vec::new();
+
+        if options.header {
+            lines.push(format!("Board {}", self.number));
+            lines.push(format!("Dealer: {}", self.dealer));
+            lines.push(format!("Vul: {}", self.vulnerability));
+            lines.push(String::new());
+        }
+
+        let north = self.diagram_hand(&BridgeDirection::N, options.color);
+        let east = self.diagram_hand(&BridgeDirection::E, options.color);
+        let south = self.diagram_hand(&BridgeDirection::S, options.color);
+        let west = self.diagram_hand(&BridgeDirection::W, options.color);
+        let indent = " ".repeat(COLUMN);
+
+        for line in north.iter() {
+            lines.push(format!("{}{}", indent, line.0));
+        }
+        for (w, e) in west.iter().zip(east.iter()) {
+            let padding = " ".repeat(COLUMN * 2 - w.1);
+            lines.push(format!("{}{}{}", w.0, padding, e.0));
+        }
+        for line in south.iter() {
+            lines.push(format!("{}{}", indent, line.0));
+        }
+
+        if let Some(table) = &options.trick_table {
+            lines.push(String::new());
+            lines.push(table.to_string().trim_end().to_string());
+        }
+
+        let mut diagram = lines
+            .iter()
+            .map(|l| l.trim_end())
+            .collect::<Vec<&str>>()
+            .join("\n");
+        diagram.push('\n');
+        diagram
+    }
+
+    /// A single line with each hand in plain ASCII, such as `N: S:AJT85 H:J76 D:KJ C:A65`,
+    /// for logs.
+    pub fn to_plain_text(&self) -> String {
+        BridgeDirection::seats()
+            .iter()
+            .map(|direction| {
+                let suits: Vec<String> = match self.get_hand(direction) {
+                    Some(hand) => SUIT_CHARS
+                        .chars()
+                        .enumerate()
+                        .map(|(i, letter)| format!("{}:{}", letter, holding_text(hand, i)))
+                        .collect(),
+                    None => Vec::new(),
+                };
+                format!("{}: {}", direction, suits.join(" "))
+            })
+            .collect::<Vec<String>>()
+            .join(" / ")
+    }
+
+    /// The seat name followed by one line per suit. Each line is returned along with its
+    /// width in characters, since colored lines also contain escape codes.
+    fn diagram_hand(&self, direction: &BridgeDirection, color: bool) -> Vec<(String, usize)> {
+        let name = match direction {
+            BridgeDirection::N => "North",
+            BridgeDirection::E => "East",
+            BridgeDirection::S => "South",
+            BridgeDirection::W => "West",
+            BridgeDirection::UNKNOWN => "",
+        };
+        let mut lines = vec![(name.to_string(), name.len())];
+
+        let empty = Pile::default();
+        let hand = self.get_hand(direction).unwrap_or(&empty);
+        for (i, letter) in SUIT_CHARS.chars().enumerate() {
+            let holding = holding_text(hand, i);
+            let holding = if holding.is_empty() { "-" } else { &holding };
+            let suit = if color {
+                let symbol = Suit::new(BRIDGE_SUITS[i]).symbol();
+                match i {
+                    1 | 2 => symbol.red().to_string(),
+                    _ => symbol,
+                }
+            } else {
+                format!("{}:", letter)
+            };
+            let width = if color { 2 } else { 3 } + holding.chars().count();
+            lines.push((format!("{} {}", suit, holding), width));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_diagram_tests {
+    use super::*;
+    use crate::cards::decks::bridge::contract::Strain;
+    use crate::cards::decks::bridge::Vulnerability;
+
+    const PBN_TEST_STRING: &str =
+        "S:Q42.Q52.AQT943.Q 97.AT93.652.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982";
+
+    #[test]
+    fn diagram() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+
+        let diagram = board.diagram(&DiagramOptions::default());
+
+        let expected = "                  North
+                  S: AJT85
+                  H: J76
+                  D: KJ
+                  C: A65
+West                                East
+S: 97                               S: K63
+H: AT93                             H: K84
+D: 652                              D: 87
+C: T743                             C: KJ982
+                  South
+                  S: Q42
+                  H: Q52
+                  D: AQT943
+                  C: Q
+";
+        assert_eq!(expected, diagram);
+    }
+
+    #[test]
+    fn diagram__header_and_trick_table() {
+        let mut board = BridgeBoard::from_pbn_deal(
+            "N:AKQJT98765432... .AKQJT98765432.. ..AKQJT98765432. ...AKQJT98765432",
+        );
+        board.set_number(6);
+        let mut table = TrickTable::default();
+        table.set(&Strain::S, &BridgeDirection::N, 13);
+        let options = DiagramOptions {
+            header: true,
+            color: false,
+            trick_table: Some(table),
+        };
+
+        let diagram = board.diagram(&options);
+
+        assert_eq!(Vulnerability::EW, board.vulnerability);
+        assert!(diagram.starts_with("Board 6\nDealer: E\nVul: EW\n\n"));
+        assert!(diagram.contains("H: -"));
+        assert!(diagram.contains("   S  H  D  C NT\nN 13  0  0  0  0"));
+    }
+
+    #[test]
+    fn diagram__color() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+        let options = DiagramOptions {
+            color: true,
+            ..Default::default()
+        };
+
+        let diagram = board.diagram(&options);
+
+        assert!(diagram.contains("♠ AJT85"));
+        assert!(diagram.contains(&format!("{} J76", "♥".red())));
+        assert!(!diagram.contains("S:"));
+    }
+
+    #[test]
+    fn to_plain_text() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+
+        assert_eq!(
+            "N: S:AJT85 H:J76 D:KJ C:A65 / E: S:K63 H:K84 D:87 C:KJ982 / \
+             S: S:Q42 H:Q52 D:AQT943 C:Q / W: S:97 H:AT93 D:652 C:T743",
+            board.to_plain_text()
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use crate::cards::decks::bridge::evaluation::HandEvaluation;
+use crate::cards::decks::bridge::generator::ShapePattern;
+use crate::cards::decks::bridge::{seat_index, suit_index, BridgeBoard, BridgeDirection};
+
+/// Exact odds for how the cards that haven't been seen lie, worked out by counting the ways
+/// they can fill the places left in each hand. A seat's hand can be fully known, unknown, or
+/// hold just the cards seen so far, such as those a defender has played.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeBoard, BridgeDirection, Distribution};
+///
+/// // Five trumps missing split 3-2 about two times in three.
+/// assert_eq!("67.83%", format!("{:.2}%", Distribution::suit_split(5, 3, 2) * 100.0));
+///
+/// // Declarer and dummy know their own hands.
+/// let board = BridgeBoard::from_pbn_deal("N:AKJ.T98.A2.KJ943 - T9876.K54.Q7.A65 -");
+/// let odds = Distribution::from_board(&board).unwrap();
+///
+/// assert_eq!(5, odds.unseen(0));
+/// assert_eq!(
+///     Distribution::suit_split(5, 3, 2),
+///     odds.split_probability(0, &BridgeDirection::E, &BridgeDirection::W, 3, 2)
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Distribution {
+    known: [[usize; 4]; 4],
+    vacant: [usize; 4],
+    unseen: [usize; 4],
+}
+
+impl Distribution {
+    /// The odds before any cards are seen.
+    pub fn new() -> Distribution {
+        Distribution {
+            known: [[0; 4]; 4],
+            vacant: [13; 4],
+            unseen: [13; 4],
+        }
+    }
+
+    /// The odds with the cards in the board's hands known, or None if the hands don't leave
+    /// room for exactly the cards that haven't been seen.
+    pub fn from_board(board: &BridgeBoard) -> Option<Distribution> {
+        let mut distribution = Distribution::new();
+        for (i, seat) in BridgeDirection::seats().iter().enumerate() {
+            let hand = board.get_hand(seat)?;
+            distribution.known[i] = hand.suit_lengths();
+            distribution.vacant[i] = 13usize.checked_sub(hand.len())?;
+        }
+
+        distribution.unseen = [0; 4];
+        let unseen = board.unseen();
+        for card in unseen.cards() {
+            distribution.unseen[suit_index(card)?] += 1;
+        }
+        if distribution.vacant.iter().sum::<usize>() != unseen.len() {
+            return None;
+        }
+        Some(distribution)
+    }
+
+    /// The chance that the missing cards in a suit split a-b between the two defenders,
+    /// either way round, when declarer can see their own hand and dummy.
+    pub fn suit_split(missing: usize, a: usize, b: usize) -> f64 {
+        if missing > 13 {
+            return 0.0;
+        }
+        let distribution = Distribution {
+            known: [[0; 4]; 4],
+            vacant: [0, 13, 0, 13],
+            unseen: [missing, 26 - missing, 0, 0],
+        };
+        distribution.split_probability(0, &BridgeDirection::E, &BridgeDirection::W, a, b)
+    }
+
+    /// The number of places left for unseen cards in the seat's hand.
+    pub fn vacant(&self, seat: &BridgeDirection) -> usize {
+        seat_index(seat).map_or(0, |i| self.vacant[i])
+    }
+
+    /// The number of unseen cards in the suit, by its position in `BRIDGE_SUITS`.
+    pub fn unseen(&self, suit: usize) -> usize {
+        self.unseen.get(suit).copied().unwrap_or(0)
+    }
+
+    /// The chance that each seat, in N E S W order, holds exactly the given number of the
+    /// unseen cards in the suit.
+    pub fn holding_probability(&self, suit: usize, counts: &[usize; 4]) -> f64 {
+        if suit >= 4
+            || counts.iter().sum::<usize>() != self.unseen[suit]
+            || counts.iter().zip(self.vacant.iter()).any(|(c, v)| c > v)
+        {
+            return 0.0;
+        }
+        let total: usize = self.vacant.iter().sum();
+        let others: Vec<usize> = (0..4).map(|i| self.vacant[i] - counts[i]).collect();
+        let ways = multinomial(self.unseen[suit], counts)
+            * multinomial(total - self.unseen[suit], &others);
+        ways as f64 / multinomial(total, &self.vacant) as f64
+    }
+
+    /// The chance that the unseen cards in the suit split a-b between the two seats, either
+    /// way round, leaving none of them with any other seat.
+    pub fn split_probability(
+        &self,
+        suit: usize,
+        first: &BridgeDirection,
+        second: &BridgeDirection,
+        a: usize,
+        b: usize,
+    ) -> f64 {
+        let (first, second) = match (seat_index(first), seat_index(second)) {
+            (Some(first), Some(second)) if first != second => (first, second),
+            _ => return 0.0,
+        };
+        let holding = |x: usize, y: usize| {
+            let mut counts = [0; 4];
+            counts[first] = x;
+            counts[second] = y;
+            self.holding_probability(suit, &counts)
+        };
+        if a == b {
+            holding(a, b)
+        } else {
+            holding(a, b) + holding(b, a)
+        }
+    }
+
+    /// Every split of the unseen cards in the suit between the two seats, longer side first,
+    /// with its chance.
+    pub fn splits(
+        &self,
+        suit: usize,
+        first: &BridgeDirection,
+        second: &BridgeDirection,
+    ) -> Vec<((usize, usize), f64)> {
+        let missing = self.unseen(suit);
+        ((missing + 1) / 2..=missing)
+            .rev()
+            .map(|a| {
+                let b = missing - a;
+                ((a, b), self.split_probability(suit, first, second, a, b))
+            })
+            .collect()
+    }
+
+    /// The chance that the seat's hand, with the cards already known, ends up with the
+    /// pattern.
+    pub fn pattern_probability(&self, seat: &BridgeDirection, pattern: &ShapePattern) -> f64 {
+        let (total, fills) = match self.fills(seat) {
+            Some(fills) => fills,
+            None => return 0.0,
+        };
+        let ways: u128 = fills
+            .iter()
+            .filter(|(lengths, _)| pattern.matches(lengths))
+            .map(|(_, ways)| ways)
+            .sum();
+        ways as f64 / total as f64
+    }
+
+    /// The chance of each hand pattern, longest suit first, for the seat, most likely first.
+    pub fn patterns(&self, seat: &BridgeDirection) -> Vec<([usize; 4], f64)> {
+        let (total, fills) = match self.fills(seat) {
+            Some(fills) => fills,
+            None => return Vec::new(),
+        };
+        let mut patterns: Vec<([usize; 4], u128)> = Vec::new();
+        for (mut lengths, ways) in fills {
+            lengths.sort_unstable_by(|a, b| b.cmp(a));
+            match patterns.iter_mut().find(|(shape, _)| *shape == lengths) {
+                Some((_, count)) => *count += ways,
+                None => patterns.push((lengths, ways)),
+            }
+        }
+        patterns.sort_by(|a, b| b.1.cmp(&a.1).then(b.0.cmp(&a.0)));
+        patterns
+            .into_iter()
+            .map(|(shape, ways)| (shape, ways as f64 / total as f64))
+            .collect()
+    }
+
+    /// Every way of filling the seat's vacant places with unseen cards, as the hand's suit
+    /// lengths and the number of ways of choosing those cards, along with the total number
+    /// of ways.
+    fn fills(&self, seat: &BridgeDirection) -> Option<(u128, Vec<Fill>)> {
+        let i = seat_index(seat)?;
+        let vacant = self.vacant[i];
+        let unseen = self.unseen;
+        let mut fills = Vec::new();
+        for s in 0..=vacant.min(unseen[0]) {
+            for h in 0..=(vacant - s).min(unseen[1]) {
+                for d in 0..=(vacant - s - h).min(unseen[2]) {
+                    let c = vacant - s - h - d;
+                    if c > unseen[3] {
+                        continue;
+                    }
+                    let extra = [s, h, d, c];
+                    let mut lengths = self.known[i];
+                    let mut ways = 1;
+                    for suit in 0..4 {
+                        lengths[suit] += extra[suit];
+                        ways *= binomial(unseen[suit], extra[suit]);
+                    }
+                    fills.push((lengths, ways));
+                }
+            }
+        }
+        Some((binomial(unseen.iter().sum(), vacant), fills))
+    }
+}
+
+/// A hand's suit lengths and the number of ways of dealing it.
+type Fill = ([usize; 4], u128);
+
+impl Default for Distribution {
+    fn default() -> Self {
+        Distribution::new()
+    }
+}
+
+impl ShapePattern {
+    /// The chance that a hand dealt at random has the pattern.
+    ///
+    /// # Usage:
+    /// ```
+    /// use cardpack::ShapePattern;
+    ///
+    /// let pattern = ShapePattern::to("4-4-3-2").unwrap();
+    ///
+    /// assert_eq!("21.55%", format!("{:.2}%", pattern.probability() * 100.0));
+    /// ```
+    pub fn probability(&self) -> f64 {
+        Distribution::new().pattern_probability(&BridgeDirection::N, self)
+    }
+}
+
+fn binomial(n: usize, k: usize) -> u128 {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    (0..k).fold(1, |r, i| r * (n - i) as u128 / (i + 1) as u128)
+}
+
+/// The number of ways of dividing n things into groups of the given sizes.
+fn multinomial(n: usize, parts: &[usize]) -> u128 {
+    let mut rest = n;
+    parts
+        .iter()
+        .map(|part| {
+            let ways = binomial(rest, *part);
+            rest = rest.saturating_sub(*part);
+            ways
+        })
+        .product()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_distribution_tests {
+    use super::*;
+    use crate::cards::card::Card;
+    use crate::cards::decks::bridge::{BRIDGE_RANKS, BRIDGE_SUITS};
+    use crate::cards::pile::Pile;
+
+    fn percent(p: f64) -> String {
+        format!("{:.2}", p * 100.0)
+    }
+
+    #[test]
+    fn shape_pattern__probability() {
+        assert_eq!(
+            "21.55",
+            percent(ShapePattern::to("4-4-3-2").unwrap().probability())
+        );
+        assert_eq!(
+            "15.52",
+            percent(ShapePattern::to("any 5332").unwrap().probability())
+        );
+        assert_eq!(
+            "10.54",
+            percent(ShapePattern::to("4-3-3-3").unwrap().probability())
+        );
+        assert_eq!(
+            "1.80",
+            percent(ShapePattern::to("4=4=3=2").unwrap().probability())
+        );
+    }
+
+    #[test]
+    fn suit_split() {
+        assert_eq!("67.83", percent(Distribution::suit_split(5, 3, 2)));
+        assert_eq!("28.26", percent(Distribution::suit_split(5, 4, 1)));
+        assert_eq!("40.70", percent(Distribution::suit_split(4, 2, 2)));
+        assert_eq!("52.00", percent(Distribution::suit_split(2, 1, 1)));
+        assert_eq!("50.00", percent(Distribution::suit_split(1, 1, 0) / 2.0));
+        assert_eq!(0.0, Distribution::suit_split(5, 3, 3));
+    }
+
+    #[test]
+    fn splits() {
+        let splits = Distribution::new().splits(0, &BridgeDirection::E, &BridgeDirection::W);
+        let declarer = Distribution {
+            known: [[0; 4]; 4],
+            vacant: [0, 13, 0, 13],
+            unseen: [4, 22, 0, 0],
+        };
+        let total: f64 = declarer
+            .splits(0, &BridgeDirection::E, &BridgeDirection::W)
+            .iter()
+            .map(|(_, p)| p)
+            .sum();
+
+        assert_eq!((13, 0), splits[0].0);
+        assert_eq!(7, splits.len());
+        assert!((total - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn patterns() {
+        let patterns = Distribution::new().patterns(&BridgeDirection::S);
+        let total: f64 = patterns.iter().map(|(_, p)| p).sum();
+
+        assert_eq!(39, patterns.len());
+        assert_eq!([4, 4, 3, 2], patterns[0].0);
+        assert_eq!([5, 3, 3, 2], patterns[1].0);
+        assert_eq!([13, 0, 0, 0], patterns[38].0);
+        assert!((total - 1.0).abs() < 1e-12);
+        assert_eq!(4.0 / 635_013_559_600.0, patterns[38].1);
+    }
+
+    #[test]
+    fn from_board() {
+        let board = BridgeBoard::from_pbn_deal("N:AKJ.T98.A2.KJ943 - T9876.K54.Q7.A65 -");
+
+        let odds = Distribution::from_board(&board).unwrap();
+
+        assert_eq!(0, odds.vacant(&BridgeDirection::N));
+        assert_eq!(13, odds.vacant(&BridgeDirection::E));
+        let unseen: Vec<usize> = (0..4).map(|suit| odds.unseen(suit)).collect();
+        assert_eq!(vec![5, 7, 9, 5], unseen);
+        assert_eq!(
+            "67.83",
+            percent(odds.split_probability(0, &BridgeDirection::E, &BridgeDirection::W, 3, 2))
+        );
+        assert_eq!(
+            0.0,
+            odds.split_probability(0, &BridgeDirection::N, &BridgeDirection::S, 3, 2)
+        );
+        assert_eq!(
+            1.0,
+            odds.pattern_probability(&BridgeDirection::N, &ShapePattern::to("5-3-3-2").unwrap())
+        );
+    }
+
+    #[test]
+    fn from_board__partial_hand() {
+        // East has played the queen of clubs, leaving fewer places for the other clubs.
+        let mut board = BridgeBoard::from_pbn_deal("N:AKJ.T98.A2.KJ943 - T9876.K54.Q7.A65 -");
+        board.east = Pile::new_from_vector(vec![Card::new(BRIDGE_RANKS[2], BRIDGE_SUITS[3])]);
+
+        let odds = Distribution::from_board(&board).unwrap();
+        let total: f64 = odds
+            .splits(3, &BridgeDirection::E, &BridgeDirection::W)
+            .iter()
+            .map(|(_, p)| p)
+            .sum();
+
+        assert_eq!(12, odds.vacant(&BridgeDirection::E));
+        assert_eq!(4, odds.unseen(3));
+        assert!((total - 1.0).abs() < 1e-12);
+        assert!(
+            odds.holding_probability(3, &[0, 0, 0, 4]) > odds.holding_probability(3, &[0, 4, 0, 0])
+        );
+        assert_eq!(
+            0.0,
+            odds.pattern_probability(&BridgeDirection::E, &ShapePattern::to("x=x=x=0").unwrap())
+        );
+    }
+
+    #[test]
+    fn from_board__inconsistent() {
+        let mut board = BridgeBoard::from_pbn_deal("N:AKJ.T98.A2.KJ943 - T9876.K54.Q7.A65 -");
+        board.south = board.north.clone();
+
+        assert_eq!(None, Distribution::from_board(&board));
+    }
+}
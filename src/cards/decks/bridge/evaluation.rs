@@ -0,0 +1,349 @@
+use crate::cards::decks::bridge::{rank_index, suit_index};
+use crate::cards::pile::Pile;
+
+// Positions in BRIDGE_RANKS for the cards that matter to the evaluators.
+const A: usize = 0;
+const K: usize = 1;
+const Q: usize = 2;
+const J: usize = 3;
+const T: usize = 4;
+const NINE: usize = 5;
+const EIGHT: usize = 6;
+
+/// Whether suit lengths, in any order, make a balanced hand. `HandEvaluation::is_balanced`
+/// and the deal database both use this.
+pub(crate) fn is_balanced_lengths(lengths: &[usize; 4]) -> bool {
+    let mut shape = *lengths;
+    shape.sort_unstable();
+    matches!(shape, [3, 3, 3, 4] | [2, 3, 4, 4] | [2, 3, 3, 5])
+}
+
+/// Hand evaluation metrics for a Bridge hand. While any Pile can be evaluated, the results
+/// are only meaningful for a 13 card hand from a French Deck.
+///
+/// Suit based results are in `BRIDGE_SUITS` order: spades, hearts, diamonds, clubs.
+///
+/// # Usage:
+/// ```
+/// use cardpack::HandEvaluation;
+///
+/// let board = cardpack::BridgeBoard::from_pbn_deal(
+///     "S:Q42.Q52.AQT943.Q 97.AT93.652.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982",
+/// );
+///
+/// assert_eq!(14, board.north.hcp());
+/// assert!(board.north.is_balanced());
+/// ```
+pub trait HandEvaluation {
+    /// Returns the rank indexes held in each suit, highest card first.
+    fn holdings(&self) -> [Vec<usize>; 4];
+
+    /// Milton Work point count: ace 4, king 3, queen 2, jack 1.
+    fn hcp(&self) -> usize {
+        self.holdings()
+            .iter()
+            .flatten()
+            .map(|r| if *r <= J { 4 - r } else { 0 })
+            .sum()
+    }
+
+    fn suit_lengths(&self) -> [usize; 4] {
+        let holdings = self.holdings();
+        let mut lengths = [0; 4];
+        for (i, holding) in holdings.iter().enumerate() {
+            lengths[i] = holding.len();
+        }
+        lengths
+    }
+
+    /// The suit lengths sorted longest first, so that a hand with four spades, three hearts,
+    /// four diamonds and two clubs has a shape of `[4, 4, 3, 2]`.
+    fn shape(&self) -> [usize; 4] {
+        let mut shape = self.suit_lengths();
+        shape.sort_unstable();
+        shape.reverse();
+        shape
+    }
+
+    /// 4-3-3-3, 4-4-3-2 and 5-3-3-2 hands.
+    fn is_balanced(&self) -> bool {
+        is_balanced_lengths(&self.suit_lengths())
+    }
+
+    /// Balanced hands plus 5-4-2-2 and 6-3-2-2.
+    fn is_semi_balanced(&self) -> bool {
+        self.is_balanced() || matches!(self.shape(), [5, 4, 2, 2] | [6, 3, 2, 2])
+    }
+
+    /// Shortness points: 3 for a void, 2 for a singleton and 1 for a doubleton.
+    fn distribution_points(&self) -> usize {
+        self.suit_lengths()
+            .iter()
+            .map(|l| match l {
+                0 => 3,
+                1 => 2,
+                2 => 1,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Losing trick count. Only the top three cards of a suit are considered, and each of
+    /// them that isn't an ace, king or queen (within the length of the suit) is a loser.
+    fn losing_trick_count(&self) -> usize {
+        self.holdings()
+            .iter()
+            .map(|holding| {
+                let considered = holding.len().min(3);
+                let winners = holding.iter().filter(|r| **r < considered).count();
+                considered - winners
+            })
+            .sum()
+    }
+
+    /// Defensive quick tricks: AK 2, AQ 1½, A 1, KQ 1 and Kx ½.
+    fn quick_tricks(&self) -> f32 {
+        self.holdings()
+            .iter()
+            .map(|holding| {
+                let has = |r: usize| holding.contains(&r);
+                if has(A) && has(K) {
+                    2.0
+                } else if has(A) && has(Q) {
+                    1.5
+                } else if has(A) || (has(K) && has(Q)) {
+                    1.0
+                } else if has(K) && holding.len() > 1 {
+                    0.5
+                } else {
+                    0.0
+                }
+            })
+            .sum()
+    }
+
+    /// Aces count 2 controls and kings 1.
+    fn controls(&self) -> usize {
+        self.holdings()
+            .iter()
+            .flatten()
+            .map(|r| match *r {
+                A => 2,
+                K => 1,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Zar points: high card points plus controls, plus the length of the two longest suits,
+    /// plus the difference between the longest and shortest suits.
+    fn zar_points(&self) -> usize {
+        let shape = self.shape();
+        self.hcp() + self.controls() + shape[0] + shape[1] + shape[0] - shape[3]
+    }
+
+    /// Kaplan-Rubens "Four C's" (Concentration, Controls, Cards and Colour) evaluation.
+    ///
+    /// Each suit scores its honours (A 3, K 2, Q 1, with reduced values for short or
+    /// unsupported honours and part credit for supported jacks and tens), a suit quality of
+    /// length times honour strength over ten, and shortness points. One point is deducted
+    /// from every hand and a further half point from 4-3-3-3 hands.
+    fn cccc(&self) -> f32 {
+        let mut hundredths: isize = 0;
+
+        for holding in self.holdings().iter() {
+            let length = holding.len() as isize;
+            let has = |r: usize| holding.contains(&r);
+            let higher = |r: usize| holding.iter().filter(|h| **h < r).count();
+
+            // Honours
+            if has(A) {
+                hundredths += 300;
+            }
+            if has(K) {
+                hundredths += if length == 1 { 50 } else { 200 };
+            }
+            if has(Q) {
+                hundredths += match (length, has(A) || has(K)) {
+                    (1, _) => 0,
+                    (2, true) => 50,
+                    (2, false) => 25,
+                    (_, true) => 100,
+                    (_, false) => 75,
+                };
+            }
+            if has(J) {
+                hundredths += match higher(J) {
+                    2 | 3 => 50,
+                    1 => 25,
+                    _ => 0,
+                };
+            }
+            if has(T) && (higher(T) >= 2 || has(NINE)) {
+                hundredths += 25;
+            }
+
+            // Suit quality, counted in half points of honour strength.
+            let mut strength: isize = 0;
+            let honours = [(A, 8), (K, 6), (Q, 4), (J, 2)];
+            for (rank, value) in honours.iter() {
+                let assumed = (*rank == Q && length >= 7) || (*rank == J && length >= 8);
+                if has(*rank) || assumed {
+                    strength += value;
+                }
+            }
+            if has(T) {
+                strength += if has(J) || higher(T) >= 2 { 2 } else { 1 };
+            }
+            if has(NINE) && (has(EIGHT) || has(T) || higher(NINE) >= 2) {
+                strength += 1;
+            }
+            hundredths += length * strength * 5;
+
+            // Shortness
+            hundredths += match length {
+                0 => 300,
+                1 => 200,
+                2 => 100,
+                _ => 0,
+            };
+        }
+
+        hundredths -= 100;
+        if self.shape() == [4, 3, 3, 3] {
+            hundredths -= 50;
+        }
+
+        hundredths as f32 / 100.0
+    }
+}
+
+impl HandEvaluation for Pile {
+    fn holdings(&self) -> [Vec<usize>; 4] {
+        let mut holdings: [Vec<usize>; 4] = Default::default();
+        for card in self.values() {
+            if let (Some(suit), Some(rank)) = (suit_index(card), rank_index(card)) {
+                holdings[suit].push(rank);
+            }
+        }
+        for holding in holdings.iter_mut() {
+            holding.sort_unstable();
+        }
+        holdings
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_evaluation_tests {
+    use super::*;
+    use crate::cards::decks::bridge::BridgeBoard;
+
+    const PBN_TEST_STRING: &str =
+        "S:Q42.Q52.AQT943.Q 97.AT93.652.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982";
+
+    fn board() -> BridgeBoard {
+        BridgeBoard::from_pbn_deal(PBN_TEST_STRING)
+    }
+
+    #[test]
+    fn hcp() {
+        let board = board();
+
+        assert_eq!(12, board.south.hcp());
+        assert_eq!(4, board.west.hcp());
+        assert_eq!(14, board.north.hcp());
+        assert_eq!(10, board.east.hcp());
+    }
+
+    #[test]
+    fn suit_lengths_shape() {
+        let board = board();
+
+        assert_eq!([3, 3, 6, 1], board.south.suit_lengths());
+        assert_eq!([6, 3, 3, 1], board.south.shape());
+        assert_eq!([5, 3, 3, 2], board.north.shape());
+    }
+
+    #[test]
+    fn is_balanced() {
+        let board = board();
+
+        assert!(board.north.is_balanced());
+        assert!(!board.south.is_balanced());
+        assert!(!board.south.is_semi_balanced());
+    }
+
+    #[test]
+    fn is_semi_balanced() {
+        let hand = Pile::french_deck()
+            .pile_by_index(&[
+                "AS", "KS", "QS", "3S", "2S", "KH", "JH", "7H", "4H", "3D", "2D", "3C", "2C",
+            ])
+            .unwrap();
+
+        assert!(!hand.is_balanced());
+        assert!(hand.is_semi_balanced());
+    }
+
+    #[test]
+    fn distribution_points() {
+        let board = board();
+
+        assert_eq!(2, board.south.distribution_points());
+        assert_eq!(1, board.north.distribution_points());
+    }
+
+    #[test]
+    fn losing_trick_count() {
+        let board = board();
+
+        assert_eq!(6, board.south.losing_trick_count());
+        assert_eq!(8, board.north.losing_trick_count());
+        assert_eq!(10, board.west.losing_trick_count());
+    }
+
+    #[test]
+    fn quick_tricks() {
+        let board = board();
+
+        assert_eq!(1.5, board.south.quick_tricks());
+        assert_eq!(2.5, board.north.quick_tricks());
+        assert_eq!(1.5, board.east.quick_tricks());
+    }
+
+    #[test]
+    fn controls() {
+        let board = board();
+
+        assert_eq!(2, board.south.controls());
+        assert_eq!(5, board.north.controls());
+    }
+
+    #[test]
+    fn zar_points() {
+        let board = board();
+
+        assert_eq!(28, board.south.zar_points());
+        assert_eq!(30, board.north.zar_points());
+    }
+
+    #[test]
+    fn cccc() {
+        let board = board();
+
+        assert!(board.north.cccc() > board.east.cccc());
+        assert!(board.east.cccc() > board.west.cccc());
+    }
+
+    #[test]
+    fn cccc__yarborough() {
+        let yarborough = Pile::french_deck()
+            .pile_by_index(&[
+                "5S", "4S", "3S", "2S", "4H", "3H", "2H", "4D", "3D", "2D", "4C", "3C", "2C",
+            ])
+            .unwrap();
+
+        assert_eq!(-1.5, yarborough.cccc());
+    }
+}
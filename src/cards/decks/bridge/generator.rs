@@ -0,0 +1,1133 @@
+use std::fmt;
+
+use crate::cards::card::Card;
+use crate::cards::decks::bridge::evaluation::HandEvaluation;
+use crate::cards::decks::bridge::{seat_index, BridgeBoard, BridgeDirection, SUIT_CHARS};
+use crate::cards::pile::Pile;
+
+/// A pattern of suit lengths. Four lengths written together, as in `5332`, or joined with
+/// `-` can be in any order, so `5332`, `any 5332` and `5-3-3-2` all match every 5-3-3-2
+/// hand. Joined with `=` the lengths are in spades, hearts, diamonds, clubs order, so
+/// `5=x=x=x` only matches hands with exactly five spades, where the "dealer" program would
+/// write `5xxx`. A length followed by `+` is a minimum, an `x` is any length, and lengths
+/// missing after the last `=` or `-` match anything, so `4+=4` is four or more spades with
+/// exactly four hearts.
+///
+/// # Usage:
+/// ```
+/// use cardpack::ShapePattern;
+///
+/// let pattern = ShapePattern::to("4-4-3-2").unwrap();
+///
+/// assert!(pattern.matches(&[2, 4, 3, 4]));
+/// assert!(ShapePattern::to("4432").unwrap().matches(&[2, 4, 3, 4]));
+/// assert!(!ShapePattern::to("4=4=3=2").unwrap().matches(&[2, 4, 3, 4]));
+/// assert!(ShapePattern::to("4+=4").unwrap().matches(&[5, 4, 2, 2]));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShapePattern {
+    lengths: [(usize, usize); 4],
+    any: bool,
+}
+
+impl ShapePattern {
+    pub fn to(s: &str) -> Option<ShapePattern> {
+        let s = s.trim();
+        let (any, s) = match s.strip_prefix("any") {
+            Some(rest) => (true, rest.trim()),
+            None => (false, s),
+        };
+        let ordered = s.contains('=');
+        let separated = ordered || s.contains('-');
+        if ordered && s.contains('-') {
+            return None;
+        }
+
+        let parts: Vec<String> = if separated {
+            s.split(|c| c == '=' || c == '-')
+                .map(|p| p.trim().to_string())
+                .collect()
+        } else {
+            let mut parts: Vec<String> = Vec::new();
+            for c in s.chars() {
+                match c {
+                    '+' => parts.last_mut()?.push(c),
+                    _ => parts.push(c.to_string()),
+                }
+            }
+            parts
+        };
+        if parts.is_empty() || parts.len() > 4 || (!separated && parts.len() != 4) {
+            return None;
+        }
+
+        let mut lengths = [(0, 13); 4];
+        for (i, part) in parts.iter().enumerate() {
+            lengths[i] = ShapePattern::component(part)?;
+        }
+        let least: usize = lengths.iter().map(|(min, _)| min).sum();
+        let most: usize = lengths.iter().map(|(_, max)| max).sum();
+        if least > 13 || most < 13 {
+            return None;
+        }
+        Some(ShapePattern {
+            lengths,
+            any: any || !ordered,
+        })
+    }
+
+    fn component(part: &str) -> Option<(usize, usize)> {
+        if part.eq_ignore_ascii_case("x") {
+            return Some((0, 13));
+        }
+        let (digits, at_least) = match part.strip_suffix('+') {
+            Some(digits) => (digits, true),
+            None => (part, false),
+        };
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let length: usize = digits.parse().ok()?;
+        match (length, at_least) {
+            (length, _) if length > 13 => None,
+            (length, true) => Some((length, 13)),
+            (length, false) => Some((length, length)),
+        }
+    }
+
+    /// Tests suit lengths, in `BRIDGE_SUITS` order, against the pattern.
+    pub fn matches(&self, lengths: &[usize; 4]) -> bool {
+        if !self.any {
+            return self
+                .lengths
+                .iter()
+                .zip(lengths.iter())
+                .all(|((min, max), length)| min <= length && length <= max);
+        }
+        self.assign(0, [false; 4], lengths)
+    }
+
+    /// Looks for a different suit for each of the pattern's lengths, from the k-th on.
+    fn assign(&self, k: usize, used: [bool; 4], lengths: &[usize; 4]) -> bool {
+        if k == 4 {
+            return true;
+        }
+        let (min, max) = self.lengths[k];
+        (0..4).any(|i| {
+            let mut used = used;
+            let fits = !used[i] && min <= lengths[i] && lengths[i] <= max;
+            used[i] = true;
+            fits && self.assign(k + 1, used, lengths)
+        })
+    }
+}
+
+impl fmt::Display for ShapePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self
+            .lengths
+            .iter()
+            .map(|(min, max)| match (min, max) {
+                (0, 13) => "x".to_string(),
+                (min, max) if min == max => min.to_string(),
+                (min, _) => format!("{}+", min),
+            })
+            .collect();
+        let separator = if self.any { "-" } else { "=" };
+        write!(f, "{}", parts.join(separator))
+    }
+}
+
+/// A requirement on a single hand.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Predicate {
+    /// High card points between a minimum and maximum, inclusive.
+    HCP(usize, usize),
+    /// The length of the suit at a position in `BRIDGE_SUITS` between a minimum and maximum.
+    LENGTH(usize, usize, usize),
+    SHAPE(ShapePattern),
+    BALANCED,
+    HOLDS(Card),
+}
+
+impl Predicate {
+    pub fn matches(&self, hand: &Pile) -> bool {
+        match self {
+            Predicate::HCP(min, max) => (*min..=*max).contains(&hand.hcp()),
+            Predicate::LENGTH(suit, min, max) => match hand.suit_lengths().get(*suit) {
+                Some(length) => (*min..=*max).contains(length),
+                None => false,
+            },
+            Predicate::SHAPE(pattern) => pattern.matches(&hand.suit_lengths()),
+            Predicate::BALANCED => hand.is_balanced(),
+            Predicate::HOLDS(card) => hand.contains(card),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    And,
+    Or,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+}
+
+/// A condition, where true and false are 1 and 0 as they are in the "dealer" program.
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Number(i64),
+    Hcp(usize, Option<usize>),
+    Length(usize, usize),
+    Controls(usize),
+    Losers(usize),
+    Shape(usize, Vec<(bool, ShapePattern)>),
+    Predicate(usize, Predicate),
+    Not(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Evaluates the condition for the hands, or returns an error if the arithmetic
+    /// overflows.
+    fn eval(&self, hands: &[Pile; 4]) -> Result<i64, String> {
+        let value = match self {
+            Expr::Number(n) => *n,
+            Expr::Hcp(seat, None) => hands[*seat].hcp() as i64,
+            Expr::Hcp(seat, Some(suit)) => hands[*seat].holdings()[*suit]
+                .iter()
+                .map(|r| 4 - (*r).min(4) as i64)
+                .sum(),
+            Expr::Length(seat, suit) => hands[*seat].suit_lengths()[*suit] as i64,
+            Expr::Controls(seat) => hands[*seat].controls() as i64,
+            Expr::Losers(seat) => hands[*seat].losing_trick_count() as i64,
+            Expr::Shape(seat, patterns) => {
+                let lengths = hands[*seat].suit_lengths();
+                let included = patterns.iter().any(|(ex, p)| !ex && p.matches(&lengths));
+                let excluded = patterns.iter().any(|(ex, p)| *ex && p.matches(&lengths));
+                (included && !excluded) as i64
+            }
+            Expr::Predicate(seat, predicate) => predicate.matches(&hands[*seat]) as i64,
+            Expr::Not(expr) => (expr.eval(hands)? == 0) as i64,
+            Expr::Binary(op, left, right) => {
+                let l = left.eval(hands)?;
+                if *op == Op::And && l == 0 {
+                    return Ok(0);
+                }
+                if *op == Op::Or && l != 0 {
+                    return Ok(1);
+                }
+                let r = right.eval(hands)?;
+                let overflow = || format!("arithmetic overflow with {} and {}", l, r);
+                match op {
+                    Op::And | Op::Or => (r != 0) as i64,
+                    Op::Eq => (l == r) as i64,
+                    Op::Ne => (l != r) as i64,
+                    Op::Lt => (l < r) as i64,
+                    Op::Le => (l <= r) as i64,
+                    Op::Gt => (l > r) as i64,
+                    Op::Ge => (l >= r) as i64,
+                    Op::Add => l.checked_add(r).ok_or_else(overflow)?,
+                    Op::Sub => l.checked_sub(r).ok_or_else(overflow)?,
+                    Op::Mul => l.checked_mul(r).ok_or_else(overflow)?,
+                }
+            }
+        };
+        Ok(value)
+    }
+
+    /// Bit mask of the seats the condition looks at.
+    fn seats(&self) -> u8 {
+        match self {
+            Expr::Number(_) => 0,
+            Expr::Hcp(seat, _)
+            | Expr::Length(seat, _)
+            | Expr::Controls(seat)
+            | Expr::Losers(seat)
+            | Expr::Shape(seat, _)
+            | Expr::Predicate(seat, _) => 1 << seat,
+            Expr::Not(expr) => expr.seats(),
+            Expr::Binary(_, left, right) => left.seats() | right.seats(),
+        }
+    }
+
+    /// Splits the condition into the parts joined by `&&` at the top level.
+    fn conjuncts(self) -> Vec<Expr> {
+        match self {
+            Expr::Binary(Op::And, left, right) => {
+                let mut parts = left.conjuncts();
+                parts.append(&mut right.conjuncts());
+                parts
+            }
+            expr => vec![expr],
+        }
+    }
+}
+
+/// An error in a deal generator script, with the line it was found on.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScriptError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Generates random BridgeBoards that meet a set of conditions, for example teaching deals
+/// where North has a 15-17 no trump opening and South has five or more spades.
+///
+/// Conditions can be added for each seat with `require()`, or written in a small script
+/// language modelled on Hans van Staveren's "dealer" program:
+///
+/// ```text
+/// # A strong no trump opposite a weak hand with spades.
+/// predeal north SAK, HQ
+/// condition hcp(north) >= 15 && hcp(north) <= 17 && shape(north, any 4333 + any 4432 + any 5332)
+///     && spades(south) >= 5 && hcp(south) >= 8 && hcp(south) <= 10
+/// generate 100000
+/// produce 4
+/// ```
+///
+/// Conditions support `hcp(seat)`, `hcp(seat, suit)`, `spades(seat)` and the other suits,
+/// `controls(seat)`, `losers(seat)`, `balanced(seat)`, `hascard(seat, AS)` and
+/// `shape(seat, patterns)` where patterns can be added with `+` and removed with `-`, set
+/// apart by spaces. They can be combined with arithmetic, comparisons, `&&`, `||`, `!`,
+/// `and`, `or` and `not`.
+///
+/// Each hand is tested as soon as it has been dealt against the parts of the condition that
+/// only look at the hands dealt so far, so that a deal can be thrown away before the rest of
+/// it is dealt. Cards that are predealt to a seat are always in its hand.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeDirection, DealGenerator, HandEvaluation};
+///
+/// let mut generator = DealGenerator::from_script(
+///     "condition hcp(north) >= 15 && hcp(north) <= 17 && balanced(north)",
+/// )
+/// .unwrap();
+/// let board = generator.generate().unwrap();
+///
+/// assert!(board.north.hcp() >= 15 && board.north.hcp() <= 17);
+/// assert!(generator.acceptance_rate() > 0.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DealGenerator {
+    predeal: [Pile; 4],
+    conditions: Vec<Expr>,
+    produce: usize,
+    max_attempts: usize,
+    attempts: usize,
+    accepted: usize,
+}
+
+impl DealGenerator {
+    pub fn new() -> DealGenerator {
+        DealGenerator::default()
+    }
+
+    /// Builds a generator from a script. Each line is a `predeal`, `condition`, `generate`
+    /// or `produce` directive; a line with none of these continues the line before it, and
+    /// everything after a `#` is a comment.
+    pub fn from_script(script: &str) -> Result<DealGenerator, ScriptError> {
+        let mut generator = DealGenerator::default();
+        let mut conditions: Vec<(usize, String)> = Vec::new();
+
+        for (i, line) in script.lines().enumerate() {
+            let number = i + 1;
+            let error = |message: &str| ScriptError {
+                line: number,
+                message: message.to_string(),
+            };
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (keyword, rest) = match line.find(char::is_whitespace) {
+                Some(i) => (&line[..i], line[i..].trim()),
+                None => (line, ""),
+            };
+
+            match keyword {
+                "predeal" => {
+                    let (seat, holding) = match rest.find(char::is_whitespace) {
+                        Some(i) => (&rest[..i], &rest[i..]),
+                        None => return Err(error("predeal needs a seat and cards")),
+                    };
+                    let seat = parse_seat(seat).ok_or_else(|| error("unknown seat"))?;
+                    let cards = parse_holding(holding).ok_or_else(|| error("invalid cards"))?;
+                    if !generator.predeal(&BridgeDirection::seats()[seat], &cards) {
+                        return Err(error("cards can't be predealt"));
+                    }
+                }
+                "condition" => conditions.push((number, rest.to_string())),
+                "generate" => {
+                    generator.max_attempts = rest.parse().map_err(|_| error("invalid number"))?
+                }
+                "produce" => {
+                    generator.produce = rest.parse().map_err(|_| error("invalid number"))?
+                }
+                _ => match conditions.last_mut() {
+                    Some((_, condition)) => {
+                        condition.push(' ');
+                        condition.push_str(line);
+                    }
+                    None => return Err(error("unknown directive")),
+                },
+            }
+        }
+
+        for (line, condition) in conditions {
+            generator
+                .condition(&condition)
+                .map_err(|message| ScriptError { line, message })?;
+        }
+        Ok(generator)
+    }
+
+    /// Adds a condition written in the script language. All conditions must hold.
+    pub fn condition(&mut self, condition: &str) -> Result<(), String> {
+        let mut parser = Parser::new(condition);
+        let expr = parser.expr()?;
+        parser.skip_whitespace();
+        if parser.pos < parser.chars.len() {
+            return Err(format!("unexpected input at {}", parser.pos + 1));
+        }
+        self.conditions.append(&mut expr.conjuncts());
+        Ok(())
+    }
+
+    /// Requires the hand dealt to the seat to match the predicate.
+    pub fn require(&mut self, direction: &BridgeDirection, predicate: Predicate) {
+        if let Some(seat) = seat_index(direction) {
+            self.conditions.push(Expr::Predicate(seat, predicate));
+        }
+    }
+
+    /// Fixes cards in a seat's hand. Returns false if any of the cards are already
+    /// predealt, or the hand would have more than thirteen cards.
+    pub fn predeal(&mut self, direction: &BridgeDirection, cards: &Pile) -> bool {
+        let seat = match seat_index(direction) {
+            Some(seat) => seat,
+            None => return false,
+        };
+        let taken = cards
+            .values()
+            .any(|c| self.predeal.iter().any(|p| p.contains(c)));
+        if taken || self.predeal[seat].len() + cards.len() > 13 {
+            return false;
+        }
+        self.predeal[seat].append(cards);
+        true
+    }
+
+    /// The number of boards `generate_all()` produces.
+    pub fn produce(&self) -> usize {
+        self.produce
+    }
+
+    pub fn set_produce(&mut self, produce: usize) {
+        self.produce = produce;
+    }
+
+    /// The most deals that are tried for each board before giving up.
+    pub fn set_max_attempts(&mut self, max_attempts: usize) {
+        self.max_attempts = max_attempts;
+    }
+
+    /// Deals until a board meets the conditions, or returns None if none is found within
+    /// the maximum number of attempts or a condition can't be evaluated.
+    pub fn generate(&mut self) -> Option<BridgeBoard> {
+        self.try_generate().ok().flatten()
+    }
+
+    /// Deals until a board meets the conditions, like `generate()`, but returns an error if a
+    /// condition can't be evaluated, such as when its arithmetic overflows.
+    pub fn try_generate(&mut self) -> Result<Option<BridgeBoard>, String> {
+        let order = self.seat_order();
+        let mut deck = Pile::french_deck();
+        for card in self.predeal.iter().flat_map(|p| p.values()) {
+            deck.remove_card(card);
+        }
+
+        for _ in 0..self.max_attempts {
+            self.attempts += 1;
+            if let Some(hands) = self.try_deal(&deck, &order)? {
+                self.accepted += 1;
+                let mut board = BridgeBoard::default();
+                for (seat, hand) in hands.iter().enumerate() {
+                    if let Some(pile) = board.get_hand_mut(&BridgeDirection::seats()[seat]) {
+                        *pile = hand.sort();
+                    }
+                }
+                board.set_number(self.accepted);
+                return Ok(Some(board));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Generates the number of boards set by `produce`, stopping early if a board can't be
+    /// found.
+    pub fn generate_all(&mut self) -> Vec<BridgeBoard> {
+        let mut boards = Vec::new();
+        while boards.len() < self.produce {
+            match self.generate() {
+                Some(board) => boards.push(board),
+                None => break,
+            }
+        }
+        boards
+    }
+
+    /// The number of deals tried so far.
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// The number of deals that have met the conditions.
+    pub fn accepted(&self) -> usize {
+        self.accepted
+    }
+
+    /// The fraction of the deals tried that met the conditions.
+    pub fn acceptance_rate(&self) -> f64 {
+        match self.attempts {
+            0 => 0.0,
+            _ => self.accepted as f64 / self.attempts as f64,
+        }
+    }
+
+    fn try_deal(&self, deck: &Pile, order: &[usize; 4]) -> Result<Option<[Pile; 4]>, String> {
+        let mut deck = deck.shuffle();
+        let mut hands = self.predeal.clone();
+        let mut dealt = 0u8;
+        let mut checked = vec![false; self.conditions.len()];
+
+        for &seat in order.iter() {
+            match deck.draw(13 - hands[seat].len()) {
+                Some(cards) => hands[seat].append(&cards),
+                None => return Ok(None),
+            }
+            dealt |= 1 << seat;
+
+            for (i, condition) in self.conditions.iter().enumerate() {
+                if checked[i] || condition.seats() & !dealt != 0 {
+                    continue;
+                }
+                if condition.eval(&hands)? == 0 {
+                    return Ok(None);
+                }
+                checked[i] = true;
+            }
+        }
+        Ok(Some(hands))
+    }
+
+    /// Deals the seats with the most conditions of their own first, so that deals which
+    /// won't work are thrown away as early as possible.
+    fn seat_order(&self) -> [usize; 4] {
+        let mut order = [0, 1, 2, 3];
+        let own = |seat: usize| {
+            self.conditions
+                .iter()
+                .filter(|c| c.seats() == 1 << seat)
+                .count()
+        };
+        order.sort_by_key(|seat| std::cmp::Reverse(own(*seat)));
+        order
+    }
+}
+
+impl Default for DealGenerator {
+    fn default() -> Self {
+        DealGenerator {
+            predeal: Default::default(),
+            conditions: Vec::new(),
+            produce: 1,
+            max_attempts: 1_000_000,
+            attempts: 0,
+            accepted: 0,
+        }
+    }
+}
+
+fn parse_seat(s: &str) -> Option<usize> {
+    let direction = match s.to_lowercase().as_str() {
+        "north" | "n" => BridgeDirection::N,
+        "east" | "e" => BridgeDirection::E,
+        "south" | "s" => BridgeDirection::S,
+        "west" | "w" => BridgeDirection::W,
+        _ => return None,
+    };
+    seat_index(&direction)
+}
+
+fn parse_suit(s: &str) -> Option<usize> {
+    match s.to_lowercase().as_str() {
+        "spades" | "s" => Some(0),
+        "hearts" | "h" => Some(1),
+        "diamonds" | "d" => Some(2),
+        "clubs" | "c" => Some(3),
+        _ => None,
+    }
+}
+
+/// Parses holdings such as `SAK, HQJ`: a suit letter followed by ranks.
+fn parse_holding(s: &str) -> Option<Pile> {
+    let deck = Pile::french_deck();
+    let mut cards = Pile::default();
+    for part in s.split(|c: char| c == ',' || c.is_whitespace()) {
+        let mut chars = part.chars();
+        let suit = match chars.next() {
+            Some(suit) => suit.to_ascii_uppercase(),
+            None => continue,
+        };
+        if !SUIT_CHARS.contains(suit) {
+            return None;
+        }
+        for rank in chars {
+            let index = format!("{}{}", rank.to_ascii_uppercase(), suit);
+            cards.add(deck.card_by_index(&index)?.clone());
+        }
+    }
+    Some(cards)
+}
+
+/// A condition on a single hand, written in the script language without seats, such as
+/// `hcp >= 15 && balanced && hcp(spades) >= 3`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct HandCondition {
+    expr: Expr,
+}
+
+impl HandCondition {
+    pub(crate) fn parse(condition: &str) -> Result<HandCondition, String> {
+        let mut parser = Parser::new(condition);
+        parser.single_hand = true;
+        let expr = parser.expr()?;
+        parser.skip_whitespace();
+        if parser.pos < parser.chars.len() {
+            return Err(format!("unexpected input at {}", parser.pos + 1));
+        }
+        Ok(HandCondition { expr })
+    }
+
+    /// Whether the hand meets the condition. A condition that can't be evaluated for the
+    /// hand doesn't match it.
+    pub(crate) fn matches(&self, hand: &Pile) -> bool {
+        let hands = [
+            hand.clone(),
+            Pile::default(),
+            Pile::default(),
+            Pile::default(),
+        ];
+        self.expr.eval(&hands).map_or(false, |value| value != 0)
+    }
+}
+
+/// A recursive descent parser for conditions.
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    /// Whether functions leave out the seat and look at a single hand, in which case the
+    /// parentheses are only needed for other arguments.
+    single_hand: bool,
+}
+
+impl Parser {
+    fn new(s: &str) -> Parser {
+        Parser {
+            chars: s.chars().collect(),
+            pos: 0,
+            single_hand: false,
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut left = self.and()?;
+        while self.eat("||") || self.eat_word("or") {
+            left = Expr::Binary(Op::Or, Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut left = self.not()?;
+        while self.eat("&&") || self.eat_word("and") {
+            left = Expr::Binary(Op::And, Box::new(left), Box::new(self.not()?));
+        }
+        Ok(left)
+    }
+
+    fn not(&mut self) -> Result<Expr, String> {
+        if self.eat_word("not") || (!self.peek("!=") && self.eat("!")) {
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let left = self.sum()?;
+        let ops = [
+            ("==", Op::Eq),
+            ("!=", Op::Ne),
+            ("<=", Op::Le),
+            (">=", Op::Ge),
+            ("<", Op::Lt),
+            (">", Op::Gt),
+        ];
+        for (token, op) in ops.iter() {
+            if self.eat(token) {
+                return Ok(Expr::Binary(*op, Box::new(left), Box::new(self.sum()?)));
+            }
+        }
+        Ok(left)
+    }
+
+    fn sum(&mut self) -> Result<Expr, String> {
+        let mut left = self.product()?;
+        loop {
+            let op = if self.eat("+") {
+                Op::Add
+            } else if self.eat("-") {
+                Op::Sub
+            } else {
+                return Ok(left);
+            };
+            left = Expr::Binary(op, Box::new(left), Box::new(self.product()?));
+        }
+    }
+
+    fn product(&mut self) -> Result<Expr, String> {
+        let mut left = self.atom()?;
+        while self.eat("*") {
+            left = Expr::Binary(Op::Mul, Box::new(left), Box::new(self.atom()?));
+        }
+        Ok(left)
+    }
+
+    fn atom(&mut self) -> Result<Expr, String> {
+        self.skip_whitespace();
+        if self.eat("(") {
+            let expr = self.expr()?;
+            self.expect(")")?;
+            return Ok(expr);
+        }
+        if self.eat("-") {
+            let expr = self.atom()?;
+            return Ok(Expr::Binary(
+                Op::Sub,
+                Box::new(Expr::Number(0)),
+                Box::new(expr),
+            ));
+        }
+        if self.current().map_or(false, |c| c.is_ascii_digit()) {
+            let digits = self.take_while(|c| c.is_ascii_digit());
+            return digits
+                .parse()
+                .map(Expr::Number)
+                .map_err(|_| format!("invalid number {}", digits));
+        }
+
+        let name = self.word();
+        if name.is_empty() {
+            return Err(format!("expected a value at {}", self.pos + 1));
+        }
+        let (seat, has_argument) = if self.single_hand {
+            (0, self.eat("("))
+        } else {
+            self.expect("(")?;
+            let seat = self.word();
+            let seat = parse_seat(&seat).ok_or(format!("unknown seat {}", seat))?;
+            (seat, self.eat(","))
+        };
+
+        let expr = match (name.to_lowercase().as_str(), has_argument) {
+            ("hcp", false) => Expr::Hcp(seat, None),
+            ("hcp", true) => {
+                let suit = self.word();
+                Expr::Hcp(
+                    seat,
+                    Some(parse_suit(&suit).ok_or(format!("unknown suit {}", suit))?),
+                )
+            }
+            ("controls", false) => Expr::Controls(seat),
+            ("losers", false) => Expr::Losers(seat),
+            ("balanced", false) => Expr::Predicate(seat, Predicate::BALANCED),
+            ("hascard", true) => {
+                let index = self.word().to_uppercase();
+                let card = Pile::french_deck()
+                    .card_by_index(&index)
+                    .cloned()
+                    .ok_or(format!("unknown card {}", index))?;
+                Expr::Predicate(seat, Predicate::HOLDS(card))
+            }
+            ("shape", true) => Expr::Shape(seat, self.shape_patterns()?),
+            (suit, false) if parse_suit(suit).is_some() && suit.len() > 1 => {
+                Expr::Length(seat, parse_suit(suit).unwrap_or(0))
+            }
+            (name, _) => return Err(format!("unknown function {}", name)),
+        };
+        if !self.single_hand || has_argument {
+            self.expect(")")?;
+        }
+        Ok(expr)
+    }
+
+    /// Reads a list of shape patterns, each added with `+` or removed with `-`. The list is
+    /// split into words, so only a `+` or `-` on its own is taken as an operator and the ones
+    /// in patterns such as `4+=4` and `5-3-3-2` are left alone. An `any` is joined to the
+    /// word after it.
+    fn shape_patterns(&mut self) -> Result<Vec<(bool, ShapePattern)>, String> {
+        let mut patterns = Vec::new();
+        let mut exclude = false;
+        loop {
+            let mut text = self.shape_word();
+            if text == "any" {
+                text = format!("{} {}", text, self.shape_word());
+            }
+            let pattern = ShapePattern::to(&text).ok_or(format!("invalid shape {}", text))?;
+            patterns.push((exclude, pattern));
+            if self.peek(")") || self.current().is_none() {
+                return Ok(patterns);
+            }
+            exclude = match self.shape_word().as_str() {
+                "+" => false,
+                "-" => true,
+                word => return Err(format!("expected + or - before {}", word)),
+            };
+        }
+    }
+
+    /// Reads the next word of a shape argument, which runs up to whitespace or the closing
+    /// parenthesis.
+    fn shape_word(&mut self) -> String {
+        self.skip_whitespace();
+        self.take_while(|c| !c.is_whitespace() && c != ')')
+    }
+
+    fn current(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.current().map_or(false, char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn take_while<F: Fn(char) -> bool>(&mut self, f: F) -> String {
+        let start = self.pos;
+        while self.current().map_or(false, &f) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn word(&mut self) -> String {
+        self.skip_whitespace();
+        self.take_while(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    fn peek(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let token: Vec<char> = token.chars().collect();
+        self.chars[self.pos..].starts_with(&token)
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.peek(token) {
+            self.pos += token.chars().count();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Eats a keyword, as long as it isn't the start of a longer word.
+    fn eat_word(&mut self, word: &str) -> bool {
+        let start = self.pos;
+        if self.word().to_lowercase() == word {
+            return true;
+        }
+        self.pos = start;
+        false
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(format!("expected {} at {}", token, self.pos + 1))
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_generator_tests {
+    use super::*;
+    use crate::cards::rank::*;
+    use crate::cards::suit::*;
+
+    fn hands(deal: &str) -> [Pile; 4] {
+        let board = BridgeBoard::from_pbn_deal(deal);
+        [board.north, board.east, board.south, board.west]
+    }
+
+    fn eval(condition: &str, deal: &str) -> i64 {
+        let mut parser = Parser::new(condition);
+        parser.expr().unwrap().eval(&hands(deal)).unwrap()
+    }
+
+    const DEAL: &str = "N:AJT85.J76.KJ.A65 K63.K84.87.KJ982 Q42.Q52.AQT943.Q 97.AT93.652.T743";
+
+    #[test]
+    fn shape_pattern() {
+        let any = ShapePattern::to("any 4333").unwrap();
+        let five = ShapePattern::to("5xxx").unwrap();
+
+        assert!(any.matches(&[3, 3, 4, 3]));
+        assert!(!any.matches(&[4, 4, 3, 2]));
+        assert!(five.matches(&[5, 1, 4, 3]));
+        assert!(five.matches(&[4, 5, 2, 2]));
+        assert!(!five.matches(&[4, 4, 3, 2]));
+        assert!(ShapePattern::to("4333").unwrap().matches(&[3, 3, 4, 3]));
+        assert!(ShapePattern::to("any 55xx").unwrap().matches(&[5, 2, 1, 5]));
+        assert_eq!(None, ShapePattern::to("4442"));
+        assert_eq!(None, ShapePattern::to("55x"));
+    }
+
+    #[test]
+    fn shape_pattern__to() {
+        assert_eq!("5-3-3-2", ShapePattern::to("5-3-3-2").unwrap().to_string());
+        assert_eq!("5-3-3-2", ShapePattern::to("any 5332").unwrap().to_string());
+        assert_eq!("5-3-3-2", ShapePattern::to("5332").unwrap().to_string());
+        assert_eq!("5-x-x-x", ShapePattern::to("5xxx").unwrap().to_string());
+        assert_eq!("5=3=3=2", ShapePattern::to("5=3=3=2").unwrap().to_string());
+        assert_eq!("4+=4=x=x", ShapePattern::to("4+=4").unwrap().to_string());
+        assert_eq!("6+-4+-x-x", ShapePattern::to("6+-4+").unwrap().to_string());
+        assert_eq!("6+-4+-x-x", ShapePattern::to("6+4+xx").unwrap().to_string());
+        assert_eq!(
+            "13=0=0=0",
+            ShapePattern::to("13=0=0=0").unwrap().to_string()
+        );
+        assert_eq!(None, ShapePattern::to(""));
+        assert_eq!(None, ShapePattern::to("any"));
+        assert_eq!(None, ShapePattern::to("6+4+"));
+        assert_eq!(None, ShapePattern::to("4-4=3-2"));
+        assert_eq!(None, ShapePattern::to("8+-6+"));
+        assert_eq!(None, ShapePattern::to("+4333"));
+        assert_eq!(None, ShapePattern::to("5-3-3-2-0"));
+    }
+
+    #[test]
+    fn shape_pattern__separators() {
+        for (dealer, separated) in [
+            ("5332", "5-3-3-2"),
+            ("any 5332", "5-3-3-2"),
+            ("4xxx", "4-x"),
+            ("x5xx", "x-5"),
+            ("55xx", "5-5"),
+            ("any 6+4+xx", "6+-4+"),
+            ("any 4=x", "4-x"),
+        ]
+        .iter()
+        {
+            let dealer = ShapePattern::to(dealer).unwrap();
+            let separated = ShapePattern::to(separated).unwrap();
+            assert_eq!(dealer, separated);
+            for spades in 0..=13 {
+                for hearts in 0..=13 - spades {
+                    for diamonds in 0..=13 - spades - hearts {
+                        let lengths = [spades, hearts, diamonds, 13 - spades - hearts - diamonds];
+                        assert_eq!(dealer.matches(&lengths), separated.matches(&lengths));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shape_pattern__matches() {
+        let any = ShapePattern::to("5-3-3-2").unwrap();
+        let ordered = ShapePattern::to("4+=4").unwrap();
+
+        assert!(any.matches(&[5, 3, 3, 2]));
+        assert!(any.matches(&[3, 2, 5, 3]));
+        assert!(!any.matches(&[4, 3, 3, 3]));
+        assert!(ordered.matches(&[4, 4, 3, 2]));
+        assert!(ordered.matches(&[6, 4, 3, 0]));
+        assert!(!ordered.matches(&[4, 5, 2, 2]));
+        assert!(!ordered.matches(&[3, 4, 3, 3]));
+        assert!(ShapePattern::to("5+-5+").unwrap().matches(&[1, 5, 1, 6]));
+        assert!(ShapePattern::to("5332").unwrap().matches(&[3, 2, 5, 3]));
+        assert!(!ShapePattern::to("5=3=3=2").unwrap().matches(&[3, 2, 5, 3]));
+    }
+
+    #[test]
+    fn predicate__matches() {
+        let hands = hands(DEAL);
+
+        assert!(Predicate::HCP(12, 14).matches(&hands[0]));
+        assert!(!Predicate::HCP(15, 17).matches(&hands[0]));
+        assert!(Predicate::LENGTH(0, 5, 13).matches(&hands[0]));
+        assert!(Predicate::BALANCED.matches(&hands[0]));
+        assert!(Predicate::HOLDS(Card::new(ACE, SPADES)).matches(&hands[0]));
+        assert!(Predicate::SHAPE(ShapePattern::to("any 5332").unwrap()).matches(&hands[0]));
+    }
+
+    #[test]
+    fn condition__eval() {
+        assert_eq!(14, eval("hcp(north)", DEAL));
+        assert_eq!(5, eval("hcp(north, spades)", DEAL));
+        assert_eq!(26, eval("hcp(north) + hcp(south)", DEAL));
+        assert_eq!(5, eval("spades(north)", DEAL));
+        assert_eq!(1, eval("hcp(north) >= 12 && hcp(north) <= 14", DEAL));
+        assert_eq!(1, eval("hcp(east) > 15 or balanced(north)", DEAL));
+        assert_eq!(0, eval("!balanced(north)", DEAL));
+        assert_eq!(1, eval("hcp(west) != 5", DEAL));
+        assert_eq!(
+            1,
+            eval("hascard(north, AS) and not hascard(north, KS)", DEAL)
+        );
+        assert_eq!(1, eval("shape(north, any 5332 - 4333)", DEAL));
+        assert_eq!(0, eval("shape(north, any 5332 - 5xxx)", DEAL));
+        assert_eq!(1, eval("shape(north, 5-3-3-2)", DEAL));
+        assert_eq!(1, eval("shape(north, 4+=3 - 6+xxx)", DEAL));
+        assert_eq!(0, eval("shape(north, 6+xxx + any 4333)", DEAL));
+        assert_eq!(1, eval("shape(north,5+xxx)", DEAL));
+        assert_eq!(5, eval("controls(north)", DEAL));
+    }
+
+    #[test]
+    fn condition__errors() {
+        let mut generator = DealGenerator::new();
+
+        assert!(generator.condition("hcp(up) > 4").is_err());
+        assert!(generator.condition("hcp(north > 4").is_err());
+        assert!(generator.condition("suits(north)").is_err());
+        assert!(generator.condition("hcp(north) 4").is_err());
+        assert!(generator.condition("shape(north, 5332 5xxx)").is_err());
+        assert!(generator.condition("shape(north, any)").is_err());
+    }
+
+    #[test]
+    fn hand_condition() {
+        let north = &hands(DEAL)[0];
+        let matches = |condition: &str| HandCondition::parse(condition).unwrap().matches(north);
+
+        assert!(matches("hcp >= 12 && hcp <= 14 && balanced"));
+        assert!(matches("spades == 5 && hcp(spades) == 5"));
+        assert!(matches(
+            "hascard(AS) + hascard(AH) + hascard(AD) + hascard(AC) == 2"
+        ));
+        assert!(matches("shape(any 5332)"));
+        assert!(!matches("losers < 7"));
+        assert!(HandCondition::parse("hcp(north) > 4").is_err());
+        assert!(HandCondition::parse("hcp >").is_err());
+    }
+
+    #[test]
+    fn from_script() {
+        let script = "
+            # Partscore battles
+            predeal north SAK, HQ
+            condition hcp(north) >= 12
+                && spades(south) >= 5
+            generate 5000
+            produce 3
+        ";
+        let mut generator = DealGenerator::from_script(script).unwrap();
+        let boards = generator.generate_all();
+
+        assert_eq!(3, generator.produce());
+        assert_eq!(3, boards.len());
+        for (i, board) in boards.iter().enumerate() {
+            assert!(board.is_valid());
+            assert_eq!(i + 1, board.number);
+            assert!(board.north.hcp() >= 12);
+            assert!(board.north.contains(&Card::new(KING, SPADES)));
+            assert!(board.north.contains(&Card::new(QUEEN, HEARTS)));
+            assert!(board.south.suit_lengths()[0] >= 5);
+        }
+        assert!(generator.acceptance_rate() > 0.0);
+        assert!(generator.acceptance_rate() <= 1.0);
+    }
+
+    #[test]
+    fn from_script__errors() {
+        let error = DealGenerator::from_script("predeal north SAK\nfoo").unwrap_err();
+        assert_eq!(2, error.line);
+
+        let error = DealGenerator::from_script("predeal north SAK\npredeal south SA").unwrap_err();
+        assert_eq!("line 2: cards can't be predealt", error.to_string());
+
+        assert_eq!(
+            3,
+            DealGenerator::from_script("generate 10\n\ncondition hcp(north) >")
+                .unwrap_err()
+                .line
+        );
+    }
+
+    #[test]
+    fn require() {
+        let mut generator = DealGenerator::new();
+        generator.require(&BridgeDirection::S, Predicate::HCP(20, 37));
+        generator.require(&BridgeDirection::E, Predicate::LENGTH(1, 0, 0));
+
+        let board = generator.generate().unwrap();
+
+        assert!(board.south.hcp() >= 20);
+        assert_eq!(0, board.east.suit_lengths()[1]);
+        assert_eq!(1, generator.accepted());
+        assert!(generator.attempts() >= 1);
+    }
+
+    #[test]
+    fn try_generate__overflow() {
+        let mut generator = DealGenerator::new();
+        generator
+            .condition("hcp(north) < 9223372036854775807 + 1")
+            .unwrap();
+
+        assert!(generator.try_generate().is_err());
+        assert!(generator.generate().is_none());
+        assert_eq!(0, generator.accepted());
+    }
+
+    #[test]
+    fn eval__overflow() {
+        let hands = hands(DEAL);
+        let eval = |condition: &str| Parser::new(condition).expr().unwrap().eval(&hands);
+
+        assert_eq!(Ok(1), eval("9223372036854775807 - 1 > 0"));
+        assert!(eval("0 - 9223372036854775807 - 2 < 0").is_err());
+        assert!(eval("hcp(north) * 9223372036854775807 > 0").is_err());
+    }
+
+    #[test]
+    fn generate__impossible() {
+        let mut generator = DealGenerator::new();
+        generator.condition("hcp(north) + hcp(south) > 40").unwrap();
+        generator.set_max_attempts(100);
+
+        assert!(generator.generate().is_none());
+        assert_eq!(100, generator.attempts());
+        assert_eq!(0.0, generator.acceptance_rate());
+    }
+}
@@ -0,0 +1,404 @@
+use crate::cards::card::Card;
+use crate::cards::decks::bridge::auction::{Auction, Call};
+use crate::cards::decks::bridge::contract::Strain;
+use crate::cards::decks::bridge::{
+    card_from_chars, card_to_text, holding_text, seat_index, split_once, suit_char_index,
+    BridgeBoard, BridgeDirection, Vulnerability, SUIT_CHARS,
+};
+use crate::cards::pile::Pile;
+
+/// The address of the Bridge Base Online hand viewer.
+pub const HANDVIEWER_URL: &str = "https://www.bridgebase.com/tools/handviewer.html";
+
+const SEAT_KEYS: [&str; 4] = ["n", "e", "s", "w"];
+
+/// A board as shown by the Bridge Base Online hand viewer, with its auction, play, players
+/// and title, which can be turned into a link and read back from one without going online.
+///
+/// The hand viewer takes each hand as a query parameter named after the seat, such as
+/// `n=sAKJhT98dA2cKJ943`, along with `d` for the dealer, `v` for the vulnerability (`-`,
+/// `n`, `e` or `b`), `b` for the board number, `a` for the auction (`p`, `d` and `r` for
+/// pass, double and redouble, and bids like `1n`), `p` for the cards played, `nn`, `en`,
+/// `sn` and `wn` for the players and `t` for a title.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeBoard, Handviewer};
+///
+/// let mut board = BridgeBoard::from_pbn_deal(
+///     "N:AKJ.T98.A2.KJ943 Q5.AJ.KT9543.Q72 T9876.K54.Q7.A65 432.Q7632.J86.T8",
+/// );
+/// board.set_number(1);
+/// let url = Handviewer::new(board.clone()).to_url();
+///
+/// assert_eq!(
+///     "https://www.bridgebase.com/tools/handviewer.html?n=sAKJhT98dA2cKJ943&e=sQ5hAJdKT9543cQ72\
+///      &s=sT9876hK54dQ7cA65&w=s432hQ7632dJ86cT8&d=n&v=-&b=1",
+///     url
+/// );
+/// assert_eq!(board, Handviewer::from_url(&url).unwrap().board);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Handviewer {
+    pub board: BridgeBoard,
+    pub auction: Option<Auction>,
+    /// The cards played, in order.
+    pub play: Vec<Card>,
+    /// The players' names, in North, East, South, West order.
+    pub players: [Option<String>; 4],
+    pub title: Option<String>,
+}
+
+impl Handviewer {
+    pub fn new(board: BridgeBoard) -> Handviewer {
+        Handviewer {
+            board,
+            auction: None,
+            play: Vec::new(),
+            players: Default::default(),
+            title: None,
+        }
+    }
+
+    /// Sets the name of the player in a seat.
+    pub fn set_player(&mut self, seat: &BridgeDirection, name: &str) {
+        if let Some(index) = seat_index(seat) {
+            self.players[index] = Some(name.to_string());
+        }
+    }
+
+    /// Returns the query string, without the leading `?`. Unknown hands are left out.
+    pub fn to_query(&self) -> String {
+        let mut parameters: Vec<(String, String)> = Vec::new();
+        for (key, seat) in SEAT_KEYS.iter().zip(BridgeDirection::seats().iter()) {
+            if let Some(hand) = self.board.get_hand(seat).filter(|hand| !hand.is_empty()) {
+                parameters.push((key.to_string(), hand_to_query(hand)));
+            }
+        }
+        if let Some(index) = seat_index(&self.board.dealer) {
+            parameters.push(("d".to_string(), SEAT_KEYS[index].to_string()));
+        }
+        let vulnerability = match self.board.vulnerability {
+            Vulnerability::NONE => "-",
+            Vulnerability::NS => "n",
+            Vulnerability::EW => "e",
+            Vulnerability::BOTH => "b",
+        };
+        parameters.push(("v".to_string(), vulnerability.to_string()));
+        if self.board.number > 0 {
+            parameters.push(("b".to_string(), self.board.number.to_string()));
+        }
+        if let Some(auction) = &self.auction {
+            let calls: String = auction.calls().iter().map(call_to_query).collect();
+            parameters.push(("a".to_string(), calls));
+        }
+        if !self.play.is_empty() {
+            let cards: String = self.play.iter().filter_map(card_to_text).collect();
+            parameters.push(("p".to_string(), cards));
+        }
+        for (key, name) in SEAT_KEYS.iter().zip(self.players.iter()) {
+            if let Some(name) = name {
+                parameters.push((format!("{}n", key), name.clone()));
+            }
+        }
+        if let Some(title) = &self.title {
+            parameters.push(("t".to_string(), title.clone()));
+        }
+
+        parameters
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, encode(value)))
+            .collect::<Vec<String>>()
+            .join("&")
+    }
+
+    pub fn to_url(&self) -> String {
+        format!("{}?{}", HANDVIEWER_URL, self.to_query())
+    }
+
+    /// Reads a hand viewer link, or just its query string. Parameters the hand viewer uses
+    /// for other things are ignored. Returns None if a hand, the auction or the play can't be
+    /// read, or the hands hold the same card twice. When three hands are given the fourth
+    /// holds the rest of the cards.
+    pub fn from_url(url: &str) -> Option<Handviewer> {
+        let query = match split_once(url, "?") {
+            Some((_, query)) => query,
+            None => url,
+        };
+        let query = query.split('#').next().unwrap_or("");
+
+        let mut viewer = Handviewer::new(BridgeBoard::default());
+        let mut number = None;
+        let mut dealer = None;
+        let mut vulnerability = None;
+        let mut auction = None;
+        let mut play = None;
+        for parameter in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = split_once(parameter, "=").unwrap_or((parameter, ""));
+            let value = decode(value)?;
+            let key = key.to_lowercase();
+            match key.as_str() {
+                "n" | "e" | "s" | "w" => {
+                    let seat = seat_for_key(&key)?;
+                    *viewer.board.get_hand_mut(&seat)? = hand_from_query(&value)?;
+                }
+                "nn" | "en" | "sn" | "wn" => {
+                    let index = seat_index(&seat_for_key(&key[..1])?)?;
+                    viewer.players[index] = Some(value);
+                }
+                "d" => dealer = Some(seat_for_key(&value.to_lowercase())?),
+                "v" => vulnerability = Some(vulnerability_from_query(&value)?),
+                "b" => number = Some(value.trim().parse::<usize>().ok()?),
+                "a" => auction = Some(value),
+                "p" => play = Some(value),
+                "t" => viewer.title = Some(value),
+                _ => {}
+            }
+        }
+
+        let unknown = viewer.board.unknown_seats();
+        if unknown.len() == 1 {
+            let unseen = viewer.board.unseen().sort();
+            if unseen.len() == 13 {
+                *viewer.board.get_hand_mut(&unknown[0])? = unseen;
+            }
+        }
+        if !viewer.board.is_consistent() {
+            return None;
+        }
+        if let Some(number) = number {
+            viewer.board.set_number(number);
+        }
+        if let Some(dealer) = dealer {
+            viewer.board.dealer = dealer;
+        }
+        if let Some(vulnerability) = vulnerability {
+            viewer.board.vulnerability = vulnerability;
+        }
+        if let Some(calls) = auction {
+            viewer.auction = Some(auction_from_query(&calls, &viewer.board.dealer)?);
+        }
+        if let Some(cards) = play {
+            viewer.play = play_from_query(&cards)?;
+        }
+        Some(viewer)
+    }
+}
+
+fn seat_for_key(key: &str) -> Option<BridgeDirection> {
+    SEAT_KEYS
+        .iter()
+        .position(|k| *k == key)
+        .map(|index| BridgeDirection::seats()[index])
+}
+
+fn vulnerability_from_query(value: &str) -> Option<Vulnerability> {
+    match value.to_lowercase().as_str() {
+        "-" | "o" | "0" | "none" => Some(Vulnerability::NONE),
+        "n" | "ns" => Some(Vulnerability::NS),
+        "e" | "ew" => Some(Vulnerability::EW),
+        "b" | "both" | "all" => Some(Vulnerability::BOTH),
+        _ => None,
+    }
+}
+
+/// A hand as each suit's letter followed by its ranks, such as `sAKJhT98dA2cKJ943`.
+fn hand_to_query(hand: &Pile) -> String {
+    let mut text = String::new();
+    for (suit, letter) in SUIT_CHARS.chars().enumerate() {
+        text.push(letter.to_ascii_lowercase());
+        text.push_str(&holding_text(hand, suit));
+    }
+    text
+}
+
+/// Reads a hand, ignoring case and any separators between the ranks.
+fn hand_from_query(text: &str) -> Option<Pile> {
+    let mut cards = Vec::new();
+    let mut suit = None;
+    let text = text.replace("10", "T");
+    for c in text.chars().filter(|c| c.is_ascii_alphanumeric()) {
+        match suit_char_index(c) {
+            Some(_) => suit = Some(c),
+            None => cards.push(card_from_chars(suit?, c)?),
+        }
+    }
+    Some(Pile::new_from_vector(cards).sort())
+}
+
+fn call_to_query(call: &Call) -> String {
+    match call {
+        Call::PASS => "p".to_string(),
+        Call::DOUBLE => "d".to_string(),
+        Call::REDOUBLE => "r".to_string(),
+        Call::BID(level, Strain::NT) => format!("{}n", level),
+        Call::BID(_, _) => call.to_pbn().to_lowercase(),
+    }
+}
+
+/// Reads the calls, skipping any explanations in parentheses.
+fn auction_from_query(text: &str, dealer: &BridgeDirection) -> Option<Auction> {
+    let mut auction = Auction::new(*dealer);
+    let mut depth = 0;
+    let mut chars = text.chars().filter(|c| !c.is_whitespace());
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ if depth > 0 => {}
+            '-' => {}
+            _ => {
+                let call = match c.to_ascii_lowercase() {
+                    'p' => Call::PASS,
+                    'd' | 'x' => Call::DOUBLE,
+                    'r' => Call::REDOUBLE,
+                    '1'..='7' => {
+                        let strain = match chars.next()?.to_ascii_lowercase() {
+                            'n' => Strain::NT,
+                            s => Strain::to(&s.to_string())?,
+                        };
+                        Call::BID(c.to_digit(10)? as usize, strain)
+                    }
+                    _ => return None,
+                };
+                if !auction.call(call) {
+                    return None;
+                }
+            }
+        }
+    }
+    Some(auction)
+}
+
+fn play_from_query(text: &str) -> Option<Vec<Card>> {
+    let text: Vec<char> = text.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+    text.chunks(2)
+        .map(|pair| card_from_chars(pair[0], *pair.get(1)?))
+        .collect()
+}
+
+/// Percent encodes everything other than letters, digits and `-_.~`.
+fn encode(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Decodes percent encoding, with `+` for a space. Returns None for a broken escape.
+fn decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+                decoded.push(u8::from_str_radix(hex, 16).ok()?);
+                i += 3;
+            }
+            b'+' => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_handviewer_tests {
+    use super::*;
+
+    const PBN_TEST_STRING: &str =
+        "N:AKJ.T98.A2.KJ943 Q5.AJ.KT9543.Q72 T9876.K54.Q7.A65 432.Q7632.J86.T8";
+
+    fn viewer() -> Handviewer {
+        let mut board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+        board.set_number(3);
+        let mut viewer = Handviewer::new(board);
+        let mut auction = Auction::new(BridgeDirection::S);
+        for call in "Pass Pass 1C Pass 1S Pass 2NT X Pass Pass 3NT Pass Pass Pass".split(' ') {
+            auction.call(Call::from_pbn(call).unwrap());
+        }
+        viewer.auction = Some(auction);
+        viewer.play = ["5D", "7D", "JD", "AD"]
+            .iter()
+            .map(|index| Pile::french_deck().card_by_index(index).unwrap().clone())
+            .collect();
+        viewer.set_player(&BridgeDirection::N, "Jo Smith");
+        viewer.title = Some("Club game & teams".to_string());
+        viewer
+    }
+
+    #[test]
+    fn to_query() {
+        assert_eq!(
+            "n=sAKJhT98dA2cKJ943&e=sQ5hAJdKT9543cQ72&s=sT9876hK54dQ7cA65&w=s432hQ7632dJ86cT8\
+             &d=s&v=e&b=3&a=pp1cp1sp2ndpp3nppp&p=D5D7DJDA&nn=Jo%20Smith\
+             &t=Club%20game%20%26%20teams",
+            viewer().to_query()
+        );
+    }
+
+    #[test]
+    fn to_query__unknown_hands() {
+        let board = BridgeBoard::from_pbn_deal("N:AKJ.T98.A2.KJ943 - - -");
+
+        assert_eq!(
+            "n=sAKJhT98dA2cKJ943&d=n&v=-&b=1",
+            Handviewer::new(board).to_query()
+        );
+    }
+
+    #[test]
+    fn from_url() {
+        let viewer = viewer();
+
+        assert_eq!(Some(viewer.clone()), Handviewer::from_url(&viewer.to_url()));
+    }
+
+    #[test]
+    fn from_url__three_hands() {
+        let url = "https://www.bridgebase.com/tools/handviewer.html?N=SAKJHT98DA2CKJ943\
+                   &e=sq5hajdkt9543cq72&s=s10-9-8-7-6hK54dQ7cA65&d=e&v=b&a=1n(15-17)ppp&x=1";
+
+        let viewer = Handviewer::from_url(url).unwrap();
+
+        assert!(viewer.board.is_valid());
+        assert_eq!(
+            BridgeBoard::from_pbn_deal(PBN_TEST_STRING).west,
+            viewer.board.west
+        );
+        assert_eq!(BridgeDirection::E, viewer.board.dealer);
+        assert_eq!(Vulnerability::BOTH, viewer.board.vulnerability);
+        assert_eq!("1NT", viewer.auction.unwrap().contract().unwrap().to_pbn());
+    }
+
+    #[test]
+    fn from_url__invalid() {
+        assert_eq!(None, Handviewer::from_url("n=sAKJhT98dA2cKJ943&s=sA"));
+        assert_eq!(None, Handviewer::from_url("n=AKJ"));
+        assert_eq!(None, Handviewer::from_url("d=n&a=1s1h"));
+        assert_eq!(None, Handviewer::from_url("p=S"));
+        assert_eq!(None, Handviewer::from_url("t=%4"));
+    }
+
+    #[test]
+    fn encode_and_decode() {
+        assert_eq!("a%20b%2Bc", encode("a b+c"));
+        assert_eq!(Some("a b+c".to_string()), decode("a+b%2Bc"));
+        assert_eq!(Some("Müller".to_string()), decode(&encode("Müller")));
+    }
+}
@@ -0,0 +1,362 @@
+use crate::cards::card::Card;
+use crate::cards::decks::bridge::{
+    holding_text, rank_char_index, rank_index, suit_index, BridgeBoard, BridgeDirection,
+    BRIDGE_RANKS, BRIDGE_SUITS,
+};
+use crate::cards::pile::Pile;
+
+/// The code page 437 bytes for the spade, heart, diamond and club symbols.
+const DGE_SUITS: [u8; 4] = [0x06, 0x03, 0x04, 0x05];
+const HANDS: usize = 68;
+const BRI_DEAL: usize = 78;
+
+/// The file formats used by duplicate dealing machines.
+///
+/// Every format stores each board as a fixed size record:
+///
+/// - **BRI:** 128 bytes. The cards held by North, East and South as two digit numbers, 13
+///   for each hand, with `01` the ace of spades through `52` the deuce of clubs. West holds
+///   the remaining cards. The deal is followed by 18 spaces and 32 zero bytes.
+/// - **DGE:** 128 bytes. The hands of North, East, South and West, each written as the four
+///   suit symbols from code page 437 followed by the ranks held, then padded with spaces.
+/// - **DUP:** 156 bytes. The four hands as in DGE, then `YN`, the board number and the
+///   number of boards in the file as four digits each, and the deal as in BRI.
+///
+/// BRI and DGE records are numbered in the order they appear in the file.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeBoard, DealFormat};
+///
+/// let boards = vec![BridgeBoard::deal_board(1), BridgeBoard::deal_board(2)];
+///
+/// let bytes = DealFormat::DUP.write(&boards).unwrap();
+///
+/// assert_eq!(312, bytes.len());
+/// assert_eq!(boards, DealFormat::DUP.read(&bytes).unwrap());
+/// ```
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DealFormat {
+    BRI,
+    DGE,
+    DUP,
+}
+
+impl DealFormat {
+    /// Returns the format for a file extension, such as `dup` or `.BRI`.
+    pub fn from_extension(extension: &str) -> Option<DealFormat> {
+        match extension.trim_start_matches('.').to_lowercase().as_str() {
+            "bri" => Some(DealFormat::BRI),
+            "dge" => Some(DealFormat::DGE),
+            "dup" => Some(DealFormat::DUP),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DealFormat::BRI => "bri",
+            DealFormat::DGE => "dge",
+            DealFormat::DUP => "dup",
+        }
+    }
+
+    /// The size in bytes of each board's record.
+    pub fn record_size(&self) -> usize {
+        match self {
+            DealFormat::BRI | DealFormat::DGE => 128,
+            DealFormat::DUP => 156,
+        }
+    }
+
+    /// Writes the boards in the format, returning None if any of them isn't a complete deal.
+    /// DUP files also return None for a board number or board count over 9999, as each has
+    /// only four digits.
+    pub fn write(&self, boards: &[BridgeBoard]) -> Option<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(boards.len() * self.record_size());
+        for board in boards.iter() {
+            if !is_complete(board) {
+                return None;
+            }
+            match self {
+                DealFormat::BRI => {
+                    bytes.extend(bri_deal(board));
+                    bytes.extend(vec![b' '; 18]);
+                    bytes.extend(vec![0; 32]);
+                }
+                DealFormat::DGE => {
+                    bytes.extend(dge_hands(board));
+                    bytes.extend(vec![b' '; 128 - HANDS]);
+                }
+                DealFormat::DUP => {
+                    if board.number > 9999 || boards.len() > 9999 {
+                        return None;
+                    }
+                    bytes.extend(dge_hands(board));
+                    bytes.extend(b"YN");
+                    bytes.extend(format!("{:04}{:04}", board.number, boards.len()).bytes());
+                    bytes.extend(bri_deal(board));
+                }
+            }
+        }
+        Some(bytes)
+    }
+
+    /// Reads the boards from a file in the format. Returns None if the file isn't made up of
+    /// whole records, or if any of the boards isn't a complete deal.
+    pub fn read(&self, bytes: &[u8]) -> Option<Vec<BridgeBoard>> {
+        let size = self.record_size();
+        if bytes.is_empty() || bytes.len() % size != 0 {
+            return None;
+        }
+
+        let mut boards = Vec::new();
+        for (i, record) in bytes.chunks(size).enumerate() {
+            let board = match self {
+                DealFormat::BRI => read_bri_deal(&record[..BRI_DEAL], i + 1)?,
+                DealFormat::DGE => read_dge_hands(&record[..HANDS], i + 1)?,
+                DealFormat::DUP => {
+                    if &record[HANDS..HANDS + 2] != b"YN" {
+                        return None;
+                    }
+                    let number = std::str::from_utf8(&record[HANDS + 2..HANDS + 6])
+                        .ok()?
+                        .trim()
+                        .parse()
+                        .ok()?;
+                    let board = read_bri_deal(&record[size - BRI_DEAL..], number)?;
+                    if read_dge_hands(&record[..HANDS], number)? != board {
+                        return None;
+                    }
+                    board
+                }
+            };
+            boards.push(board);
+        }
+        Some(boards)
+    }
+}
+
+fn is_complete(board: &BridgeBoard) -> bool {
+    board.is_valid()
+        && BridgeDirection::seats()
+            .iter()
+            .all(|d| board.get_hand(d).map_or(false, |hand| hand.len() == 13))
+}
+
+fn card_from_number(number: usize) -> Option<Card> {
+    if !(1..=52).contains(&number) {
+        return None;
+    }
+    let index = number - 1;
+    Some(Card::new(
+        BRIDGE_RANKS[index % 13],
+        BRIDGE_SUITS[index / 13],
+    ))
+}
+
+fn bri_deal(board: &BridgeBoard) -> Vec<u8> {
+    let mut deal = String::new();
+    for direction in [BridgeDirection::N, BridgeDirection::E, BridgeDirection::S].iter() {
+        let mut numbers: Vec<usize> = board
+            .get_hand(direction)
+            .map(|hand| {
+                hand.values()
+                    .filter_map(|card| Some(suit_index(card)? * 13 + rank_index(card)? + 1))
+                    .collect()
+            })
+            .unwrap_or_default();
+        numbers.sort_unstable();
+        for number in numbers {
+            deal.push_str(&format!("{:02}", number));
+        }
+    }
+    deal.into_bytes()
+}
+
+fn read_bri_deal(record: &[u8], number: usize) -> Option<BridgeBoard> {
+    let digits = std::str::from_utf8(record).ok()?;
+    let mut board = BridgeBoard::default();
+    board.set_number(number);
+
+    let mut remaining = Pile::french_deck();
+    for (i, direction) in [BridgeDirection::N, BridgeDirection::E, BridgeDirection::S]
+        .iter()
+        .enumerate()
+    {
+        let mut cards = Vec::new();
+        for card in 0..13 {
+            let start = (i * 13 + card) * 2;
+            let number = digits.get(start..start + 2)?.parse().ok()?;
+            cards.push(remaining.remove_card(&card_from_number(number)?)?);
+        }
+        *board.get_hand_mut(direction)? = Pile::new_from_vector(cards).sort();
+    }
+    board.west = remaining.sort();
+    Some(board)
+}
+
+fn dge_hands(board: &BridgeBoard) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HANDS);
+    for direction in BridgeDirection::seats().iter() {
+        let hand = board.get_hand(direction).cloned().unwrap_or_default();
+        for (i, marker) in DGE_SUITS.iter().enumerate() {
+            bytes.push(*marker);
+            bytes.extend(holding_text(&hand, i).bytes());
+        }
+    }
+    bytes
+}
+
+fn read_dge_hands(record: &[u8], number: usize) -> Option<BridgeBoard> {
+    let mut board = BridgeBoard::default();
+    board.set_number(number);
+
+    let mut bytes = record.iter();
+    let mut next = bytes.next();
+    for direction in BridgeDirection::seats().iter() {
+        let mut cards = Vec::new();
+        for (suit, marker) in DGE_SUITS.iter().enumerate() {
+            if next != Some(marker) {
+                return None;
+            }
+            next = bytes.next();
+            while let Some(rank) = next.and_then(|b| rank_char_index(*b as char)) {
+                cards.push(Card::new(BRIDGE_RANKS[rank], BRIDGE_SUITS[suit]));
+                next = bytes.next();
+            }
+        }
+        *board.get_hand_mut(direction)? = Pile::new_from_vector(cards).sort();
+    }
+    if next.is_some() || !is_complete(&board) {
+        return None;
+    }
+    Some(board)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_machine_tests {
+    use super::*;
+
+    const PBN_TEST_STRING: &str =
+        "S:Q42.Q52.AQT943.Q 97.AT93.652.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982";
+
+    fn boards() -> Vec<BridgeBoard> {
+        let mut first = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+        first.set_number(1);
+        vec![
+            first,
+            BridgeBoard::deal_board(2),
+            BridgeBoard::deal_board(3),
+        ]
+    }
+
+    #[test]
+    fn from_extension() {
+        assert_eq!(Some(DealFormat::DUP), DealFormat::from_extension("dup"));
+        assert_eq!(Some(DealFormat::BRI), DealFormat::from_extension(".BRI"));
+        assert_eq!(Some(DealFormat::DGE), DealFormat::from_extension("Dge"));
+        assert_eq!(None, DealFormat::from_extension("pbn"));
+        assert_eq!("dge", DealFormat::DGE.extension());
+    }
+
+    #[test]
+    fn write__bri() {
+        let bytes = DealFormat::BRI.write(&boards()).unwrap();
+
+        assert_eq!(384, bytes.len());
+        // North starts with the ace of spades and East with the king.
+        assert_eq!(b"01040507", &bytes[..8]);
+        assert_eq!(b"02", &bytes[26..28]);
+        assert_eq!(b' ', bytes[78]);
+        assert_eq!(0, bytes[127]);
+    }
+
+    #[test]
+    fn write__dge() {
+        let bytes = DealFormat::DGE.write(&boards()).unwrap();
+
+        assert_eq!(&[0x06, b'A', b'J', b'T', b'8', b'5', 0x03], &bytes[..7]);
+        assert_eq!(b' ', bytes[68]);
+    }
+
+    #[test]
+    fn write__dup() {
+        let bytes = DealFormat::DUP.write(&boards()).unwrap();
+
+        assert_eq!(468, bytes.len());
+        assert_eq!(b"YN00010003", &bytes[68..78]);
+        assert_eq!(b"01040507", &bytes[78..86]);
+        assert_eq!(b"YN00030003", &bytes[156 * 2 + 68..156 * 2 + 78]);
+    }
+
+    #[test]
+    fn write__incomplete() {
+        let mut boards = boards();
+        boards[1].north.draw_first();
+
+        assert!(DealFormat::BRI.write(&boards).is_none());
+        assert!(DealFormat::DUP.write(&[BridgeBoard::default()]).is_none());
+    }
+
+    #[test]
+    fn write__dup_too_many() {
+        let mut board = BridgeBoard::deal();
+        board.set_number(10_000);
+
+        assert!(DealFormat::DUP.write(&[board.clone()]).is_none());
+        assert!(DealFormat::BRI.write(&[board.clone()]).is_some());
+        board.set_number(9999);
+        assert!(DealFormat::DUP.write(&[board.clone()]).is_some());
+        assert!(DealFormat::DUP.write(&vec![board; 10_000]).is_none());
+    }
+
+    #[test]
+    fn read__round_trip() {
+        let boards = boards();
+
+        for format in [DealFormat::BRI, DealFormat::DGE, DealFormat::DUP].iter() {
+            let bytes = format.write(&boards).unwrap();
+            assert_eq!(boards, format.read(&bytes).unwrap());
+        }
+    }
+
+    #[test]
+    fn read__dup_board_numbers() {
+        let mut board = BridgeBoard::deal();
+        board.set_number(17);
+
+        let bytes = DealFormat::DUP.write(&[board.clone()]).unwrap();
+        let boards = DealFormat::DUP.read(&bytes).unwrap();
+
+        assert_eq!(17, boards[0].number);
+        assert_eq!(board.vulnerability, boards[0].vulnerability);
+    }
+
+    #[test]
+    fn read__invalid() {
+        let mut bri = DealFormat::BRI.write(&boards()).unwrap();
+        let mut dge = DealFormat::DGE.write(&boards()).unwrap();
+        let dup = DealFormat::DUP.write(&boards()).unwrap();
+
+        assert!(DealFormat::BRI.read(&[]).is_none());
+        assert!(DealFormat::BRI.read(&bri[..100]).is_none());
+        assert!(DealFormat::DUP.read(&bri).is_none());
+
+        // The same card twice.
+        bri[2] = b'0';
+        bri[3] = b'1';
+        assert!(DealFormat::BRI.read(&bri).is_none());
+
+        // A card missing from West.
+        dge[67] = b' ';
+        assert!(DealFormat::DGE.read(&dge).is_none());
+
+        // The two copies of the deal disagree.
+        let mut mixed = dup.clone();
+        mixed[78..156].copy_from_slice(&dup[156 + 78..312]);
+        assert!(DealFormat::DUP.read(&mixed).is_none());
+    }
+}
@@ -0,0 +1,350 @@
+use crate::cards::card::Card;
+use crate::cards::decks::bridge::contract::{Contract, Strain};
+use crate::cards::decks::bridge::evaluation::HandEvaluation;
+use crate::cards::decks::bridge::play::BridgePlay;
+use crate::cards::decks::bridge::{BridgeBoard, BridgeDirection, BridgeSide};
+use crate::cards::pile::Pile;
+
+/// The contract declarer picks in minibridge: a trump suit or no trump, and whether to try
+/// for game or play in a part score.
+///
+/// A part score needs seven tricks. Game needs nine in no trump, ten in a major and eleven
+/// in a minor.
+///
+/// Scoring follows the minibridge score table:
+///
+/// | | Part score | Game |
+/// |---|---|---|
+/// | Each trick over six | clubs and diamonds 20, hearts and spades 30, no trump 40 for the first and 30 after | same |
+/// | Bonus when it makes | 50 | 300, or 500 vulnerable |
+/// | Each trick short | 50, or 100 vulnerable | same |
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MinibridgeContract {
+    pub strain: Strain,
+    pub game: bool,
+    pub declarer: BridgeDirection,
+}
+
+impl MinibridgeContract {
+    pub fn new(strain: Strain, game: bool, declarer: BridgeDirection) -> MinibridgeContract {
+        MinibridgeContract {
+            strain,
+            game,
+            declarer,
+        }
+    }
+
+    pub fn tricks_needed(&self) -> usize {
+        self.level() + 6
+    }
+
+    /// The score for declarer's side when declarer takes the passed in number of tricks.
+    pub fn score(&self, tricks: usize, vulnerable: bool) -> isize {
+        let needed = self.tricks_needed();
+        if tricks < needed {
+            let per_trick = if vulnerable { 100 } else { 50 };
+            return -per_trick * (needed - tricks) as isize;
+        }
+
+        let over = tricks.saturating_sub(6) as isize;
+        let trick_score = match self.strain {
+            Strain::C | Strain::D => 20 * over,
+            Strain::H | Strain::S => 30 * over,
+            Strain::NT => 30 * over + 10,
+        };
+        let bonus = match (self.game, vulnerable) {
+            (false, _) => 50,
+            (true, false) => 300,
+            (true, true) => 500,
+        };
+        trick_score + bonus
+    }
+
+    /// The equivalent Bridge contract, which is played the same way.
+    pub fn to_contract(&self) -> Contract {
+        Contract::new(self.level(), self.strain, self.declarer)
+    }
+
+    fn level(&self) -> usize {
+        match (self.game, self.strain) {
+            (false, _) => 1,
+            (true, Strain::NT) => 3,
+            (true, Strain::H) | (true, Strain::S) => 4,
+            (true, Strain::C) | (true, Strain::D) => 5,
+        }
+    }
+}
+
+/// A game of minibridge on a BridgeBoard.
+///
+/// There is no auction. Each player counts their high card points, and the side with more
+/// points declares, with the partner holding more of them as declarer. When partners have
+/// the same count, the one who would speak first from the dealer declares. If both sides
+/// have 20 points nobody declares and the board is redealt.
+///
+/// Declarer's partner puts their hand down as dummy straight away, and after looking at it
+/// declarer chooses the contract. The player on declarer's left then leads, and the play
+/// follows the normal Bridge rules.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeBoard, BridgeDirection, Minibridge, Strain};
+///
+/// let board = BridgeBoard::from_pbn_deal(
+///     "N:AKQJ.AKQ.AKQ.AKQ T987.JT9.JT9.JT9 6543.876.876.876 2.5432.5432.5432",
+/// );
+/// let mut game = Minibridge::new(&board);
+///
+/// assert_eq!(Some(BridgeDirection::N), game.declarer());
+/// assert_eq!(13, game.dummy_hand().unwrap().len());
+///
+/// assert!(game.choose_contract(Strain::NT, true));
+/// assert_eq!(BridgeDirection::E, game.play().unwrap().to_play());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Minibridge {
+    board: BridgeBoard,
+    declarer: Option<BridgeDirection>,
+    contract: Option<MinibridgeContract>,
+    play: Option<BridgePlay>,
+}
+
+impl Minibridge {
+    pub fn new(board: &BridgeBoard) -> Minibridge {
+        let mut game = Minibridge {
+            board: board.clone(),
+            declarer: None,
+            contract: None,
+            play: None,
+        };
+        game.declarer = game.find_declarer();
+        game
+    }
+
+    pub fn board(&self) -> &BridgeBoard {
+        &self.board
+    }
+
+    /// The high card points a player announces.
+    pub fn hcp(&self, direction: &BridgeDirection) -> usize {
+        self.board.get_hand(direction).map_or(0, |hand| hand.hcp())
+    }
+
+    pub fn side_hcp(&self, side: &BridgeSide) -> usize {
+        side.seats().iter().map(|seat| self.hcp(seat)).sum()
+    }
+
+    /// The declarer, or None if both sides hold 20 points.
+    pub fn declarer(&self) -> Option<BridgeDirection> {
+        self.declarer
+    }
+
+    pub fn dummy(&self) -> Option<BridgeDirection> {
+        self.declarer.map(|declarer| declarer.partner())
+    }
+
+    /// Dummy's cards, which are shown before declarer picks the contract and stay on view
+    /// as they are played.
+    pub fn dummy_hand(&self) -> Option<&Pile> {
+        let dummy = self.dummy()?;
+        match &self.play {
+            Some(play) => play.hand(&dummy),
+            None => self.board.get_hand(&dummy),
+        }
+    }
+
+    /// Declarer picks the contract, which starts the play. Returns false if there is no
+    /// declarer or the contract has already been chosen.
+    pub fn choose_contract(&mut self, strain: Strain, game: bool) -> bool {
+        let declarer = match self.declarer {
+            Some(declarer) if self.contract.is_none() => declarer,
+            _ => return false,
+        };
+        let contract = MinibridgeContract::new(strain, game, declarer);
+        self.play = Some(BridgePlay::new(&self.board, contract.to_contract()));
+        self.contract = Some(contract);
+        true
+    }
+
+    pub fn contract(&self) -> Option<&MinibridgeContract> {
+        self.contract.as_ref()
+    }
+
+    pub fn play(&self) -> Option<&BridgePlay> {
+        self.play.as_ref()
+    }
+
+    /// Plays a card for the player whose turn it is, returning false if it isn't legal or
+    /// no contract has been chosen yet.
+    pub fn play_card(&mut self, card: &Card) -> bool {
+        self.play.as_mut().map_or(false, |play| play.play(card))
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.play.as_ref().map_or(false, |play| play.is_complete())
+    }
+
+    /// The score from North-South's point of view once all the cards have been played. A
+    /// board where nobody declares scores zero.
+    pub fn score(&self) -> Option<isize> {
+        if self.declarer.is_none() {
+            return Some(0);
+        }
+        let play = self.play.as_ref().filter(|play| play.is_complete())?;
+        let contract = self.contract?;
+        let declarer = contract.declarer;
+        let vulnerable = self.board.is_vulnerable(&declarer);
+        let score = contract.score(play.declarer_tricks(), vulnerable);
+        match declarer.side() {
+            Some(BridgeSide::NS) => Some(score),
+            _ => Some(-score),
+        }
+    }
+
+    fn find_declarer(&self) -> Option<BridgeDirection> {
+        let ns = self.side_hcp(&BridgeSide::NS);
+        let ew = self.side_hcp(&BridgeSide::EW);
+        let side = match ns.cmp(&ew) {
+            std::cmp::Ordering::Greater => BridgeSide::NS,
+            std::cmp::Ordering::Less => BridgeSide::EW,
+            std::cmp::Ordering::Equal => return None,
+        };
+        let dealer = match self.board.dealer {
+            BridgeDirection::UNKNOWN => BridgeDirection::N,
+            dealer => dealer,
+        };
+        let mut declarer: Option<BridgeDirection> = None;
+        for seat in dealer.clockwise() {
+            if seat.side() != Some(side) {
+                continue;
+            }
+            if declarer.map_or(true, |d| self.hcp(&seat) > self.hcp(&d)) {
+                declarer = Some(seat);
+            }
+        }
+        declarer
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_minibridge_tests {
+    use super::*;
+    use crate::cards::rank::TEN;
+    use crate::cards::suit::SPADES;
+
+    const PBN_TEST_STRING: &str =
+        "S:Q42.Q52.AQT943.Q 97.AT93.652.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982";
+    const TOP_CARDS: &str = "N:AKQJ.AKQ.AKQ.AKQ T987.JT9.JT9.JT9 6543.876.876.876 2.5432.5432.5432";
+
+    #[test]
+    fn contract__tricks_needed() {
+        let declarer = BridgeDirection::S;
+
+        assert_eq!(
+            7,
+            MinibridgeContract::new(Strain::C, false, declarer).tricks_needed()
+        );
+        assert_eq!(
+            9,
+            MinibridgeContract::new(Strain::NT, true, declarer).tricks_needed()
+        );
+        assert_eq!(
+            10,
+            MinibridgeContract::new(Strain::H, true, declarer).tricks_needed()
+        );
+        assert_eq!(
+            11,
+            MinibridgeContract::new(Strain::D, true, declarer).tricks_needed()
+        );
+    }
+
+    #[test]
+    fn contract__score() {
+        let declarer = BridgeDirection::S;
+        let part_nt = MinibridgeContract::new(Strain::NT, false, declarer);
+        let part_clubs = MinibridgeContract::new(Strain::C, false, declarer);
+        let game_spades = MinibridgeContract::new(Strain::S, true, declarer);
+        let game_diamonds = MinibridgeContract::new(Strain::D, true, declarer);
+
+        assert_eq!(90, part_nt.score(7, false));
+        assert_eq!(150, part_nt.score(9, true));
+        assert_eq!(110, part_clubs.score(9, false));
+        assert_eq!(420, game_spades.score(10, false));
+        assert_eq!(650, game_spades.score(11, true));
+        assert_eq!(400, game_diamonds.score(11, false));
+        assert_eq!(-100, game_spades.score(8, false));
+        assert_eq!(-300, game_diamonds.score(8, true));
+        assert_eq!(-50, part_clubs.score(6, false));
+    }
+
+    #[test]
+    fn contract__to_contract() {
+        let contract = MinibridgeContract::new(Strain::H, true, BridgeDirection::W).to_contract();
+
+        assert_eq!("4H", contract.to_pbn());
+        assert_eq!(BridgeDirection::W, contract.declarer);
+    }
+
+    #[test]
+    fn declarer() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+
+        let game = Minibridge::new(&board);
+
+        assert_eq!(12, game.hcp(&BridgeDirection::S));
+        assert_eq!(14, game.hcp(&BridgeDirection::N));
+        assert_eq!(26, game.side_hcp(&BridgeSide::NS));
+        assert_eq!(Some(BridgeDirection::N), game.declarer());
+        assert_eq!(Some(BridgeDirection::S), game.dummy());
+        assert_eq!(board.south, *game.dummy_hand().unwrap());
+    }
+
+    #[test]
+    fn declarer__partners_tied() {
+        // North and South both hold 11 points and East deals, so South speaks first.
+        let mut board = BridgeBoard::from_pbn_deal(
+            "N:AKJ2.Q32.J32.432 T86.J975.K97.KJ9 Q53.AK4.Q654.765 974.T86.AT8.AQT8",
+        );
+        board.set_number(2);
+
+        let game = Minibridge::new(&board);
+
+        assert_eq!(11, game.hcp(&BridgeDirection::N));
+        assert_eq!(11, game.hcp(&BridgeDirection::S));
+        assert_eq!(Some(BridgeDirection::S), game.declarer());
+    }
+
+    #[test]
+    fn declarer__sides_tied() {
+        let board = BridgeBoard::from_pbn_deal(
+            "N:AKQJ.T98.T98.T98 T98.AKQJ.765.765 6543.76.AKQJ.432 72.5432.432.AKQJ",
+        );
+
+        let mut game = Minibridge::new(&board);
+
+        assert_eq!(None, game.declarer());
+        assert!(game.dummy_hand().is_none());
+        assert!(!game.choose_contract(Strain::NT, false));
+        assert_eq!(Some(0), game.score());
+    }
+
+    #[test]
+    fn play_and_score() {
+        let board = BridgeBoard::from_pbn_deal(TOP_CARDS);
+        let mut game = Minibridge::new(&board);
+
+        assert!(!game.play_card(&Card::new(TEN, SPADES)));
+        assert!(game.choose_contract(Strain::NT, true));
+        assert!(!game.choose_contract(Strain::S, false));
+        assert_eq!(None, game.score());
+
+        while !game.is_complete() {
+            let card = game.play().unwrap().legal_plays().get(0).unwrap().clone();
+            assert!(game.play_card(&card));
+        }
+
+        assert_eq!(13, game.play().unwrap().declarer_tricks());
+        assert_eq!(Some(520), game.score());
+    }
+}
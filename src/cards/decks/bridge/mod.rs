@@ -0,0 +1,1104 @@
+use crate::cards::card::Card;
+use crate::cards::pack::Pack;
+use crate::cards::pile::Pile;
+use crate::cards::rank::*;
+use crate::cards::suit::*;
+use crate::Named;
+use dds::{DoubleDummy, TrickTable};
+use evaluation::HandEvaluation;
+use std::fmt;
+
+pub mod auction;
+pub mod bidding;
+pub mod bluechip;
+pub mod contract;
+pub mod database;
+pub mod dds;
+pub mod dealset;
+pub mod diagram;
+pub mod distribution;
+pub mod evaluation;
+pub mod generator;
+pub mod handviewer;
+pub mod machine;
+pub mod minibridge;
+pub mod movement;
+pub mod numbering;
+pub mod par;
+pub mod play;
+pub mod rbn;
+pub mod record;
+pub mod session;
+pub mod transform;
+
+/// The French Deck suits in the order they appear in Bridge notation.
+pub const BRIDGE_SUITS: [&str; 4] = [SPADES, HEARTS, DIAMONDS, CLUBS];
+
+/// The French Deck ranks from highest to lowest.
+pub const BRIDGE_RANKS: [&str; 13] = [
+    ACE, KING, QUEEN, JACK, TEN, NINE, EIGHT, SEVEN, SIX, FIVE, FOUR, THREE, TWO,
+];
+
+/// Returns the position of the Card's Suit in `BRIDGE_SUITS`, so spades are 0 and clubs 3.
+pub fn suit_index(card: &Card) -> Option<usize> {
+    BRIDGE_SUITS.iter().position(|s| *s == card.suit.name())
+}
+
+/// Returns the position of the Card's Rank in `BRIDGE_RANKS`, so aces are 0 and deuces 12.
+pub fn rank_index(card: &Card) -> Option<usize> {
+    BRIDGE_RANKS.iter().position(|r| *r == card.rank.name())
+}
+
+/// The letters for the suits in `BRIDGE_SUITS` order, as written in PBN and the other text
+/// formats.
+pub const SUIT_CHARS: &str = "SHDC";
+
+/// The letters for the ranks in `BRIDGE_RANKS` order, with `T` for the ten.
+pub const RANK_CHARS: &str = "AKQJT98765432";
+
+/// Returns the position of a suit letter in `SUIT_CHARS`, ignoring case.
+pub fn suit_char_index(c: char) -> Option<usize> {
+    SUIT_CHARS.find(c.to_ascii_uppercase())
+}
+
+/// Returns the position of a rank letter in `RANK_CHARS`, ignoring case.
+pub fn rank_char_index(c: char) -> Option<usize> {
+    RANK_CHARS.find(c.to_ascii_uppercase())
+}
+
+/// Returns the Card for a suit letter and a rank letter, ignoring case, so `S` and `A` are
+/// the ace of spades.
+pub fn card_from_chars(suit: char, rank: char) -> Option<Card> {
+    Some(Card::new(
+        BRIDGE_RANKS[rank_char_index(rank)?],
+        BRIDGE_SUITS[suit_char_index(suit)?],
+    ))
+}
+
+/// Returns the Card as its suit letter followed by its rank letter, such as `SA` for the ace
+/// of spades, or None if it isn't a Bridge card.
+pub fn card_to_text(card: &Card) -> Option<String> {
+    let suit = SUIT_CHARS.as_bytes()[suit_index(card)?] as char;
+    let rank = RANK_CHARS.as_bytes()[rank_index(card)?] as char;
+    Some(format!("{}{}", suit, rank))
+}
+
+/// Returns the rank letters of the hand's cards in the suit at `suit` in `BRIDGE_SUITS`,
+/// from highest to lowest, such as `AQT2`.
+pub fn holding_text(hand: &Pile, suit: usize) -> String {
+    let mut ranks: Vec<usize> = hand
+        .values()
+        .filter(|card| suit_index(card) == Some(suit))
+        .filter_map(rank_index)
+        .collect();
+    ranks.sort_unstable();
+    ranks
+        .iter()
+        .map(|rank| RANK_CHARS.as_bytes()[*rank] as char)
+        .collect()
+}
+
+/// Splits the text around the first `separator`, as `str::split_once` does in newer Rust
+/// than the crate supports.
+pub(crate) fn split_once<'a>(text: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    let at = text.find(separator)?;
+    Some((&text[..at], &text[at + separator.len()..]))
+}
+
+/// Returns the position of the seat in `BridgeDirection::seats()`, so North is 0 and West 3.
+pub fn seat_index(direction: &BridgeDirection) -> Option<usize> {
+    BridgeDirection::seats().iter().position(|d| d == direction)
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BridgeDirection {
+    N,
+    E,
+    S,
+    W,
+    UNKNOWN,
+}
+
+impl BridgeDirection {
+    pub fn to(c: char) -> BridgeDirection {
+        match c {
+            'S' => BridgeDirection::S,
+            's' => BridgeDirection::S,
+            'N' => BridgeDirection::N,
+            'n' => BridgeDirection::N,
+            'E' => BridgeDirection::E,
+            'e' => BridgeDirection::E,
+            'W' => BridgeDirection::W,
+            'w' => BridgeDirection::W,
+            _ => BridgeDirection::UNKNOWN,
+        }
+    }
+
+    /// Returns the four seats in clockwise order starting with North.
+    pub fn seats() -> Vec<BridgeDirection> {
+        vec![
+            BridgeDirection::N,
+            BridgeDirection::E,
+            BridgeDirection::S,
+            BridgeDirection::W,
+        ]
+    }
+
+    /// Returns the dealer for a board number under the standard rotation, where board 1 is
+    /// dealt by North, board 2 by East, and so on. Board 0 is treated as board 16, the same
+    /// as `Vulnerability::for_board()` does.
+    pub fn dealer_for_board(board_number: usize) -> BridgeDirection {
+        BridgeDirection::seats()[(board_number + 3) % 4]
+    }
+
+    /// Returns the character used for the seat in Portable Bridge Notation.
+    pub fn to_char(&self) -> char {
+        match self {
+            BridgeDirection::N => 'N',
+            BridgeDirection::E => 'E',
+            BridgeDirection::S => 'S',
+            BridgeDirection::W => 'W',
+            BridgeDirection::UNKNOWN => '?',
+        }
+    }
+
+    /// Left hand opponent; the seat that plays after this one.
+    pub fn lho(&self) -> BridgeDirection {
+        match self {
+            BridgeDirection::S => BridgeDirection::W,
+            BridgeDirection::W => BridgeDirection::N,
+            BridgeDirection::N => BridgeDirection::E,
+            BridgeDirection::E => BridgeDirection::S,
+            BridgeDirection::UNKNOWN => BridgeDirection::UNKNOWN,
+        }
+    }
+
+    /// Right hand opponent; the seat that plays before this one.
+    pub fn rho(&self) -> BridgeDirection {
+        self.lho().lho().lho()
+    }
+
+    pub fn partner(&self) -> BridgeDirection {
+        self.lho().lho()
+    }
+
+    /// Returns all four seats in playing order starting with this one, so that
+    /// `dealer.clockwise()` is the order of the auction.
+    pub fn clockwise(&self) -> Vec<BridgeDirection> {
+        match self {
+            BridgeDirection::UNKNOWN => Vec::new(),
+            _ => vec![*self, self.lho(), self.partner(), self.rho()],
+        }
+    }
+
+    pub fn side(&self) -> Option<BridgeSide> {
+        match self {
+            BridgeDirection::N | BridgeDirection::S => Some(BridgeSide::NS),
+            BridgeDirection::E | BridgeDirection::W => Some(BridgeSide::EW),
+            BridgeDirection::UNKNOWN => None,
+        }
+    }
+
+    pub fn is_partner_of(&self, other: &BridgeDirection) -> bool {
+        *self != BridgeDirection::UNKNOWN && self.partner() == *other
+    }
+}
+
+impl fmt::Display for BridgeDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// One of the two partnerships at a Bridge table.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BridgeSide {
+    NS,
+    EW,
+}
+
+impl BridgeSide {
+    pub fn opponents(&self) -> BridgeSide {
+        match self {
+            BridgeSide::NS => BridgeSide::EW,
+            BridgeSide::EW => BridgeSide::NS,
+        }
+    }
+
+    /// Returns the two seats of the partnership.
+    pub fn seats(&self) -> Vec<BridgeDirection> {
+        match self {
+            BridgeSide::NS => vec![BridgeDirection::N, BridgeDirection::S],
+            BridgeSide::EW => vec![BridgeDirection::E, BridgeDirection::W],
+        }
+    }
+}
+
+/// Which partnerships are vulnerable on a board.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Vulnerability {
+    NONE,
+    NS,
+    EW,
+    BOTH,
+}
+
+impl Vulnerability {
+    /// Returns the vulnerability for a board number under the standard 16 board rotation.
+    /// Board 0 is treated as board 16.
+    pub fn for_board(board_number: usize) -> Vulnerability {
+        match board_number % 16 {
+            1 | 8 | 11 | 14 => Vulnerability::NONE,
+            2 | 5 | 12 | 15 => Vulnerability::NS,
+            3 | 6 | 9 | 0 => Vulnerability::EW,
+            _ => Vulnerability::BOTH,
+        }
+    }
+
+    /// Parses the value of a PBN `Vulnerable` tag, such as `None`, `NS`, `EW` or `All`.
+    pub fn from_pbn(s: &str) -> Option<Vulnerability> {
+        match s.trim().to_lowercase().as_str() {
+            "none" | "-" | "love" => Some(Vulnerability::NONE),
+            "ns" => Some(Vulnerability::NS),
+            "ew" => Some(Vulnerability::EW),
+            "all" | "both" => Some(Vulnerability::BOTH),
+            _ => None,
+        }
+    }
+
+    pub fn to_pbn(&self) -> &'static str {
+        match self {
+            Vulnerability::NONE => "None",
+            Vulnerability::NS => "NS",
+            Vulnerability::EW => "EW",
+            Vulnerability::BOTH => "All",
+        }
+    }
+
+    pub fn is_vulnerable(&self, direction: &BridgeDirection) -> bool {
+        match direction.side() {
+            Some(side) => self.is_side_vulnerable(&side),
+            None => false,
+        }
+    }
+
+    pub fn is_side_vulnerable(&self, side: &BridgeSide) -> bool {
+        match self {
+            Vulnerability::NONE => false,
+            Vulnerability::NS => *side == BridgeSide::NS,
+            Vulnerability::EW => *side == BridgeSide::EW,
+            Vulnerability::BOTH => true,
+        }
+    }
+}
+
+impl fmt::Display for Vulnerability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_pbn())
+    }
+}
+
+/// BridgeBoard is a French Deck Pack that sorts and validates the hands dealt as a part
+/// of a Bridge hand.
+///
+/// A board also carries its number, along with the dealer and vulnerability, which by default
+/// follow the standard 16 board rotation for that number.
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub struct BridgeBoard {
+    pack: Pack,
+    pub number: usize,
+    pub dealer: BridgeDirection,
+    pub vulnerability: Vulnerability,
+    pub south: Pile,
+    pub west: Pile,
+    pub north: Pile,
+    pub east: Pile,
+}
+
+impl BridgeBoard {
+    /// Parses a Portable Bridge Notation deal string and converts it into a
+    /// BridgeBoard struct.
+    ///
+    /// A hand given as `-` is unknown, and is left empty. Unlike `try_from_pbn_deal()`, hands
+    /// can hold any number of cards, so that endings and positions in the middle of the play
+    /// can be set up.
+    ///
+    /// **A deal that isn't well formed, or holds a card twice, silently gives a board with no
+    /// cards**, which can't be told apart from a board with every hand unknown. Use
+    /// `try_from_pbn_deal()` for deals read from files or typed in by users.
+    pub fn from_pbn_deal(deal: &str) -> BridgeBoard {
+        BridgeBoard::parse_pbn_deal(deal).unwrap_or_default()
+    }
+
+    /// Parses a Portable Bridge Notation deal string, returning None unless it is a seat, a
+    /// colon and four hands, each either `-` or four suits of cards separated by dots, with
+    /// thirteen cards in each known hand and no card held twice.
+    ///
+    /// # Usage:
+    /// ```
+    /// use cardpack::BridgeBoard;
+    ///
+    /// assert!(BridgeBoard::try_from_pbn_deal("N:AKQJ.AKQ.AKQ.AKQ - - -").is_some());
+    /// assert!(BridgeBoard::try_from_pbn_deal("N:AKQ JT9 876 5432").is_none());
+    /// assert!(BridgeBoard::try_from_pbn_deal("N:AKQ... - - -").is_none());
+    /// ```
+    pub fn try_from_pbn_deal(deal: &str) -> Option<BridgeBoard> {
+        BridgeBoard::parse_pbn_deal(deal).filter(BridgeBoard::is_consistent)
+    }
+
+    /// Parses the hands of a deal string, which can be any size, as long as no card is held
+    /// twice.
+    fn parse_pbn_deal(deal: &str) -> Option<BridgeBoard> {
+        let (direction, pbn) = BridgeBoard::split_on_direction(deal)?;
+        let hands: Vec<&str> = pbn.split_whitespace().collect();
+        if hands.len() != 4 {
+            return None;
+        }
+
+        let mut board = BridgeBoard::default();
+        let mut held = Pile::default();
+        let mut seat = direction;
+        for hand in hands {
+            let pile = board.to_pile(hand)?;
+            if pile.values().any(|card| held.contains(card)) {
+                return None;
+            }
+            held.append(&pile);
+            board.fold_in(&seat, pile);
+            seat = seat.lho();
+        }
+        Some(board)
+    }
+
+    fn fold_in(&mut self, direction: &BridgeDirection, hand: Pile) {
+        match direction {
+            BridgeDirection::S => self.south = hand.sort(),
+            BridgeDirection::W => self.west = hand.sort(),
+            BridgeDirection::N => self.north = hand.sort(),
+            BridgeDirection::E => self.east = hand.sort(),
+            BridgeDirection::UNKNOWN => self.east = hand,
+        }
+    }
+
+    pub fn deal() -> BridgeBoard {
+        let mut board = BridgeBoard::default();
+        let mut cards = board.pack.cards().shuffle();
+        board.south = cards.draw(13).unwrap().sort();
+        board.west = cards.draw(13).unwrap().sort();
+        board.north = cards.draw(13).unwrap().sort();
+        board.east = cards.draw(13).unwrap().sort();
+        board
+    }
+
+    /// Deals a board, setting the dealer and vulnerability from the board number.
+    pub fn deal_board(number: usize) -> BridgeBoard {
+        let mut board = BridgeBoard::deal();
+        board.set_number(number);
+        board
+    }
+
+    /// Sets the board number, along with the dealer and vulnerability that go with it under
+    /// the standard rotation.
+    pub fn set_number(&mut self, number: usize) {
+        self.number = number;
+        self.dealer = BridgeDirection::dealer_for_board(number);
+        self.vulnerability = Vulnerability::for_board(number);
+    }
+
+    pub fn get_hand(&self, direction: &BridgeDirection) -> Option<&Pile> {
+        match direction {
+            BridgeDirection::S => Some(&self.south),
+            BridgeDirection::W => Some(&self.west),
+            BridgeDirection::N => Some(&self.north),
+            BridgeDirection::E => Some(&self.east),
+            BridgeDirection::UNKNOWN => None,
+        }
+    }
+
+    pub fn get_hand_mut(&mut self, direction: &BridgeDirection) -> Option<&mut Pile> {
+        match direction {
+            BridgeDirection::S => Some(&mut self.south),
+            BridgeDirection::W => Some(&mut self.west),
+            BridgeDirection::N => Some(&mut self.north),
+            BridgeDirection::E => Some(&mut self.east),
+            BridgeDirection::UNKNOWN => None,
+        }
+    }
+
+    pub fn is_vulnerable(&self, direction: &BridgeDirection) -> bool {
+        self.vulnerability.is_vulnerable(direction)
+    }
+
+    /// Solves the board double dummy, returning the tricks each declarer can take in each
+    /// strain, or None if the hands aren't a deal that can be played out.
+    pub fn double_dummy(&self) -> Option<TrickTable> {
+        DoubleDummy::trick_table(self)
+    }
+
+    /// Returns the two hands held by a partnership.
+    pub fn partnership(&self, side: &BridgeSide) -> Vec<&Pile> {
+        side.seats()
+            .iter()
+            .filter_map(|d| self.get_hand(d))
+            .collect()
+    }
+
+    /// Combined high card points for a partnership.
+    pub fn partnership_hcp(&self, side: &BridgeSide) -> usize {
+        self.partnership(side).iter().map(|h| h.hcp()).sum()
+    }
+
+    /// Combined length in each suit for a partnership, in `BRIDGE_SUITS` order.
+    pub fn partnership_suit_lengths(&self, side: &BridgeSide) -> [usize; 4] {
+        let mut lengths = [0; 4];
+        for hand in self.partnership(side) {
+            for (i, length) in hand.suit_lengths().iter().enumerate() {
+                lengths[i] += length;
+            }
+        }
+        lengths
+    }
+
+    /// Number of tricks the partnership is expected to take according to the losing trick
+    /// count: 24 less the losers in both hands.
+    pub fn partnership_ltc_tricks(&self, side: &BridgeSide) -> usize {
+        let losers: usize = self
+            .partnership(side)
+            .iter()
+            .map(|h| h.losing_trick_count())
+            .sum();
+        24usize.saturating_sub(losers)
+    }
+
+    pub fn demo(&self) {
+        println!("S: {}", self.south.sort().by_symbol_index());
+        println!("W: {}", self.west.sort().by_symbol_index());
+        println!("N: {}", self.north.sort().by_symbol_index());
+        println!("E: {}", self.east.sort().by_symbol_index());
+    }
+
+    pub fn get_pack(&self) -> &Pack {
+        &self.pack
+    }
+
+    /// Returns true if the seat's hand is known. Unknown hands are empty.
+    pub fn is_known(&self, direction: &BridgeDirection) -> bool {
+        self.get_hand(direction)
+            .map_or(false, |hand| !hand.is_empty())
+    }
+
+    /// Returns the seats whose hands are unknown, starting with North.
+    pub fn unknown_seats(&self) -> Vec<BridgeDirection> {
+        BridgeDirection::seats()
+            .into_iter()
+            .filter(|direction| !self.is_known(direction))
+            .collect()
+    }
+
+    /// Returns the cards in the pack that aren't in any of the known hands.
+    pub fn unseen(&self) -> Pile {
+        let hands = [&self.north, &self.east, &self.south, &self.west];
+        let cards: Vec<Card> = self
+            .pack
+            .cards()
+            .values()
+            .filter(|card| !hands.iter().any(|hand| hand.contains(card)))
+            .cloned()
+            .collect();
+        Pile::new_from_vector(cards)
+    }
+
+    /// Returns true if the known hands could be part of a deal: each has thirteen cards
+    /// and no card is held twice.
+    pub fn is_consistent(&self) -> bool {
+        let hands = [&self.north, &self.east, &self.south, &self.west];
+        let known: Vec<&&Pile> = hands.iter().filter(|hand| !hand.is_empty()).collect();
+        let held: usize = known.iter().map(|hand| hand.len()).sum();
+        known.iter().all(|hand| hand.len() == 13) && held + self.unseen().len() == 52
+    }
+
+    /// Returns a copy of the board with the unseen cards dealt at random to the unknown
+    /// hands, or None if the known hands aren't consistent.
+    ///
+    /// # Usage:
+    /// ```
+    /// use cardpack::{BridgeBoard, BridgeDirection};
+    ///
+    /// let board = BridgeBoard::from_pbn_deal("N:AKQJ.AKQ.AKQ.AKQ - - -");
+    ///
+    /// assert_eq!(39, board.unseen().len());
+    /// assert_eq!("N:AKQJ.AKQ.AKQ.AKQ - - -", board.to_pbn_deal_from(&BridgeDirection::N));
+    ///
+    /// let complete = board.complete().unwrap();
+    ///
+    /// assert!(complete.is_valid());
+    /// assert_eq!(board.north, complete.north);
+    /// ```
+    pub fn complete(&self) -> Option<BridgeBoard> {
+        if !self.is_consistent() {
+            return None;
+        }
+        let mut board = self.clone();
+        let mut unseen = self.unseen().shuffle();
+        for direction in self.unknown_seats().iter() {
+            *board.get_hand_mut(direction)? = unseen.draw(13)?.sort();
+        }
+        Some(board)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        let piles = &[
+            self.south.clone(),
+            self.west.clone(),
+            self.north.clone(),
+            self.east.clone(),
+        ];
+        let pile = Pile::pile_on(piles.to_vec());
+        self.pack.is_complete(&[pile])
+    }
+
+    /// Returns a Portable Bridge Notation deal string from a Bridge Board, with unknown
+    /// hands shown as `-`.
+    pub fn to_pbn_deal(&self) -> String {
+        self.to_pbn_deal_from(&BridgeDirection::S)
+    }
+
+    /// Returns a Portable Bridge Notation deal string starting with the passed in seat.
+    pub fn to_pbn_deal_from(&self, first: &BridgeDirection) -> String {
+        let first = match first {
+            BridgeDirection::UNKNOWN => BridgeDirection::S,
+            _ => *first,
+        };
+        let hands: Vec<String> = first
+            .clockwise()
+            .iter()
+            .map(|direction| match self.get_hand(direction) {
+                Some(hand) => BridgeBoard::hand_to_pbn_deal_segment(hand),
+                None => "-".to_string(),
+            })
+            .collect();
+        format!("{}:{}", first, hands.join(" "))
+    }
+
+    fn hand_to_pbn_deal_segment(hand: &Pile) -> String {
+        if hand.is_empty() {
+            return "-".to_string();
+        }
+        let suits: Vec<String> = (0..BRIDGE_SUITS.len())
+            .map(|suit| holding_text(hand, suit))
+            .collect();
+        suits.join(".")
+    }
+
+    fn to_pile(&self, s: &str) -> Option<Pile> {
+        if s == "-" {
+            return Some(Pile::default());
+        }
+        let rawsuits: Vec<&str> = s.split('.').collect();
+        if rawsuits.len() != 4 {
+            return None;
+        }
+
+        let mut v: Vec<String> = Vec::new();
+        v.append(&mut BridgeBoard::splice_suit_in(rawsuits[0], 'S'));
+        v.append(&mut BridgeBoard::splice_suit_in(rawsuits[1], 'H'));
+        v.append(&mut BridgeBoard::splice_suit_in(rawsuits[2], 'D'));
+        v.append(&mut BridgeBoard::splice_suit_in(rawsuits[3], 'C'));
+
+        let mut pile = Pile::default();
+        for index in v.iter() {
+            let card = self.pack.cards().card_by_index(index.as_str())?;
+            if pile.contains(card) {
+                return None;
+            }
+            pile.add(card.clone());
+        }
+        Some(pile)
+    }
+
+    fn splice_suit_in(s: &str, suit: char) -> Vec<String> {
+        let mut v: Vec<String> = Vec::new();
+
+        for c in s.chars() {
+            v.push(format!("{}{}", c, suit));
+        }
+        v
+    }
+
+    fn split_on_direction(deal: &str) -> Option<(BridgeDirection, &str)> {
+        let deal = deal.trim();
+        let direction = BridgeDirection::to(deal.chars().next()?);
+        if direction == BridgeDirection::UNKNOWN {
+            return None;
+        }
+        let remainder = deal.get(1..)?.strip_prefix(':')?;
+
+        Some((direction, remainder))
+    }
+}
+
+impl Default for BridgeBoard {
+    fn default() -> Self {
+        BridgeBoard {
+            pack: Pack::french_deck(),
+            number: 1,
+            dealer: BridgeDirection::N,
+            vulnerability: Vulnerability::NONE,
+            south: Pile::default(),
+            west: Pile::default(),
+            north: Pile::default(),
+            east: Pile::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_tests {
+    use super::*;
+
+    const PBN_TEST_STRING: &str =
+        "S:Q42.Q52.AQT943.Q 97.AT93.652.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982";
+
+    #[test]
+    fn card_from_chars() {
+        let ace = Card::new(ACE, SPADES);
+        let ten = Card::new(TEN, CLUBS);
+
+        assert_eq!(Some(ace.clone()), super::card_from_chars('S', 'A'));
+        assert_eq!(Some(ace), super::card_from_chars('s', 'a'));
+        assert_eq!(Some(ten), super::card_from_chars('C', 'T'));
+        assert_eq!(None, super::card_from_chars('X', 'A'));
+        assert_eq!(None, super::card_from_chars('S', '1'));
+    }
+
+    #[test]
+    fn card_to_text() {
+        assert_eq!(
+            Some("SA".to_string()),
+            super::card_to_text(&Card::new(ACE, SPADES))
+        );
+        assert_eq!(
+            Some("CT".to_string()),
+            super::card_to_text(&Card::new(TEN, CLUBS))
+        );
+        assert_eq!(None, super::card_to_text(&Card::new(BIG_JOKER, TRUMP)));
+        for card in Pile::french_deck().values() {
+            let text = super::card_to_text(card).unwrap();
+            let mut chars = text.chars();
+            let (suit, rank) = (chars.next().unwrap(), chars.next().unwrap());
+            assert_eq!(card.index, format!("{}{}", rank, suit));
+        }
+    }
+
+    #[test]
+    fn holding_text() {
+        let deal = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+
+        assert_eq!("AQT943", super::holding_text(&deal.south, 2));
+        assert_eq!("Q", super::holding_text(&deal.south, 3));
+        assert_eq!("", super::holding_text(&Pile::default(), 0));
+    }
+
+    #[test]
+    fn from_pbn_deal() {
+        let deck = Pile::french_deck();
+        let south = deck.pile_by_index(&[
+            "QS", "4S", "2S", "QH", "5H", "2H", "AD", "QD", "TD", "9D", "4D", "3D", "QC",
+        ]);
+        let west = deck.pile_by_index(&[
+            "9S", "7S", "AH", "TH", "9H", "3H", "6D", "5D", "2D", "TC", "7C", "4C", "3C",
+        ]);
+        let north = deck.pile_by_index(&[
+            "AS", "JS", "TS", "8S", "5S", "JH", "7H", "6H", "KD", "JD", "AC", "6C", "5C",
+        ]);
+        let east = deck.pile_by_index(&[
+            "KS", "6S", "3S", "KH", "8H", "4H", "8D", "7D", "KC", "JC", "9C", "8C", "2C",
+        ]);
+
+        let deal = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+
+        assert_eq!(south.unwrap().by_index(), deal.south.by_index());
+        assert_eq!(west.unwrap().by_index(), deal.west.by_index());
+        assert_eq!(north.unwrap().by_index(), deal.north.by_index());
+        assert_eq!(east.unwrap().by_index(), deal.east.by_index());
+        assert!(deal.is_valid())
+    }
+
+    #[test]
+    fn try_from_pbn_deal__malformed() {
+        for deal in [
+            "garbage",
+            "",
+            "N:AKQ x y z",
+            "N:AKQ JT9 876 5432",
+            "N:AKQ... - -",
+            "N:AKQ... - - - -",
+            "Z:AKQ... - - -",
+            "N:AKX... - - -",
+            "N:AKQ... - - -",
+            "N:AKQJ.AKQ.AKQ.AKA - - -",
+            "N:AKQJ.AKQ.AKQ.AKQ AT98.JT9.JT9.JT9 - -",
+        ]
+        .iter()
+        {
+            assert!(BridgeBoard::try_from_pbn_deal(deal).is_none(), "{}", deal);
+        }
+        assert_eq!(
+            BridgeBoard::default(),
+            BridgeBoard::from_pbn_deal("N:AKA... - - -")
+        );
+        assert_eq!(
+            BridgeBoard::default(),
+            BridgeBoard::from_pbn_deal("N:A... A... - -")
+        );
+        assert_eq!(3, BridgeBoard::from_pbn_deal("N:AKQ... - - -").north.len());
+        assert_eq!(
+            BridgeBoard::default(),
+            BridgeBoard::from_pbn_deal("N:AKQ x y z")
+        );
+        assert_eq!(
+            Some(BridgeBoard::from_pbn_deal(PBN_TEST_STRING)),
+            BridgeBoard::try_from_pbn_deal(PBN_TEST_STRING)
+        );
+    }
+
+    #[test]
+    fn from_pbn_deal__unsorted() {
+        let unsorted = "S:4Q2.5Q2.Q94T3A.Q 79.AT93.562.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982";
+
+        let sorted = BridgeBoard::from_pbn_deal(unsorted).to_pbn_deal();
+
+        assert_eq!(PBN_TEST_STRING, sorted.as_str());
+    }
+
+    #[test]
+    fn from_pbn_deal__west() {
+        let deck = Pile::french_deck();
+        let pbn = "W:A94.K2.T876.QT53 Q75.AQJT976.9.42 KT62.3.AK2.AK986 J83.854.QJ543.J7";
+        let west = deck.pile_by_index(&[
+            "AS", "9S", "4S", "KH", "2H", "TD", "8D", "7D", "6D", "QC", "TC", "5C", "3C",
+        ]);
+
+        let deal = BridgeBoard::from_pbn_deal(pbn);
+
+        assert_eq!(west.unwrap().by_index(), deal.west.by_index());
+        assert!(deal.is_valid())
+    }
+
+    #[test]
+    fn from_to_pbn_deal() {
+        let bb = BridgeBoard::deal();
+
+        let pbn = bb.to_pbn_deal();
+        let from = BridgeBoard::from_pbn_deal(pbn.as_str()).to_pbn_deal();
+
+        assert_eq!(pbn, from)
+    }
+
+    #[test]
+    fn from_pbn_deal__unknown_hands() {
+        let pbn = "W:- Q75.AQJT976.9.42 - J83.854.QJ543.J7";
+
+        let board = BridgeBoard::from_pbn_deal(pbn);
+
+        assert!(board.west.is_empty());
+        assert!(board.east.is_empty());
+        assert_eq!(13, board.north.len());
+        assert_eq!(
+            vec![BridgeDirection::E, BridgeDirection::W],
+            board.unknown_seats()
+        );
+        assert!(board.is_known(&BridgeDirection::S));
+        assert!(!board.is_valid());
+        assert_eq!(pbn, board.to_pbn_deal_from(&BridgeDirection::W));
+        assert_eq!(
+            "S:J83.854.QJ543.J7 - Q75.AQJT976.9.42 -",
+            board.to_pbn_deal()
+        );
+    }
+
+    #[test]
+    fn unseen() {
+        let board = BridgeBoard::from_pbn_deal("N:AKQJ.AKQ.AKQ.AKQ - - -");
+
+        let unseen = board.unseen();
+
+        assert_eq!(39, unseen.len());
+        assert!(!unseen.contains(&Card::new(ACE, SPADES)));
+        assert!(unseen.contains(&Card::new(TEN, SPADES)));
+        assert_eq!(52, BridgeBoard::default().unseen().len());
+        assert!(BridgeBoard::deal().unseen().is_empty());
+    }
+
+    #[test]
+    fn is_consistent() {
+        let mut short = BridgeBoard::from_pbn_deal("N:AKQJ.AKQ.AKQ.AKQ - - -");
+        short.north.draw_first();
+        let mut twice = BridgeBoard::from_pbn_deal("N:AKQJ.AKQ.AKQ.AKQ - - -");
+        twice.south = twice.north.clone();
+
+        assert!(BridgeBoard::from_pbn_deal("N:AKQJ.AKQ.AKQ.AKQ - - -").is_consistent());
+        assert!(BridgeBoard::default().is_consistent());
+        assert!(BridgeBoard::deal().is_consistent());
+        assert!(!short.is_consistent());
+        assert!(!twice.is_consistent());
+    }
+
+    #[test]
+    fn complete() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+        let mut partial = board.clone();
+        partial.east = Pile::default();
+        partial.west = Pile::default();
+
+        let complete = partial.complete().unwrap();
+
+        assert!(complete.is_valid());
+        assert_eq!(board.north, complete.north);
+        assert_eq!(board.south, complete.south);
+        assert_eq!(
+            board.east.len() + board.west.len(),
+            complete.east.len() + complete.west.len()
+        );
+        assert_eq!(board, board.complete().unwrap());
+        assert!(BridgeBoard::default().complete().unwrap().is_valid());
+    }
+
+    #[test]
+    fn complete__inconsistent() {
+        let mut board = BridgeBoard::from_pbn_deal("N:AKQJ.AKQ.AKQ.AKQ - - -");
+        board.north.draw_first();
+
+        assert!(board.complete().is_none());
+    }
+
+    #[test]
+    fn is_valid() {
+        let deck = BridgeBoard::deal();
+
+        assert!(deck.is_valid())
+    }
+
+    #[test]
+    fn is_valid_ne() {
+        let mut deck = BridgeBoard::default();
+        let mut cards = deck.pack.cards().shuffle();
+        deck.south = cards.draw(13).unwrap();
+        deck.west = cards.draw(13).unwrap();
+        deck.north = cards.draw(13).unwrap();
+        deck.east = cards.draw(12).unwrap();
+
+        assert!(!deck.is_valid())
+    }
+
+    #[test]
+    fn splice_suit_in() {
+        let expected = vec!["QS".to_string(), "4S".to_string()];
+
+        let actual = BridgeBoard::splice_suit_in("Q4", 'S');
+
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn split_on_direction() {
+        let expected_remainder =
+            "Q42.Q52.AQT943.Q 97.AT93.652.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982";
+
+        let (char, remainder) = BridgeBoard::split_on_direction(PBN_TEST_STRING).unwrap();
+
+        assert_eq!(BridgeDirection::S, char);
+        assert_eq!(expected_remainder, remainder);
+        assert!(BridgeBoard::split_on_direction("X:AKQ").is_none());
+        assert!(BridgeBoard::split_on_direction("").is_none());
+    }
+
+    #[test]
+    fn to_pbn_deal() {
+        assert_eq!(
+            PBN_TEST_STRING.to_string(),
+            BridgeBoard::from_pbn_deal(PBN_TEST_STRING).to_pbn_deal()
+        )
+    }
+
+    #[test]
+    fn hand_to_pbn_deal_segment() {
+        let deal = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+        let hand = deal
+            .pack
+            .cards()
+            .pile_by_index(&[
+                "QS", "4S", "2S", "QH", "5H", "2H", "AD", "QD", "TD", "9D", "4D", "3D", "QC",
+            ])
+            .unwrap();
+        let expected = "Q42.Q52.AQT943.Q";
+
+        let actual = BridgeBoard::hand_to_pbn_deal_segment(&hand);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn hand_to_pbn_deal_segment__unbalanced() {
+        let all_spades = Pile::french_deck().draw(13).unwrap();
+        let expected = "AKQJT98765432...";
+
+        let actual = BridgeBoard::hand_to_pbn_deal_segment(&all_spades);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn to() {
+        assert_eq!(BridgeDirection::S, BridgeDirection::to('S'));
+        assert_eq!(BridgeDirection::S, BridgeDirection::to('s'));
+        assert_eq!(BridgeDirection::E, BridgeDirection::to('E'));
+        assert_eq!(BridgeDirection::E, BridgeDirection::to('e'));
+        assert_eq!(BridgeDirection::N, BridgeDirection::to('N'));
+        assert_eq!(BridgeDirection::N, BridgeDirection::to('n'));
+        assert_eq!(BridgeDirection::W, BridgeDirection::to('W'));
+        assert_eq!(BridgeDirection::W, BridgeDirection::to('w'));
+        assert_eq!(BridgeDirection::UNKNOWN, BridgeDirection::to(' '));
+    }
+
+    #[test]
+    fn lho() {
+        assert_eq!(BridgeDirection::W, BridgeDirection::S.lho());
+        assert_eq!(BridgeDirection::N, BridgeDirection::W.lho());
+        assert_eq!(BridgeDirection::E, BridgeDirection::N.lho());
+        assert_eq!(BridgeDirection::S, BridgeDirection::E.lho());
+        assert_eq!(BridgeDirection::UNKNOWN, BridgeDirection::UNKNOWN.lho());
+    }
+
+    #[test]
+    fn rho_partner() {
+        assert_eq!(BridgeDirection::E, BridgeDirection::S.rho());
+        assert_eq!(BridgeDirection::W, BridgeDirection::N.rho());
+        assert_eq!(BridgeDirection::N, BridgeDirection::S.partner());
+        assert_eq!(BridgeDirection::E, BridgeDirection::W.partner());
+        assert!(BridgeDirection::E.is_partner_of(&BridgeDirection::W));
+        assert!(!BridgeDirection::E.is_partner_of(&BridgeDirection::N));
+    }
+
+    #[test]
+    fn clockwise() {
+        assert_eq!(
+            vec![
+                BridgeDirection::W,
+                BridgeDirection::N,
+                BridgeDirection::E,
+                BridgeDirection::S
+            ],
+            BridgeDirection::W.clockwise()
+        );
+        assert!(BridgeDirection::UNKNOWN.clockwise().is_empty());
+    }
+
+    #[test]
+    fn side() {
+        assert_eq!(Some(BridgeSide::NS), BridgeDirection::S.side());
+        assert_eq!(Some(BridgeSide::EW), BridgeDirection::E.side());
+        assert_eq!(None, BridgeDirection::UNKNOWN.side());
+        assert_eq!(BridgeSide::EW, BridgeSide::NS.opponents());
+    }
+
+    #[test]
+    fn dealer_for_board() {
+        assert_eq!(BridgeDirection::N, BridgeDirection::dealer_for_board(1));
+        assert_eq!(BridgeDirection::E, BridgeDirection::dealer_for_board(2));
+        assert_eq!(BridgeDirection::W, BridgeDirection::dealer_for_board(16));
+        assert_eq!(BridgeDirection::N, BridgeDirection::dealer_for_board(17));
+        assert_eq!(BridgeDirection::W, BridgeDirection::dealer_for_board(0));
+    }
+
+    #[test]
+    fn vulnerability__for_board() {
+        let expected = [
+            Vulnerability::NONE,
+            Vulnerability::NS,
+            Vulnerability::EW,
+            Vulnerability::BOTH,
+            Vulnerability::NS,
+            Vulnerability::EW,
+            Vulnerability::BOTH,
+            Vulnerability::NONE,
+            Vulnerability::EW,
+            Vulnerability::BOTH,
+            Vulnerability::NONE,
+            Vulnerability::NS,
+            Vulnerability::BOTH,
+            Vulnerability::NONE,
+            Vulnerability::NS,
+            Vulnerability::EW,
+        ];
+
+        for (i, vulnerability) in expected.iter().enumerate() {
+            assert_eq!(*vulnerability, Vulnerability::for_board(i + 1));
+            assert_eq!(*vulnerability, Vulnerability::for_board(i + 17));
+        }
+        assert_eq!(Vulnerability::EW, Vulnerability::for_board(0));
+    }
+
+    #[test]
+    fn vulnerability__is_vulnerable() {
+        assert!(Vulnerability::NS.is_vulnerable(&BridgeDirection::S));
+        assert!(!Vulnerability::NS.is_vulnerable(&BridgeDirection::W));
+        assert!(Vulnerability::BOTH.is_vulnerable(&BridgeDirection::W));
+        assert!(!Vulnerability::BOTH.is_vulnerable(&BridgeDirection::UNKNOWN));
+    }
+
+    #[test]
+    fn vulnerability__pbn() {
+        assert_eq!(Some(Vulnerability::BOTH), Vulnerability::from_pbn("All"));
+        assert_eq!(Some(Vulnerability::NONE), Vulnerability::from_pbn("None"));
+        assert_eq!(None, Vulnerability::from_pbn("XX"));
+        assert_eq!("EW", Vulnerability::EW.to_pbn());
+    }
+
+    #[test]
+    fn deal_board() {
+        let board = BridgeBoard::deal_board(7);
+
+        assert_eq!(7, board.number);
+        assert_eq!(BridgeDirection::S, board.dealer);
+        assert_eq!(Vulnerability::BOTH, board.vulnerability);
+        assert!(board.is_vulnerable(&BridgeDirection::E));
+        assert!(board.is_valid());
+    }
+
+    #[test]
+    fn suit_index_rank_index() {
+        let card = Card::new(QUEEN, DIAMONDS);
+
+        assert_eq!(Some(2), suit_index(&card));
+        assert_eq!(Some(2), rank_index(&card));
+        assert_eq!(None, suit_index(&Card::new(BIG_JOKER, TRUMP)));
+    }
+
+    #[test]
+    fn partnership_totals() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+
+        assert_eq!(26, board.partnership_hcp(&BridgeSide::NS));
+        assert_eq!(14, board.partnership_hcp(&BridgeSide::EW));
+        assert_eq!(
+            [8, 6, 8, 4],
+            board.partnership_suit_lengths(&BridgeSide::NS)
+        );
+        assert_eq!(
+            [5, 7, 5, 9],
+            board.partnership_suit_lengths(&BridgeSide::EW)
+        );
+    }
+
+    #[test]
+    fn get_hand() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+
+        assert_eq!(&board.west, board.get_hand(&BridgeDirection::W).unwrap());
+        assert!(board.get_hand(&BridgeDirection::UNKNOWN).is_none());
+    }
+}
@@ -0,0 +1,585 @@
+/// The kinds of pairs movement a session can be run with.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MovementKind {
+    MITCHELL,
+    HOWELL,
+    SWISS,
+}
+
+/// One table for one round: the pairs sitting North-South and East-West and the boards they
+/// play. Rounds, tables, pairs and boards are all numbered from one.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Assignment {
+    pub round: usize,
+    pub table: usize,
+    pub ns: usize,
+    pub ew: usize,
+    pub boards: Vec<usize>,
+}
+
+impl Assignment {
+    pub fn has_pair(&self, pair: usize) -> bool {
+        self.ns == pair || self.ew == pair
+    }
+
+    /// The pair's opponents at the table, or None if the pair isn't playing here.
+    pub fn opponents(&self, pair: usize) -> Option<usize> {
+        if self.ns == pair {
+            Some(self.ew)
+        } else if self.ew == pair {
+            Some(self.ns)
+        } else {
+            None
+        }
+    }
+}
+
+/// A pairs movement: which pairs meet at which table in each round, which boards they play
+/// and who sits out.
+///
+/// - **Mitchell:** North-South pairs `1..=tables` stay put and East-West pairs move up a
+///   table each round while the boards move down. With an even number of tables the first
+///   and last tables share a relay of boards and a bye stand holds the set nobody plays,
+///   so no one meets the same boards twice.
+/// - **Howell:** every pair meets every other pair once. Each table plays its own set of
+///   boards in a round, and the sets move round the tables so that no pair meets the same
+///   boards twice. That can't be done with 4, 5 or 6 pairs, so there is no Howell movement
+///   for them.
+/// - **Swiss:** each round is paired from the standings so far, top down, without repeat
+///   meetings. Rounds are added with `pair_swiss_round` as the session goes on.
+///
+/// With an odd number of pairs the pair that would have met the missing pair sits out.
+///
+/// # Usage:
+/// ```
+/// use cardpack::Movement;
+///
+/// let movement = Movement::mitchell(14, 3).unwrap();
+///
+/// assert_eq!(7, movement.tables);
+/// assert_eq!(7, movement.rounds);
+/// assert_eq!(21, movement.board_count());
+/// assert_eq!(vec![1, 2, 3], movement.round(1)[0].boards);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Movement {
+    pub kind: MovementKind,
+    pub pairs: usize,
+    pub tables: usize,
+    pub rounds: usize,
+    pub boards_per_round: usize,
+    pub assignments: Vec<Assignment>,
+    /// The pair sitting out in each round that has one, as `(round, pair)`.
+    pub sit_outs: Vec<(usize, usize)>,
+}
+
+impl Movement {
+    /// A Mitchell movement for the number of pairs, playing one round per table.
+    pub fn mitchell(pairs: usize, boards_per_round: usize) -> Option<Movement> {
+        if pairs < 3 || boards_per_round == 0 {
+            return None;
+        }
+        let tables = (pairs + 1) / 2;
+        let mut movement = Movement::empty(
+            MovementKind::MITCHELL,
+            pairs,
+            tables,
+            tables,
+            boards_per_round,
+        );
+
+        // Where each table takes its boards from along the path the boards move down. With
+        // an even number of tables the last table shares the first table's boards, and the
+        // bye stand sits halfway.
+        let position = |table: usize| -> usize {
+            if tables % 2 == 1 || table < tables / 2 {
+                table
+            } else if table == tables - 1 {
+                0
+            } else {
+                table + 1
+            }
+        };
+
+        for round in 0..tables {
+            for table in 0..tables {
+                let ew = (table + tables - round) % tables;
+                let set = (position(table) + round) % tables;
+                movement.seat(round + 1, table + 1, table + 1, tables + ew + 1, set);
+            }
+        }
+        Some(movement)
+    }
+
+    /// A complete Howell movement for the number of pairs, in which every pair meets every
+    /// other. Returns None for 4, 5 or 6 pairs, and for fields too large to find a rotation
+    /// of the sets in which no pair plays a set twice.
+    pub fn howell(pairs: usize, boards_per_round: usize) -> Option<Movement> {
+        if pairs < 3 || boards_per_round == 0 {
+            return None;
+        }
+        let even = pairs + pairs % 2;
+        let rounds = even - 1;
+        let mut movement = Movement::empty(
+            MovementKind::HOWELL,
+            pairs,
+            even / 2,
+            rounds,
+            boards_per_round,
+        );
+
+        // Pairs are placed round a circle that turns one step each round, with the last pair
+        // staying at table one. Each table's set moves on one step each round too, from
+        // where it started, except with 9 or 10 pairs where no such rotation works.
+        let sets: Vec<Vec<usize>> = if even == 10 {
+            HOWELL_TEN.iter().map(|sets| sets.to_vec()).collect()
+        } else {
+            let offsets = howell_offsets(rounds, even / 2, pairs % 2 == 1)?;
+            (0..rounds)
+                .map(|round| offsets.iter().map(|o| (round + o) % rounds).collect())
+                .collect()
+        };
+        for (round, sets) in sets.iter().enumerate() {
+            let (ns, ew) = if round % 2 == 0 {
+                (even, round + 1)
+            } else {
+                (round + 1, even)
+            };
+            movement.seat(round + 1, 1, ns, ew, sets[0]);
+            for (table, set) in sets.iter().enumerate().skip(1) {
+                let ns = (round + table) % rounds + 1;
+                let ew = (round + rounds - table) % rounds + 1;
+                movement.seat(round + 1, table + 1, ns, ew, *set);
+            }
+        }
+        Some(movement)
+    }
+
+    /// A Swiss movement with no rounds paired yet.
+    pub fn swiss(pairs: usize, rounds: usize, boards_per_round: usize) -> Option<Movement> {
+        if pairs < 2 || rounds == 0 || rounds >= pairs + pairs % 2 || boards_per_round == 0 {
+            return None;
+        }
+        Some(Movement::empty(
+            MovementKind::SWISS,
+            pairs,
+            (pairs + 1) / 2,
+            rounds,
+            boards_per_round,
+        ))
+    }
+
+    /// Pairs the next Swiss round from the standings, best pair first. Each pair meets the
+    /// highest placed pair below it that it hasn't already played, and with an odd number
+    /// of pairs the lowest placed pair that hasn't yet sat out has the bye.
+    ///
+    /// Returns false if this isn't a Swiss movement, all its rounds have been paired, the
+    /// standings aren't a list of every pair, or there is no pairing without repeat
+    /// meetings.
+    pub fn pair_swiss_round(&mut self, standings: &[usize]) -> bool {
+        let round = self.paired_rounds() + 1;
+        let mut sorted = standings.to_vec();
+        sorted.sort_unstable();
+        if self.kind != MovementKind::SWISS
+            || round > self.rounds
+            || sorted != (1..=self.pairs).collect::<Vec<usize>>()
+        {
+            return false;
+        }
+
+        let mut order = standings.to_vec();
+        if self.pairs % 2 == 1 {
+            let bye = order
+                .iter()
+                .rposition(|pair| !self.sit_outs.iter().any(|(_, p)| p == pair));
+            match bye {
+                Some(index) => {
+                    let pair = order.remove(index);
+                    self.sit_outs.push((round, pair));
+                }
+                None => return false,
+            }
+        }
+
+        let mut pairings = Vec::new();
+        if !self.pair_off(&mut order, &mut pairings) {
+            if self.pairs % 2 == 1 {
+                self.sit_outs.pop();
+            }
+            return false;
+        }
+        for (table, (ns, ew)) in pairings.into_iter().enumerate() {
+            self.assignments.push(Assignment {
+                round,
+                table: table + 1,
+                ns,
+                ew,
+                boards: self.round_boards(round - 1),
+            });
+        }
+        true
+    }
+
+    /// The number of rounds that have pairings, which is all of them except for a Swiss
+    /// movement still in progress.
+    pub fn paired_rounds(&self) -> usize {
+        self.assignments.iter().map(|a| a.round).max().unwrap_or(0)
+    }
+
+    /// The total number of boards in play over the session.
+    pub fn board_count(&self) -> usize {
+        match self.kind {
+            MovementKind::MITCHELL => self.tables * self.boards_per_round,
+            _ => self.rounds * self.boards_per_round,
+        }
+    }
+
+    /// The tables in play in a round.
+    pub fn round(&self, round: usize) -> Vec<&Assignment> {
+        self.assignments
+            .iter()
+            .filter(|a| a.round == round)
+            .collect()
+    }
+
+    /// Where a pair plays in each round, leaving out any round it sits out.
+    pub fn pair_assignments(&self, pair: usize) -> Vec<&Assignment> {
+        self.assignments
+            .iter()
+            .filter(|a| a.has_pair(pair))
+            .collect()
+    }
+
+    /// The pair sitting out in a round, if there is one.
+    pub fn sit_out(&self, round: usize) -> Option<usize> {
+        self.sit_outs
+            .iter()
+            .find(|(r, _)| *r == round)
+            .map(|(_, pair)| *pair)
+    }
+
+    /// Whether the pairs meet over the board, with `ns` sitting North-South.
+    pub fn plays(&self, board: usize, ns: usize, ew: usize) -> bool {
+        self.assignments
+            .iter()
+            .any(|a| a.ns == ns && a.ew == ew && a.boards.contains(&board))
+    }
+
+    fn empty(
+        kind: MovementKind,
+        pairs: usize,
+        tables: usize,
+        rounds: usize,
+        boards_per_round: usize,
+    ) -> Movement {
+        Movement {
+            kind,
+            pairs,
+            tables,
+            rounds,
+            boards_per_round,
+            assignments: Vec::new(),
+            sit_outs: Vec::new(),
+        }
+    }
+
+    /// Seats the pairs at a table with a set of boards, or records a sit-out if one of the
+    /// pairs is the missing pair.
+    fn seat(&mut self, round: usize, table: usize, ns: usize, ew: usize, set: usize) {
+        if ew > self.pairs {
+            self.sit_outs.push((round, ns));
+        } else if ns > self.pairs {
+            self.sit_outs.push((round, ew));
+        } else {
+            let boards = self.round_boards(set);
+            self.assignments.push(Assignment {
+                round,
+                table,
+                ns,
+                ew,
+                boards,
+            });
+        }
+    }
+
+    fn round_boards(&self, set: usize) -> Vec<usize> {
+        (set * self.boards_per_round + 1..=(set + 1) * self.boards_per_round).collect()
+    }
+
+    fn has_met(&self, a: usize, b: usize) -> bool {
+        self.assignments.iter().any(|x| x.opponents(a) == Some(b))
+    }
+
+    /// Pairs off the remaining pairs in order, backtracking when the lower pairs can't be
+    /// paired without a repeat meeting.
+    fn pair_off(&self, order: &mut Vec<usize>, pairings: &mut Vec<(usize, usize)>) -> bool {
+        if order.is_empty() {
+            return true;
+        }
+        let first = order.remove(0);
+        for i in 0..order.len() {
+            let other = order[i];
+            if self.has_met(first, other) {
+                continue;
+            }
+            order.remove(i);
+            pairings.push((first, other));
+            if self.pair_off(order, pairings) {
+                return true;
+            }
+            pairings.pop();
+            order.insert(i, other);
+        }
+        order.insert(0, first);
+        false
+    }
+}
+
+/// How many placements `howell_offsets` tries before giving up.
+const HOWELL_SEARCH_LIMIT: usize = 1_000_000;
+
+/// The set each table plays in each round of a Howell movement for 9 or 10 pairs, where
+/// pairs are seated as for the other Howell movements.
+const HOWELL_TEN: [[usize; 5]; 9] = [
+    [0, 1, 2, 3, 4],
+    [2, 3, 0, 5, 1],
+    [8, 5, 7, 6, 4],
+    [4, 6, 7, 2, 3],
+    [1, 2, 7, 0, 4],
+    [3, 0, 7, 5, 8],
+    [5, 8, 2, 6, 4],
+    [6, 8, 5, 3, 1],
+    [7, 1, 6, 0, 8],
+];
+
+/// Finds the set each table of a Howell movement starts with, so that every table has its
+/// own set in each round and no pair plays a set twice, or returns None if there is none.
+///
+/// In round `r` table `t` holds the pairs `r + t` and `r - t` round the circle and plays set
+/// `r + offsets[t]`, so a pair meets the sets `offsets[t] - t` and `offsets[t] + t` on
+/// from its own number. The search looks for offsets that are all different and give every
+/// pair different sets, starting with the tables furthest round the circle. The stationary
+/// table is left out with an odd number of pairs, as the pair there sits out.
+fn howell_offsets(rounds: usize, tables: usize, sit_out: bool) -> Option<Vec<usize>> {
+    fn place(
+        order: &[usize],
+        rounds: usize,
+        offsets: &mut [usize],
+        used: &mut [bool],
+        met: &mut [bool],
+        budget: &mut usize,
+    ) -> bool {
+        let (table, rest) = match order.split_first() {
+            Some((table, rest)) => (*table, rest),
+            None => return true,
+        };
+        for offset in 0..rounds {
+            if *budget == 0 {
+                return false;
+            }
+            *budget -= 1;
+            let (a, b) = (
+                (offset + rounds - table) % rounds,
+                (offset + table) % rounds,
+            );
+            if used[offset] || met[a] || met[b] {
+                continue;
+            }
+            offsets[table] = offset;
+            used[offset] = true;
+            met[a] = true;
+            met[b] = true;
+            if place(rest, rounds, offsets, used, met, budget) {
+                return true;
+            }
+            used[offset] = false;
+            met[a] = false;
+            met[b] = false;
+        }
+        false
+    }
+
+    let mut offsets = vec![0; tables];
+    let mut used = vec![false; rounds];
+    let mut met = vec![false; rounds];
+    if !sit_out {
+        used[0] = true;
+        met[0] = true;
+    }
+    let order: Vec<usize> = (1..tables).rev().collect();
+    let mut budget = HOWELL_SEARCH_LIMIT;
+    if place(
+        &order,
+        rounds,
+        &mut offsets,
+        &mut used,
+        &mut met,
+        &mut budget,
+    ) {
+        Some(offsets)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_movement_tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Checks that no pair plays a board twice or meets the same pair twice.
+    fn assert_fair(movement: &Movement) {
+        for pair in 1..=movement.pairs {
+            let mut boards = HashSet::new();
+            let mut opponents = HashSet::new();
+            for assignment in movement.pair_assignments(pair) {
+                for board in assignment.boards.iter() {
+                    assert!(boards.insert(*board), "pair {} board {}", pair, board);
+                }
+                let opponent = assignment.opponents(pair).unwrap();
+                assert!(
+                    opponents.insert(opponent),
+                    "pair {} meets {}",
+                    pair,
+                    opponent
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mitchell__odd_tables() {
+        let movement = Movement::mitchell(10, 2).unwrap();
+
+        assert_eq!(5, movement.tables);
+        assert_eq!(5, movement.rounds);
+        assert_eq!(25, movement.assignments.len());
+        assert!(movement.sit_outs.is_empty());
+        assert_eq!(5, movement.pair_assignments(1).len());
+        assert!(movement.pair_assignments(1).iter().all(|a| a.ns == 1));
+        assert_fair(&movement);
+    }
+
+    #[test]
+    fn mitchell__even_tables_relay() {
+        for pairs in [4, 8, 12, 16].iter() {
+            let movement = Movement::mitchell(*pairs, 2).unwrap();
+            assert_fair(&movement);
+        }
+
+        let movement = Movement::mitchell(8, 2).unwrap();
+        let round = movement.round(1);
+
+        // The first and last tables share boards, and one set is on the bye stand.
+        assert_eq!(round[0].boards, round[3].boards);
+        assert_eq!(8, movement.board_count());
+    }
+
+    #[test]
+    fn mitchell__sit_out() {
+        let movement = Movement::mitchell(9, 3).unwrap();
+
+        assert_eq!(5, movement.tables);
+        assert_eq!(20, movement.assignments.len());
+        assert_eq!(5, movement.sit_outs.len());
+        assert!((1..=5).all(|round| movement.sit_out(round).is_some()));
+        assert_fair(&movement);
+    }
+
+    #[test]
+    fn howell() {
+        let movement = Movement::howell(8, 3).unwrap();
+
+        assert_eq!(4, movement.tables);
+        assert_eq!(7, movement.rounds);
+        assert_eq!(21, movement.board_count());
+        assert!(movement.sit_outs.is_empty());
+        for pair in 1..=8 {
+            assert_eq!(7, movement.pair_assignments(pair).len());
+        }
+        assert_fair(&movement);
+    }
+
+    #[test]
+    fn howell__own_boards() {
+        for pairs in (3..=24).filter(|pairs| ![4, 5, 6].contains(pairs)) {
+            let movement = Movement::howell(pairs, 2).unwrap();
+            for round in 1..=movement.rounds {
+                let sets: HashSet<Vec<usize>> = movement
+                    .round(round)
+                    .iter()
+                    .map(|a| a.boards.clone())
+                    .collect();
+                assert_eq!(movement.round(round).len(), sets.len(), "{} pairs", pairs);
+            }
+            assert_fair(&movement);
+        }
+    }
+
+    #[test]
+    fn howell__sit_out() {
+        let movement = Movement::howell(7, 2).unwrap();
+
+        assert_eq!(7, movement.rounds);
+        assert_eq!(7, movement.sit_outs.len());
+        for pair in 1..=7 {
+            assert_eq!(6, movement.pair_assignments(pair).len());
+            assert_eq!(1, movement.sit_outs.iter().filter(|s| s.1 == pair).count());
+        }
+        assert_fair(&movement);
+    }
+
+    #[test]
+    fn new__too_few() {
+        assert!(Movement::mitchell(2, 2).is_none());
+        assert!(Movement::howell(8, 0).is_none());
+        assert!(Movement::howell(4, 2).is_none());
+        assert!(Movement::howell(5, 2).is_none());
+        assert!(Movement::howell(6, 2).is_none());
+        assert!(Movement::swiss(6, 6, 2).is_none());
+    }
+
+    #[test]
+    fn swiss() {
+        let mut movement = Movement::swiss(6, 3, 4).unwrap();
+
+        assert!(movement.pair_swiss_round(&[1, 2, 3, 4, 5, 6]));
+        assert!(movement.plays(1, 1, 2));
+        assert!(movement.pair_swiss_round(&[1, 3, 2, 5, 4, 6]));
+        assert!(movement.plays(5, 1, 3));
+        assert!(movement.plays(5, 2, 5));
+        assert!(movement.plays(5, 4, 6));
+
+        // 1 has played 2 and 3, and 5 and 6 met in the first round.
+        assert!(movement.pair_swiss_round(&[1, 2, 3, 4, 5, 6]));
+        assert!(movement.plays(9, 1, 4));
+        assert!(movement.plays(9, 2, 6));
+        assert!(movement.plays(9, 3, 5));
+
+        assert!(!movement.pair_swiss_round(&[1, 2, 3, 4, 5, 6]));
+        assert_fair(&movement);
+    }
+
+    #[test]
+    fn swiss__bye() {
+        let mut movement = Movement::swiss(5, 2, 2).unwrap();
+
+        assert!(movement.pair_swiss_round(&[1, 2, 3, 4, 5]));
+        assert!(movement.pair_swiss_round(&[5, 4, 3, 2, 1]));
+
+        assert_eq!(vec![(1, 5), (2, 1)], movement.sit_outs);
+        assert!(!movement.pair_swiss_round(&[1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn pair_swiss_round__invalid() {
+        let mut swiss = Movement::swiss(4, 2, 2).unwrap();
+        let mut mitchell = Movement::mitchell(8, 2).unwrap();
+
+        assert!(!swiss.pair_swiss_round(&[1, 2, 3]));
+        assert!(!swiss.pair_swiss_round(&[1, 2, 3, 3]));
+        assert!(!mitchell.pair_swiss_round(&[1, 2, 3, 4, 5, 6, 7, 8]));
+    }
+}
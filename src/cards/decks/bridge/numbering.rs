@@ -0,0 +1,204 @@
+use crate::cards::card::Card;
+use crate::cards::decks::bridge::{
+    rank_index, suit_index, BridgeBoard, BridgeDirection, BRIDGE_RANKS, BRIDGE_SUITS,
+};
+use crate::cards::pile::Pile;
+
+/// The number of different Bridge deals: 52! / (13!)^4.
+pub const DEAL_COUNT: u128 = 53_644_737_765_488_792_839_237_440_000;
+
+/// Deal numbers and the 13 byte binary form of a deal.
+///
+/// Deal numbers follow Richard Pavlicek's scheme: the cards are taken from the ace of spades
+/// down to the deuce of clubs, and each one narrows the range of numbers by the share of the
+/// remaining deals in which it is held by North, East, South or West. Every number from zero
+/// up to `DEAL_COUNT` is a different deal, and the largest fits in 96 bits.
+///
+/// The binary form stores the seat holding each card in two bits, in the same card order,
+/// so that four cards fit in each byte.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeBoard, DEAL_COUNT};
+///
+/// let board = BridgeBoard::deal();
+/// let number = board.deal_number().unwrap();
+///
+/// assert!(number < DEAL_COUNT);
+/// assert_eq!(board, BridgeBoard::from_deal_number(number).unwrap());
+/// assert_eq!(board, BridgeBoard::from_bytes(&board.to_bytes().unwrap()).unwrap());
+/// ```
+impl BridgeBoard {
+    /// Returns the deal's number, or None if the board isn't a complete deal.
+    pub fn deal_number(&self) -> Option<u128> {
+        let owners = self.owners()?;
+        let mut counts = [13u128; 4];
+        let mut deals = DEAL_COUNT;
+        let mut number = 0;
+
+        for (card, owner) in owners.iter().enumerate() {
+            let remaining = (52 - card) as u128;
+            for count in counts.iter().take(*owner) {
+                number += deals * count / remaining;
+            }
+            deals = deals * counts[*owner] / remaining;
+            counts[*owner] -= 1;
+        }
+        Some(number)
+    }
+
+    /// Returns the deal for a number, or None if the number is `DEAL_COUNT` or larger.
+    pub fn from_deal_number(number: u128) -> Option<BridgeBoard> {
+        if number >= DEAL_COUNT {
+            return None;
+        }
+        let mut owners = [0; 52];
+        let mut counts = [13u128; 4];
+        let mut deals = DEAL_COUNT;
+        let mut number = number;
+
+        for (card, owner) in owners.iter_mut().enumerate() {
+            let remaining = (52 - card) as u128;
+            for (seat, count) in counts.iter_mut().enumerate() {
+                let share = deals * *count / remaining;
+                if number < share {
+                    *owner = seat;
+                    deals = share;
+                    *count -= 1;
+                    break;
+                }
+                number -= share;
+            }
+        }
+        Some(BridgeBoard::from_owners(&owners))
+    }
+
+    /// Returns the deal packed into 13 bytes, or None if the board isn't a complete deal.
+    pub fn to_bytes(&self) -> Option<[u8; 13]> {
+        let owners = self.owners()?;
+        let mut bytes = [0; 13];
+        for (card, owner) in owners.iter().enumerate() {
+            bytes[card / 4] |= (*owner as u8) << (6 - 2 * (card % 4));
+        }
+        Some(bytes)
+    }
+
+    /// Unpacks a deal from its 13 byte form, returning None unless each seat holds thirteen
+    /// cards.
+    pub fn from_bytes(bytes: &[u8; 13]) -> Option<BridgeBoard> {
+        let mut owners = [0; 52];
+        let mut counts = [0; 4];
+        for (card, owner) in owners.iter_mut().enumerate() {
+            *owner = ((bytes[card / 4] >> (6 - 2 * (card % 4))) & 0b11) as usize;
+            counts[*owner] += 1;
+        }
+        if counts != [13; 4] {
+            return None;
+        }
+        Some(BridgeBoard::from_owners(&owners))
+    }
+
+    /// The seat holding each card, from the ace of spades down to the deuce of clubs.
+    fn owners(&self) -> Option<[usize; 52]> {
+        let mut owners = [4; 52];
+        for (seat, direction) in BridgeDirection::seats().iter().enumerate() {
+            let hand = self.get_hand(direction)?;
+            if hand.len() != 13 {
+                return None;
+            }
+            for card in hand.values() {
+                let index = suit_index(card)? * 13 + rank_index(card)?;
+                owners[index] = seat;
+            }
+        }
+        if owners.contains(&4) {
+            return None;
+        }
+        Some(owners)
+    }
+
+    fn from_owners(owners: &[usize; 52]) -> BridgeBoard {
+        let mut board = BridgeBoard::default();
+        for (seat, direction) in BridgeDirection::seats().iter().enumerate() {
+            let cards: Vec<Card> = owners
+                .iter()
+                .enumerate()
+                .filter(|(_, owner)| **owner == seat)
+                .map(|(i, _)| Card::new(BRIDGE_RANKS[i % 13], BRIDGE_SUITS[i / 13]))
+                .collect();
+            if let Some(hand) = board.get_hand_mut(direction) {
+                *hand = Pile::new_from_vector(cards).sort();
+            }
+        }
+        board
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_numbering_tests {
+    use super::*;
+
+    const PBN_TEST_STRING: &str =
+        "S:Q42.Q52.AQT943.Q 97.AT93.652.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982";
+
+    #[test]
+    fn deal_count__fits_in_96_bits() {
+        assert_eq!(96, 128 - (DEAL_COUNT - 1).leading_zeros());
+    }
+
+    #[test]
+    fn from_deal_number__first_and_last() {
+        let first = BridgeBoard::from_deal_number(0).unwrap();
+        let last = BridgeBoard::from_deal_number(DEAL_COUNT - 1).unwrap();
+
+        assert_eq!(
+            "S:..AKQJT98765432. ...AKQJT98765432 AKQJT98765432... .AKQJT98765432..",
+            first.to_pbn_deal()
+        );
+        assert_eq!(last.north, first.west);
+        assert_eq!(Some(0), first.deal_number());
+        assert_eq!(Some(DEAL_COUNT - 1), last.deal_number());
+        assert!(BridgeBoard::from_deal_number(DEAL_COUNT).is_none());
+    }
+
+    #[test]
+    fn deal_number__round_trip() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+        let number = board.deal_number().unwrap();
+
+        assert_eq!(board, BridgeBoard::from_deal_number(number).unwrap());
+
+        for _ in 0..20 {
+            let board = BridgeBoard::deal();
+            let number = board.deal_number().unwrap();
+            assert_eq!(board, BridgeBoard::from_deal_number(number).unwrap());
+        }
+    }
+
+    #[test]
+    fn deal_number__incomplete() {
+        let mut board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+        board.north.draw_first();
+
+        assert!(board.deal_number().is_none());
+        assert!(board.to_bytes().is_none());
+        assert!(BridgeBoard::default().deal_number().is_none());
+    }
+
+    #[test]
+    fn to_bytes() {
+        let board = BridgeBoard::from_deal_number(0).unwrap();
+
+        let bytes = board.to_bytes().unwrap();
+
+        assert_eq!(0, bytes[0]);
+        assert_eq!(0b1111_1111, bytes[12]);
+        assert_eq!(board, BridgeBoard::from_bytes(&bytes).unwrap());
+    }
+
+    #[test]
+    fn from_bytes__invalid() {
+        assert!(BridgeBoard::from_bytes(&[0; 13]).is_none());
+    }
+}
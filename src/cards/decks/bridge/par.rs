@@ -0,0 +1,364 @@
+use std::fmt;
+
+use crate::cards::decks::bridge::contract::{Contract, Doubling, Strain};
+use crate::cards::decks::bridge::dds::TrickTable;
+use crate::cards::decks::bridge::{BridgeBoard, BridgeDirection, BridgeSide, Vulnerability};
+
+/// The strains in the order they rank in the auction.
+const AUCTION_STRAINS: [Strain; 5] = [Strain::C, Strain::D, Strain::H, Strain::S, Strain::NT];
+const BIDS: usize = 35;
+
+/// One of the contracts that reaches par, and the number of tricks declarer takes in it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParContract {
+    pub contract: Contract,
+    pub tricks: usize,
+}
+
+/// The par result of a deal: the score North-South get when both sides bid as well as
+/// they can given the double dummy results, and every contract that scores it.
+///
+/// Making contracts are played undoubled and sacrifices doubled. When both sides can make
+/// the same contract, the dealer's side gets to bid it first.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeDirection, Par, Strain, TrickTable, Vulnerability};
+///
+/// let mut table = TrickTable::default();
+/// table.set(&Strain::S, &BridgeDirection::N, 10);
+/// table.set(&Strain::S, &BridgeDirection::S, 10);
+///
+/// let par = Par::new(&table, &Vulnerability::NS, &BridgeDirection::N);
+///
+/// assert_eq!(620, par.score);
+/// assert_eq!("4S= by NS, +620", par.to_string());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Par {
+    /// The par score, from North-South's point of view.
+    pub score: isize,
+    pub contracts: Vec<ParContract>,
+}
+
+impl Par {
+    pub fn new(table: &TrickTable, vulnerability: &Vulnerability, dealer: &BridgeDirection) -> Par {
+        let solver = ParSolver::new(table, vulnerability);
+        let dealer_side = dealer.side().unwrap_or(BridgeSide::NS);
+        let score = solver.start(dealer_side);
+
+        let mut contracts: Vec<ParContract> = Vec::new();
+        for bid in 0..BIDS {
+            for side in [BridgeSide::NS, BridgeSide::EW].iter() {
+                let value = solver.value(bid, side);
+                if value != score || !solver.stands(bid, side) {
+                    continue;
+                }
+                // Only the lowest level in each strain, since bidding higher gains nothing.
+                let (level, strain) = bid_contract(bid);
+                let lower = contracts.iter().any(|c| {
+                    c.contract.strain == strain
+                        && c.contract.declarer.side().as_ref() == Some(side)
+                        && c.contract.level < level
+                });
+                if lower {
+                    continue;
+                }
+                for declarer in side.seats() {
+                    let contract = solver.contract(bid, side, &declarer);
+                    let tricks = table.get(&strain, &declarer);
+                    if solver.signed(side, contract.score(tricks, solver.vulnerable(side))) == score
+                    {
+                        contracts.push(ParContract { contract, tricks });
+                    }
+                }
+            }
+        }
+
+        Par { score, contracts }
+    }
+}
+
+/// Displays the par contracts and score, such as `4S= by NS, +620`.
+impl fmt::Display for Par {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.contracts.is_empty() {
+            return write!(f, "Passed out, 0");
+        }
+
+        let mut parts: Vec<String> = Vec::new();
+        let mut shown: Vec<&ParContract> = Vec::new();
+        for par in self.contracts.iter() {
+            if shown.iter().any(|s| same_contract(s, par)) {
+                continue;
+            }
+            shown.push(par);
+            let declarers: String = self
+                .contracts
+                .iter()
+                .filter(|p| same_contract(p, par))
+                .map(|p| p.contract.declarer.to_char())
+                .collect();
+            let result = par.tricks as isize - par.contract.tricks_needed() as isize;
+            let result = match result {
+                0 => "=".to_string(),
+                _ => format!("{:+}", result),
+            };
+            parts.push(format!(
+                "{}{} by {}",
+                par.contract.to_pbn(),
+                result,
+                declarers
+            ));
+        }
+        write!(f, "{}, {:+}", parts.join(", "), self.score)
+    }
+}
+
+impl BridgeBoard {
+    /// Returns the par result for the board from its double dummy trick table, using the
+    /// board's dealer and vulnerability.
+    pub fn par(&self, table: &TrickTable) -> Par {
+        Par::new(table, &self.vulnerability, &self.dealer)
+    }
+}
+
+fn same_contract(a: &ParContract, b: &ParContract) -> bool {
+    a.contract.to_pbn() == b.contract.to_pbn()
+        && a.tricks == b.tricks
+        && a.contract.declarer.side() == b.contract.declarer.side()
+}
+
+fn side_index(side: &BridgeSide) -> usize {
+    match side {
+        BridgeSide::NS => 0,
+        BridgeSide::EW => 1,
+    }
+}
+
+fn bid_contract(bid: usize) -> (usize, Strain) {
+    (bid / 5 + 1, AUCTION_STRAINS[bid % 5])
+}
+
+/// Works out par as a game between the two sides, where each in turn can pass or bid any
+/// higher contract. The results are worked out from the top contract down, so that each
+/// position only needs to look at those above it.
+struct ParSolver<'a> {
+    table: &'a TrickTable,
+    vulnerability: &'a Vulnerability,
+    /// The result when a side holds the contract and the opponents are next to act. They
+    /// can pass, or bid higher themselves.
+    opponents_turn: [[isize; 2]; BIDS],
+}
+
+impl<'a> ParSolver<'a> {
+    fn new(table: &'a TrickTable, vulnerability: &'a Vulnerability) -> ParSolver<'a> {
+        let mut solver = ParSolver {
+            table,
+            vulnerability,
+            opponents_turn: [[0; 2]; BIDS],
+        };
+
+        for bid in (0..BIDS).rev() {
+            for holder in [BridgeSide::NS, BridgeSide::EW].iter() {
+                let opponents = holder.opponents();
+
+                // The opponents have passed: the holder can let the contract stand or bid
+                // higher.
+                let mut holders_turn = solver.value(bid, holder);
+                for higher in bid + 1..BIDS {
+                    let result = solver.opponents_turn(higher, holder);
+                    holders_turn = solver.better(holder, holders_turn, result);
+                }
+
+                let mut best = holders_turn;
+                for higher in bid + 1..BIDS {
+                    let result = solver.opponents_turn(higher, &opponents);
+                    best = solver.better(&opponents, best, result);
+                }
+                solver.opponents_turn[bid][side_index(holder)] = best;
+            }
+        }
+        solver
+    }
+
+    /// The result when the dealer's side is first to bid.
+    fn start(&self, dealer_side: BridgeSide) -> isize {
+        let other = dealer_side.opponents();
+        let mut after_pass = 0;
+        for bid in 0..BIDS {
+            after_pass = self.better(&other, after_pass, self.opponents_turn(bid, &other));
+        }
+        let mut best = after_pass;
+        for bid in 0..BIDS {
+            best = self.better(&dealer_side, best, self.opponents_turn(bid, &dealer_side));
+        }
+        best
+    }
+
+    fn opponents_turn(&self, bid: usize, holder: &BridgeSide) -> isize {
+        self.opponents_turn[bid][side_index(holder)]
+    }
+
+    /// Whether the opponents would let the side's contract stand: it has to outrank every
+    /// contract they can make that would score better for them.
+    fn stands(&self, bid: usize, side: &BridgeSide) -> bool {
+        let value = self.value(bid, side);
+        let opponents = side.opponents();
+        (bid + 1..BIDS).all(|higher| {
+            let declarer = self.best_declarer(higher, &opponents);
+            let contract = self.contract(higher, &opponents, &declarer);
+            contract.doubling == Doubling::DOUBLED
+                || self.better(&opponents, value, self.value(higher, &opponents)) == value
+        })
+    }
+
+    /// Picks the better result for the side, keeping the first when they are equal.
+    fn better(&self, side: &BridgeSide, current: isize, other: isize) -> isize {
+        match side {
+            BridgeSide::NS if other > current => other,
+            BridgeSide::EW if other < current => other,
+            _ => current,
+        }
+    }
+
+    /// North-South's score when the side plays the contract with its best declarer.
+    fn value(&self, bid: usize, side: &BridgeSide) -> isize {
+        let declarer = self.best_declarer(bid, side);
+        let contract = self.contract(bid, side, &declarer);
+        let (_, strain) = bid_contract(bid);
+        let tricks = self.table.get(&strain, &declarer);
+        self.signed(side, contract.score(tricks, self.vulnerable(side)))
+    }
+
+    fn best_declarer(&self, bid: usize, side: &BridgeSide) -> BridgeDirection {
+        let (_, strain) = bid_contract(bid);
+        let seats = side.seats();
+        if self.table.get(&strain, &seats[1]) > self.table.get(&strain, &seats[0]) {
+            seats[1]
+        } else {
+            seats[0]
+        }
+    }
+
+    /// The contract as the side would play it: undoubled if it makes, doubled if it doesn't.
+    fn contract(&self, bid: usize, side: &BridgeSide, declarer: &BridgeDirection) -> Contract {
+        let (level, strain) = bid_contract(bid);
+        let mut contract = Contract::new(level, strain, *declarer);
+        let best = self.best_declarer(bid, side);
+        if self.table.get(&strain, &best) < contract.tricks_needed() {
+            contract.doubling = Doubling::DOUBLED;
+        }
+        contract
+    }
+
+    fn vulnerable(&self, side: &BridgeSide) -> bool {
+        self.vulnerability.is_side_vulnerable(side)
+    }
+
+    fn signed(&self, side: &BridgeSide, score: isize) -> isize {
+        match side {
+            BridgeSide::NS => score,
+            BridgeSide::EW => -score,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_par_tests {
+    use super::*;
+
+    /// Builds a table from the tricks North-South take in spades, hearts, diamonds, clubs
+    /// and no trump, with East-West taking the rest.
+    fn table(ns: [usize; 5]) -> TrickTable {
+        let mut tricks = [[0; 4]; 5];
+        for (strain, ns_tricks) in ns.iter().enumerate() {
+            tricks[strain] = [*ns_tricks, 13 - ns_tricks, *ns_tricks, 13 - ns_tricks];
+        }
+        TrickTable::new(tricks)
+    }
+
+    #[test]
+    fn new__game() {
+        let par = Par::new(
+            &table([10, 7, 5, 6, 8]),
+            &Vulnerability::NONE,
+            &BridgeDirection::N,
+        );
+
+        assert_eq!(420, par.score);
+        assert_eq!(2, par.contracts.len());
+        assert_eq!(Strain::S, par.contracts[0].contract.strain);
+        assert_eq!("4S= by NS, +420", par.to_string());
+    }
+
+    #[test]
+    fn new__sacrifice() {
+        let par = Par::new(
+            &table([10, 7, 5, 6, 8]),
+            &Vulnerability::NS,
+            &BridgeDirection::N,
+        );
+
+        assert_eq!(500, par.score);
+        assert_eq!(Doubling::DOUBLED, par.contracts[0].contract.doubling);
+        assert_eq!("5DX-3 by EW, +500", par.to_string());
+    }
+
+    #[test]
+    fn new__overtricks() {
+        let par = Par::new(
+            &table([11, 7, 5, 6, 8]),
+            &Vulnerability::NONE,
+            &BridgeDirection::N,
+        );
+
+        assert_eq!(450, par.score);
+        assert_eq!("4S+1 by NS, +450", par.to_string());
+    }
+
+    #[test]
+    fn new__dealer_bids_first() {
+        let mut tricks = [[6; 4]; 5];
+        tricks[Strain::NT.index()] = [7; 4];
+        let table = TrickTable::new(tricks);
+
+        let north = Par::new(&table, &Vulnerability::NONE, &BridgeDirection::N);
+        let east = Par::new(&table, &Vulnerability::NONE, &BridgeDirection::E);
+
+        assert_eq!("1NT= by NS, +90", north.to_string());
+        assert_eq!("1NT= by EW, -90", east.to_string());
+    }
+
+    #[test]
+    fn new__single_declarer() {
+        let mut table = table([10, 7, 5, 6, 8]);
+        table.set(&Strain::S, &BridgeDirection::S, 9);
+
+        let par = Par::new(&table, &Vulnerability::NONE, &BridgeDirection::N);
+
+        assert_eq!("4S= by N, +420", par.to_string());
+    }
+
+    #[test]
+    fn new__passed_out() {
+        let table = TrickTable::new([[6; 4]; 5]);
+
+        let par = Par::new(&table, &Vulnerability::BOTH, &BridgeDirection::S);
+
+        assert_eq!(0, par.score);
+        assert!(par.contracts.is_empty());
+        assert_eq!("Passed out, 0", par.to_string());
+    }
+
+    #[test]
+    fn board__par() {
+        let mut board = BridgeBoard::default();
+        board.set_number(2);
+
+        let par = board.par(&table([10, 7, 5, 6, 8]));
+
+        assert_eq!(500, par.score);
+    }
+}
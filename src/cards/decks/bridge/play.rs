@@ -0,0 +1,401 @@
+use crate::cards::card::Card;
+use crate::cards::decks::bridge::contract::{Contract, Strain};
+use crate::cards::decks::bridge::dds::DoubleDummy;
+use crate::cards::decks::bridge::{
+    rank_index, suit_index, BridgeBoard, BridgeDirection, BridgeSide,
+};
+use crate::cards::pile::Pile;
+
+/// A single trick: the seat that led to it and the cards in the order they were played.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trick {
+    pub leader: BridgeDirection,
+    pub cards: Vec<Card>,
+}
+
+impl Trick {
+    pub fn new(leader: BridgeDirection) -> Trick {
+        Trick {
+            leader,
+            cards: Vec::new(),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.cards.len() == 4
+    }
+
+    /// Position in `BRIDGE_SUITS` of the suit that was led.
+    pub fn led_suit_index(&self) -> Option<usize> {
+        self.cards.first().and_then(suit_index)
+    }
+
+    /// Returns the seat that played the card at the passed in position of the trick.
+    pub fn player(&self, position: usize) -> BridgeDirection {
+        self.leader
+            .clockwise()
+            .get(position % 4)
+            .copied()
+            .unwrap_or(BridgeDirection::UNKNOWN)
+    }
+
+    /// Returns the seat whose card is winning the trick so far: the highest trump, or if no
+    /// trumps have been played the highest card of the suit led.
+    pub fn winner(&self, strain: &Strain) -> Option<BridgeDirection> {
+        let led = self.led_suit_index()?;
+        let trump = strain.suit_index();
+        let (position, _) = self.cards.iter().enumerate().max_by_key(|(_, card)| {
+            let suit = suit_index(card);
+            let rank = 13 - rank_index(card).unwrap_or(13);
+            if suit.is_some() && suit == trump {
+                rank + 100
+            } else if suit == Some(led) {
+                rank + 50
+            } else {
+                0
+            }
+        })?;
+        Some(self.player(position))
+    }
+}
+
+/// Plays out the cards of a BridgeBoard in a contract, one card at a time.
+///
+/// The opening lead is made by the player on declarer's left, after which dummy's hand is
+/// exposed. Every card is checked against the follow suit rule, and the winner of each
+/// trick leads to the next one. Declarer plays dummy's cards, but the play is tracked by
+/// seat, so `to_play()` returns the dummy when it is dummy's turn.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeBoard, BridgeDirection, BridgePlay, BridgeSide, Contract, Strain};
+///
+/// let board = BridgeBoard::from_pbn_deal(
+///     "N:AKQJ.AKQ.AKQ.AKQ T987.JT9.JT9.JT9 6543.876.876.876 2.5432.5432.5432",
+/// );
+/// let mut play = BridgePlay::new(&board, Contract::new(7, Strain::NT, BridgeDirection::N));
+///
+/// assert_eq!(BridgeDirection::E, play.to_play());
+/// assert!(play.dummy().is_none());
+///
+/// let lead = play.legal_plays().get(0).unwrap().clone();
+/// assert!(play.play(&lead));
+/// assert!(play.dummy().is_some());
+/// assert!(play.claim(13));
+/// ```
+#[derive(Clone, Debug)]
+pub struct BridgePlay {
+    contract: Contract,
+    hands: BridgeBoard,
+    tricks: Vec<Trick>,
+}
+
+impl BridgePlay {
+    pub fn new(board: &BridgeBoard, contract: Contract) -> BridgePlay {
+        BridgePlay {
+            contract,
+            hands: board.clone(),
+            tricks: Vec::new(),
+        }
+    }
+
+    pub fn contract(&self) -> &Contract {
+        &self.contract
+    }
+
+    /// Returns the cards a seat has left to play.
+    pub fn hand(&self, direction: &BridgeDirection) -> Option<&Pile> {
+        self.hands.get_hand(direction)
+    }
+
+    /// Returns the dummy's remaining cards, once the opening lead has been made.
+    pub fn dummy(&self) -> Option<&Pile> {
+        if self.tricks.is_empty() {
+            None
+        } else {
+            self.hand(&self.contract.dummy())
+        }
+    }
+
+    /// All of the tricks played so far, including the one in progress.
+    pub fn tricks(&self) -> &Vec<Trick> {
+        &self.tricks
+    }
+
+    /// Returns the trick in progress, if any cards have been played to it.
+    pub fn current_trick(&self) -> Option<&Trick> {
+        self.tricks.last().filter(|t| !t.is_complete())
+    }
+
+    /// The seat that led, or is about to lead, the current trick.
+    pub fn leader(&self) -> BridgeDirection {
+        match self.tricks.last() {
+            None => self.contract.opening_leader(),
+            Some(trick) if trick.is_complete() => self.trick_winner(trick),
+            Some(trick) => trick.leader,
+        }
+    }
+
+    pub fn to_play(&self) -> BridgeDirection {
+        match self.current_trick() {
+            Some(trick) => trick.player(trick.cards.len()),
+            None => self.leader(),
+        }
+    }
+
+    /// Returns the cards the player to play may play: any card when leading, otherwise the
+    /// cards of the suit led if they hold any.
+    pub fn legal_plays(&self) -> Pile {
+        let hand = match self.hand(&self.to_play()) {
+            Some(hand) => hand,
+            None => return Pile::default(),
+        };
+        let led = self.current_trick().and_then(|t| t.led_suit_index());
+        let following: Vec<Card> = hand
+            .values()
+            .filter(|c| led.is_some() && suit_index(c) == led)
+            .cloned()
+            .collect();
+
+        if following.is_empty() {
+            hand.clone()
+        } else {
+            Pile::new_from_vector(following)
+        }
+    }
+
+    pub fn is_legal(&self, card: &Card) -> bool {
+        self.legal_plays().contains(card)
+    }
+
+    /// Plays a card for the seat whose turn it is. Returns false, leaving the play unchanged,
+    /// if the card isn't a legal play.
+    pub fn play(&mut self, card: &Card) -> bool {
+        if !self.is_legal(card) {
+            return false;
+        }
+        let seat = self.to_play();
+        if self.current_trick().is_none() {
+            self.tricks.push(Trick::new(seat));
+        }
+        if let Some(hand) = self.hands.get_hand_mut(&seat) {
+            hand.remove_card(card);
+        }
+        if let Some(trick) = self.tricks.last_mut() {
+            trick.cards.push(card.clone());
+        }
+        true
+    }
+
+    /// Takes back the last card played, returning it to its player's hand.
+    pub fn undo(&mut self) -> Option<Card> {
+        let trick = self.tricks.last_mut()?;
+        let seat = trick.player(trick.cards.len().saturating_sub(1));
+        let card = trick.cards.pop()?;
+        if trick.cards.is_empty() {
+            self.tricks.pop();
+        }
+        if let Some(hand) = self.hands.get_hand_mut(&seat) {
+            hand.add(card.clone());
+            hand.sort_in_place();
+        }
+        Some(card)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.tricks_remaining() == 0
+    }
+
+    /// The number of tricks that are yet to be won, including the one in progress.
+    pub fn tricks_remaining(&self) -> usize {
+        let in_progress = match self.current_trick() {
+            Some(trick) => trick.cards.len(),
+            None => 0,
+        };
+        let cards: usize = BridgeDirection::seats()
+            .iter()
+            .filter_map(|d| self.hand(d))
+            .map(|h| h.len())
+            .sum();
+        (cards + in_progress) / 4
+    }
+
+    pub fn tricks_won(&self, side: &BridgeSide) -> usize {
+        self.tricks
+            .iter()
+            .filter(|t| t.is_complete())
+            .filter(|t| self.trick_winner(t).side().as_ref() == Some(side))
+            .count()
+    }
+
+    pub fn declarer_tricks(&self) -> usize {
+        match self.contract.declarer.side() {
+            Some(side) => self.tricks_won(&side),
+            None => 0,
+        }
+    }
+
+    pub fn defender_tricks(&self) -> usize {
+        match self.contract.declarer.side() {
+            Some(side) => self.tricks_won(&side.opponents()),
+            None => 0,
+        }
+    }
+
+    /// Checks a claim by declarer of the passed in number of the remaining tricks, including
+    /// the trick in progress. The claim holds if declarer can take that many tricks against
+    /// any defence, which is worked out with a double dummy search bounded by the claim.
+    pub fn claim(&self, tricks: usize) -> bool {
+        let mut solver = DoubleDummy::new(&self.hands, self.contract.strain);
+        let (leader, played) = match self.current_trick() {
+            Some(trick) => (trick.leader, &trick.cards[..]),
+            None => (self.leader(), &[][..]),
+        };
+        solver
+            .can_take(&leader, played, &self.contract.declarer, tricks)
+            .unwrap_or(false)
+    }
+
+    fn trick_winner(&self, trick: &Trick) -> BridgeDirection {
+        trick
+            .winner(&self.contract.strain)
+            .unwrap_or(BridgeDirection::UNKNOWN)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_play_tests {
+    use super::*;
+    use crate::cards::rank::*;
+    use crate::cards::suit::*;
+
+    fn play(deal: &str, contract: &str, declarer: BridgeDirection) -> BridgePlay {
+        let board = BridgeBoard::from_pbn_deal(deal);
+        BridgePlay::new(&board, Contract::from_pbn(contract, declarer).unwrap())
+    }
+
+    #[test]
+    fn trick__winner() {
+        let mut trick = Trick::new(BridgeDirection::W);
+        trick.cards = vec![
+            Card::new(KING, HEARTS),
+            Card::new(ACE, HEARTS),
+            Card::new(TWO, SPADES),
+            Card::new(ACE, CLUBS),
+        ];
+
+        assert_eq!(Some(BridgeDirection::N), trick.winner(&Strain::NT));
+        assert_eq!(Some(BridgeDirection::E), trick.winner(&Strain::S));
+        assert_eq!(None, Trick::new(BridgeDirection::W).winner(&Strain::NT));
+    }
+
+    #[test]
+    fn play__follow_suit() {
+        let mut play = play("N:A2.K.. K3.A.. Q4.2.. J5.3..", "1NT", BridgeDirection::S);
+
+        assert_eq!(BridgeDirection::W, play.to_play());
+        assert!(play.play(&Card::new(JACK, SPADES)));
+        // North holds spades, so can't play the king of hearts.
+        assert!(!play.play(&Card::new(KING, HEARTS)));
+        assert!(!play.play(&Card::new(KING, SPADES)));
+        assert_eq!(2, play.legal_plays().len());
+        assert!(play.play(&Card::new(ACE, SPADES)));
+        assert!(play.play(&Card::new(THREE, SPADES)));
+        assert!(play.play(&Card::new(FOUR, SPADES)));
+
+        assert_eq!(BridgeDirection::N, play.to_play());
+        assert_eq!(1, play.declarer_tricks());
+        assert_eq!(0, play.defender_tricks());
+        assert_eq!(2, play.tricks_remaining());
+    }
+
+    #[test]
+    fn play__trumps() {
+        let mut play = play("N:A.2.. .K..2 K..A. 2.A..", "1C", BridgeDirection::W);
+
+        // East ruffs North's ace of spades.
+        for card in [
+            Card::new(ACE, SPADES),
+            Card::new(TWO, CLUBS),
+            Card::new(KING, SPADES),
+            Card::new(TWO, SPADES),
+        ]
+        .iter()
+        {
+            assert!(play.play(card));
+        }
+
+        assert_eq!(BridgeDirection::E, play.leader());
+        assert_eq!(1, play.tricks_won(&BridgeSide::EW));
+    }
+
+    #[test]
+    fn dummy() {
+        let mut play = play("N:A.K.. K.A.. 2.2.. 3.3..", "1NT", BridgeDirection::N);
+
+        assert!(play.dummy().is_none());
+        play.play(&Card::new(KING, SPADES));
+
+        assert_eq!(play.hand(&BridgeDirection::S), play.dummy());
+    }
+
+    #[test]
+    fn undo() {
+        let mut play = play("N:A2.K.. K3.A.. Q4.2.. J5.3..", "1NT", BridgeDirection::S);
+        let before = play.hand(&BridgeDirection::W).unwrap().clone();
+        let cards = [
+            Card::new(JACK, SPADES),
+            Card::new(ACE, SPADES),
+            Card::new(THREE, SPADES),
+            Card::new(FOUR, SPADES),
+        ];
+        for card in cards.iter() {
+            play.play(card);
+        }
+
+        assert_eq!(Some(Card::new(FOUR, SPADES)), play.undo());
+        assert_eq!(BridgeDirection::S, play.to_play());
+        assert_eq!(0, play.declarer_tricks());
+        play.undo();
+        play.undo();
+        play.undo();
+
+        assert!(play.tricks().is_empty());
+        assert_eq!(Some(&before), play.hand(&BridgeDirection::W));
+        assert_eq!(None, play.undo());
+    }
+
+    #[test]
+    fn claim() {
+        let mut play = play("N:A.K.. K.A.. 2.2.. 3.3..", "1NT", BridgeDirection::N);
+
+        // East holds the ace of hearts, so declarer can only ever make the ace of spades.
+        assert!(play.claim(1));
+        assert!(!play.claim(2));
+        play.play(&Card::new(KING, SPADES));
+        assert!(play.claim(1));
+        assert!(!play.claim(2));
+        play.play(&Card::new(TWO, SPADES));
+        play.play(&Card::new(THREE, SPADES));
+        play.play(&Card::new(ACE, SPADES));
+
+        assert_eq!(1, play.declarer_tricks());
+        assert!(play.claim(0));
+        assert!(!play.claim(1));
+        assert!(!play.claim(2));
+    }
+
+    #[test]
+    fn is_complete() {
+        let mut play = play("N:A... K... 2... 3...", "1S", BridgeDirection::N);
+
+        while !play.is_complete() {
+            let card = play.legal_plays().get(0).unwrap().clone();
+            assert!(play.play(&card));
+        }
+
+        assert_eq!(0, play.tricks_remaining());
+        assert_eq!(1, play.declarer_tricks());
+    }
+}
@@ -0,0 +1,559 @@
+use crate::cards::card::Card;
+use crate::cards::decks::bridge::auction::{Auction, Call};
+use crate::cards::decks::bridge::contract::{Contract, Doubling, Strain};
+use crate::cards::decks::bridge::play::BridgePlay;
+use crate::cards::decks::bridge::{
+    card_from_chars, card_to_text, split_once, suit_char_index, BridgeBoard, BridgeDirection,
+    Vulnerability,
+};
+
+impl BridgeBoard {
+    /// Parses the hands of a Richard's Bridge Notation `H` line, such as
+    /// `N:AKJ.T98.A2.KJ943:Q5.AJ.KT9543.Q72:...`, where the hands follow clockwise from the seat
+    /// before the first colon. A missing or empty hand is unknown, unless it is the only one,
+    /// in which case it is made up of the cards the others don't hold.
+    pub fn from_rbn_deal(deal: &str) -> Option<BridgeBoard> {
+        let (first, hands) = split_once(deal.trim(), ":")?;
+        let first = BridgeDirection::to(first.trim().chars().next()?);
+        if first == BridgeDirection::UNKNOWN {
+            return None;
+        }
+        let mut hands: Vec<&str> = hands.split(':').map(str::trim).collect();
+        if hands.len() > 4 {
+            return None;
+        }
+        hands.resize(4, "");
+        let pbn: Vec<&str> = hands
+            .iter()
+            .map(|hand| if hand.is_empty() { "-" } else { hand })
+            .collect();
+        let mut board =
+            BridgeBoard::try_from_pbn_deal(&format!("{}:{}", first.to_char(), pbn.join(" ")))?;
+        let unknown = board.unknown_seats();
+        let known_complete = BridgeDirection::seats()
+            .iter()
+            .filter(|seat| !unknown.contains(seat))
+            .all(|seat| board.get_hand(seat).map_or(false, |hand| hand.len() == 13));
+        if unknown.len() == 1 && known_complete {
+            let unseen = board.unseen().sort();
+            *board.get_hand_mut(&unknown[0])? = unseen;
+        }
+        if board.is_consistent() {
+            Some(board)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the hands as a Richard's Bridge Notation `H` line, starting with the dealer,
+    /// or North when the dealer is unknown.
+    pub fn to_rbn_deal(&self) -> String {
+        let first = match self.dealer {
+            BridgeDirection::UNKNOWN => BridgeDirection::N,
+            dealer => dealer,
+        };
+        let pbn = self.to_pbn_deal_from(&first);
+        let hands: Vec<&str> = pbn
+            .get(2..)
+            .unwrap_or("")
+            .split_whitespace()
+            .map(|hand| if hand == "-" { "" } else { hand })
+            .collect();
+        format!("{}:{}", first.to_char(), hands.join(":"))
+    }
+}
+
+/// A board written in Richard's Bridge Notation, a compact format with one line for each
+/// part of the record, each starting with a letter that says what it holds:
+///
+/// ```text
+/// B 1
+/// H N:AKJ.T98.A2.KJ943:Q5.AJ.KT9543.Q72:T9876.K54.Q7.A65:432.Q7632.J86.T8
+/// A NZ:1NP3NA
+/// C 3NN
+/// P D5D7DJDA:D2DKDQD6:...
+/// R 9
+/// ```
+///
+/// `B` is the board number, `H` the hands, `A` the dealer and vulnerability (`Z` for none,
+/// `N`, `E` or `B` for both) followed by the calls, with `P`, `X` and `R` for pass, double
+/// and redouble and `A` for all pass. `C` is the contract and declarer, `P` the play with a
+/// colon after each trick, and `R` the number of tricks declarer took. Any other lines, such
+/// as `E` for the event or `N` for the players, are kept as they are.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeDirection, RbnRecord};
+///
+/// let record = RbnRecord::from_rbn(
+///     "B 1\n\
+///      H N:AKJ.T98.A2.KJ943:Q5.AJ.KT9543.Q72:T9876.K54.Q7.A65\n\
+///      A NZ:1NP3NA\n\
+///      R 9",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(13, record.board.west.len());
+/// assert_eq!("3NT", record.contract.unwrap().to_pbn());
+/// assert_eq!(BridgeDirection::N, record.contract.unwrap().declarer);
+/// assert_eq!(
+///     "N:AKJ.T98.A2.KJ943 Q5.AJ.KT9543.Q72 T9876.K54.Q7.A65 432.Q7632.J86.T8",
+///     record.board.to_pbn_deal_from(&BridgeDirection::N)
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RbnRecord {
+    pub board: BridgeBoard,
+    pub auction: Option<Auction>,
+    pub contract: Option<Contract>,
+    /// The cards played, in order.
+    pub play: Vec<Card>,
+    /// The number of tricks taken by declarer.
+    pub result: Option<usize>,
+    /// The lines that aren't otherwise understood, by their letter.
+    pub other: Vec<(char, String)>,
+}
+
+impl RbnRecord {
+    /// Starts a record for a board, without an auction, play or result.
+    pub fn new(board: BridgeBoard) -> RbnRecord {
+        RbnRecord {
+            board,
+            auction: None,
+            contract: None,
+            play: Vec::new(),
+            result: None,
+            other: Vec::new(),
+        }
+    }
+
+    /// Reads a single board's record. Returns None if it has no hands, or any of its lines
+    /// can't be read. When there's an auction but no `C` line, the contract comes from the
+    /// auction.
+    pub fn from_rbn(rbn: &str) -> Option<RbnRecord> {
+        let mut record = RbnRecord::new(BridgeBoard::default());
+        let mut hands = None;
+        let mut number = None;
+        let mut auction_line = None;
+        let mut contract_line = None;
+        let mut play_line = None;
+
+        for line in rbn.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let mut chars = line.chars();
+            let tag = chars.next()?;
+            let value = chars.as_str().trim();
+            match tag {
+                'H' => hands = Some(value),
+                'B' => number = Some(value.parse::<usize>().ok()?),
+                'A' => auction_line = Some(value),
+                'C' => contract_line = Some(value),
+                'P' => play_line = Some(value),
+                'R' => record.result = Some(value.split_whitespace().next()?.parse().ok()?),
+                _ => record.other.push((tag, value.to_string())),
+            }
+        }
+
+        record.board = BridgeBoard::from_rbn_deal(hands?)?;
+        if let Some(number) = number {
+            record.board.set_number(number);
+        }
+        if let Some(line) = auction_line {
+            let (auction, vulnerability) = parse_auction(line)?;
+            record.board.dealer = auction.dealer;
+            record.board.vulnerability = vulnerability;
+            record.contract = auction.contract();
+            record.auction = Some(auction);
+        }
+        if let Some(contract) = contract_line {
+            record.contract = parse_contract(contract)?;
+        }
+        if let Some(play) = play_line {
+            record.play = parse_play(play)?;
+        }
+        Some(record)
+    }
+
+    /// Reads every record in a file, where records are separated by blank lines. Records
+    /// that can't be read are skipped.
+    pub fn read_all(rbn: &str) -> Vec<RbnRecord> {
+        let mut records = Vec::new();
+        let mut lines = Vec::new();
+        for line in rbn.lines().chain(std::iter::once("")) {
+            if line.trim().is_empty() {
+                if !lines.is_empty() {
+                    records.extend(RbnRecord::from_rbn(&lines.join("\n")));
+                    lines.clear();
+                }
+            } else {
+                lines.push(line);
+            }
+        }
+        records
+    }
+
+    pub fn to_rbn(&self) -> String {
+        let mut lines = Vec::new();
+        for (tag, value) in self.other.iter() {
+            lines.push(format!("{} {}", tag, value));
+        }
+        if self.board.number > 0 {
+            lines.push(format!("B {}", self.board.number));
+        }
+        lines.push(format!("H {}", self.board.to_rbn_deal()));
+        if let Some(auction) = &self.auction {
+            lines.push(format!(
+                "A {}",
+                auction_to_rbn(auction, &self.board.vulnerability)
+            ));
+        }
+        if let Some(contract) = &self.contract {
+            lines.push(format!("C {}", contract_to_rbn(contract)));
+        }
+        if !self.play.is_empty() {
+            let tricks: Vec<String> = self
+                .play
+                .chunks(4)
+                .map(|trick| trick.iter().filter_map(card_to_text).collect::<String>())
+                .collect();
+            lines.push(format!("P {}", tricks.join(":")));
+        }
+        if let Some(result) = self.result {
+            lines.push(format!("R {}", result));
+        }
+        lines.join("\n")
+    }
+
+    /// Plays the cards in the record through a BridgePlay, which checks that each one is
+    /// legal. Returns None if there's no contract or a card can't be played.
+    pub fn bridge_play(&self) -> Option<BridgePlay> {
+        let mut play = BridgePlay::new(&self.board, self.contract?);
+        for card in self.play.iter() {
+            if !play.play(card) {
+                return None;
+            }
+        }
+        Some(play)
+    }
+}
+
+fn vulnerability_char(vulnerability: &Vulnerability) -> char {
+    match vulnerability {
+        Vulnerability::NONE => 'Z',
+        Vulnerability::NS => 'N',
+        Vulnerability::EW => 'E',
+        Vulnerability::BOTH => 'B',
+    }
+}
+
+fn strain_char(strain: &Strain) -> char {
+    match strain {
+        Strain::S => 'S',
+        Strain::H => 'H',
+        Strain::D => 'D',
+        Strain::C => 'C',
+        Strain::NT => 'N',
+    }
+}
+
+fn parse_strain(c: char) -> Option<Strain> {
+    match c {
+        'N' => Some(Strain::NT),
+        c => Strain::to(&c.to_string()),
+    }
+}
+
+/// Writes the dealer, vulnerability and calls, with a colon after every round and `A` in
+/// place of the final passes.
+fn auction_to_rbn(auction: &Auction, vulnerability: &Vulnerability) -> String {
+    let calls = auction.calls();
+    let trailing = calls
+        .iter()
+        .rev()
+        .take_while(|call| **call == Call::PASS)
+        .count();
+    let all_pass = auction.is_complete() && (trailing == 3 || calls.len() == 4);
+    let written = if all_pass {
+        calls.len() - trailing
+    } else {
+        calls.len()
+    };
+
+    let mut text = format!(
+        "{}{}:",
+        auction.dealer.to_char(),
+        vulnerability_char(vulnerability)
+    );
+    for (i, call) in calls[..written].iter().enumerate() {
+        if i > 0 && i % 4 == 0 {
+            text.push(':');
+        }
+        match call {
+            Call::PASS => text.push('P'),
+            Call::DOUBLE => text.push('X'),
+            Call::REDOUBLE => text.push('R'),
+            Call::BID(level, strain) => {
+                text.push_str(&level.to_string());
+                text.push(strain_char(strain));
+            }
+        }
+    }
+    if all_pass {
+        if written > 0 && written % 4 == 0 {
+            text.push(':');
+        }
+        text.push('A');
+    }
+    text
+}
+
+fn parse_auction(text: &str) -> Option<(Auction, Vulnerability)> {
+    let mut chars = text.chars().filter(|c| !c.is_whitespace()).peekable();
+    let dealer = BridgeDirection::to(chars.next()?);
+    let vulnerability = match chars.next()? {
+        'Z' => Vulnerability::NONE,
+        'N' => Vulnerability::NS,
+        'E' => Vulnerability::EW,
+        'B' => Vulnerability::BOTH,
+        _ => return None,
+    };
+    if dealer == BridgeDirection::UNKNOWN {
+        return None;
+    }
+
+    let mut auction = Auction::new(dealer);
+    while let Some(c) = chars.next() {
+        let call = match c {
+            ':' | '*' => continue,
+            'P' => Call::PASS,
+            'X' => Call::DOUBLE,
+            'R' => Call::REDOUBLE,
+            'A' => {
+                while !auction.is_complete() {
+                    auction.call(Call::PASS);
+                }
+                continue;
+            }
+            '1'..='7' => Call::BID(c.to_digit(10)? as usize, parse_strain(chars.next()?)?),
+            _ => return None,
+        };
+        if !auction.call(call) {
+            return None;
+        }
+    }
+    Some((auction, vulnerability))
+}
+
+fn contract_to_rbn(contract: &Contract) -> String {
+    let doubling = match contract.doubling {
+        Doubling::NONE => "",
+        Doubling::DOUBLED => "X",
+        Doubling::REDOUBLED => "R",
+    };
+    format!(
+        "{}{}{}{}",
+        contract.level,
+        strain_char(&contract.strain),
+        doubling,
+        contract.declarer.to_char()
+    )
+}
+
+/// Parses a `C` line, where `P` on its own means the board was passed out.
+fn parse_contract(text: &str) -> Option<Option<Contract>> {
+    let text = text.trim();
+    if text == "P" {
+        return Some(None);
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let level = chars.first()?.to_digit(10)? as usize;
+    let strain = parse_strain(*chars.get(1)?)?;
+    let (doubling, seat) = match chars.get(2..)? {
+        ['X', seat] => (Doubling::DOUBLED, seat),
+        ['R', seat] => (Doubling::REDOUBLED, seat),
+        [seat] => (Doubling::NONE, seat),
+        _ => return None,
+    };
+    let declarer = BridgeDirection::to(*seat);
+    if !(1..=7).contains(&level) || declarer == BridgeDirection::UNKNOWN {
+        return None;
+    }
+    let mut contract = Contract::new(level, strain, declarer);
+    contract.doubling = doubling;
+    Some(Some(contract))
+}
+
+/// Parses the cards played. A card without a suit follows the suit led to the trick.
+fn parse_play(text: &str) -> Option<Vec<Card>> {
+    let mut cards = Vec::new();
+    for trick in text.split(':').map(str::trim).filter(|t| !t.is_empty()) {
+        let mut led = None;
+        let mut chars = trick.chars().filter(|c| !c.is_whitespace()).peekable();
+        while let Some(c) = chars.next() {
+            let (suit, rank) = match suit_char_index(c) {
+                Some(_) => (c, chars.next()?),
+                None => (led?, c),
+            };
+            led = led.or(Some(suit));
+            cards.push(card_from_chars(suit, rank)?);
+        }
+    }
+    Some(cards)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_rbn_tests {
+    use super::*;
+
+    const PBN_TEST_STRING: &str =
+        "N:AKJ.T98.A2.KJ943 Q5.AJ.KT9543.Q72 T9876.K54.Q7.A65 432.Q7632.J86.T8";
+    const RBN_TEST_STRING: &str =
+        "N:AKJ.T98.A2.KJ943:Q5.AJ.KT9543.Q72:T9876.K54.Q7.A65:432.Q7632.J86.T8";
+
+    const RECORD: &str = "E Club game
+N Smith+Jones:Brown+Green
+B 3
+H S:T9876.K54.Q7.A65:432.Q7632.J86.T8:AKJ.T98.A2.KJ943:Q5.AJ.KT9543.Q72
+A SE:PP1CP:1SP2NP:3NA
+C 3NN
+P D5D7DJDA:D2DKDQD6:CQCAC8C3:C5CTCKC2
+R 9";
+
+    #[test]
+    fn from_rbn_deal() {
+        let board = BridgeBoard::from_rbn_deal(RBN_TEST_STRING).unwrap();
+
+        assert_eq!(PBN_TEST_STRING, board.to_pbn_deal_from(&BridgeDirection::N));
+    }
+
+    #[test]
+    fn from_rbn_deal__fourth_hand() {
+        let board =
+            BridgeBoard::from_rbn_deal("N:AKJ.T98.A2.KJ943:Q5.AJ.KT9543.Q72:T9876.K54.Q7.A65")
+                .unwrap();
+
+        assert!(board.is_valid());
+        assert_eq!(BridgeBoard::from_pbn_deal(PBN_TEST_STRING).west, board.west);
+    }
+
+    #[test]
+    fn from_rbn_deal__unknown_hands() {
+        let board = BridgeBoard::from_rbn_deal("S:T9876.K54.Q7.A65::AKJ.T98.A2.KJ943:").unwrap();
+
+        assert_eq!(
+            vec![BridgeDirection::E, BridgeDirection::W],
+            board.unknown_seats()
+        );
+    }
+
+    #[test]
+    fn from_rbn_deal__invalid() {
+        assert_eq!(None, BridgeBoard::from_rbn_deal("AKJ.T98.A2.KJ943"));
+        assert_eq!(None, BridgeBoard::from_rbn_deal("X:AKJ.T98.A2.KJ943"));
+        assert_eq!(None, BridgeBoard::from_rbn_deal("N:AKJ.T98.A2"));
+        assert_eq!(
+            None,
+            BridgeBoard::from_rbn_deal("N:AKJ.T98.A2.KJ943:AKJ.T98.A2.KJ943")
+        );
+    }
+
+    #[test]
+    fn to_rbn_deal() {
+        let mut board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+        board.set_number(1);
+
+        assert_eq!(RBN_TEST_STRING, board.to_rbn_deal());
+        assert_eq!(
+            "N:::Q5.AJ.KT9543.Q72:",
+            BridgeBoard::from_pbn_deal("N:- - Q5.AJ.KT9543.Q72 -").to_rbn_deal()
+        );
+    }
+
+    #[test]
+    fn from_rbn() {
+        let record = RbnRecord::from_rbn(RECORD).unwrap();
+
+        assert_eq!(3, record.board.number);
+        assert_eq!(BridgeDirection::S, record.board.dealer);
+        assert_eq!(Vulnerability::EW, record.board.vulnerability);
+        assert_eq!(
+            "Pass Pass 1C Pass 1S Pass 2NT Pass 3NT Pass Pass Pass",
+            record.auction.as_ref().unwrap().to_string()
+        );
+        assert_eq!("3NT", record.contract.unwrap().to_pbn());
+        assert_eq!(BridgeDirection::N, record.contract.unwrap().declarer);
+        assert_eq!(16, record.play.len());
+        assert_eq!(Some(9), record.result);
+        assert_eq!(
+            vec![
+                ('E', "Club game".to_string()),
+                ('N', "Smith+Jones:Brown+Green".to_string())
+            ],
+            record.other
+        );
+    }
+
+    #[test]
+    fn from_rbn__play_without_suits() {
+        let short = RECORD.replace("D5D7DJDA:D2DKDQD6", "D57JA:D2KQ6");
+
+        let record = RbnRecord::from_rbn(&short).unwrap();
+
+        assert_eq!(RbnRecord::from_rbn(RECORD).unwrap().play, record.play);
+    }
+
+    #[test]
+    fn from_rbn__invalid() {
+        assert_eq!(None, RbnRecord::from_rbn("B 1"));
+        assert_eq!(
+            None,
+            RbnRecord::from_rbn(&RECORD.replace("A SE:PP1CP:1SP", "A SE:PP1CP:1CP"))
+        );
+        assert_eq!(None, RbnRecord::from_rbn(&RECORD.replace("C 3NN", "C 3NQ")));
+    }
+
+    #[test]
+    fn to_rbn() {
+        let record = RbnRecord::from_rbn(RECORD).unwrap();
+
+        assert_eq!(RECORD, record.to_rbn());
+        assert_eq!(Some(record.clone()), RbnRecord::from_rbn(&record.to_rbn()));
+    }
+
+    #[test]
+    fn to_rbn__passed_out() {
+        let mut auction = Auction::new(BridgeDirection::W);
+        for _ in 0..4 {
+            auction.call(Call::PASS);
+        }
+        let mut record = RbnRecord::new(BridgeBoard::from_pbn_deal(PBN_TEST_STRING));
+        record.board.set_number(4);
+        record.auction = Some(auction);
+
+        let rbn = record.to_rbn();
+
+        assert!(rbn.ends_with("A WB:A"));
+        assert_eq!(Some(record), RbnRecord::from_rbn(&rbn));
+    }
+
+    #[test]
+    fn read_all() {
+        let text = format!("{}\n\n{}\n\nB 2\nH nonsense\n", RECORD, RECORD);
+
+        assert_eq!(2, RbnRecord::read_all(&text).len());
+    }
+
+    #[test]
+    fn bridge_play() {
+        let record = RbnRecord::from_rbn(RECORD).unwrap();
+
+        let play = record.bridge_play().unwrap();
+
+        assert_eq!(4, play.tricks().len());
+        assert_eq!(3, play.declarer_tricks());
+        assert!(RbnRecord::from_rbn(&RECORD.replace("CQCAC8C3", "CQC8CAC3"))
+            .unwrap()
+            .bridge_play()
+            .is_none());
+    }
+}
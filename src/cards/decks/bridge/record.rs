@@ -0,0 +1,517 @@
+use unic_langid::LanguageIdentifier;
+
+use crate::cards::decks::bridge::contract::Strain;
+use crate::cards::decks::bridge::dds::TrickTable;
+use crate::cards::decks::bridge::evaluation::HandEvaluation;
+use crate::cards::decks::bridge::{
+    suit_index, BridgeBoard, BridgeDirection, Vulnerability, BRIDGE_SUITS,
+};
+use crate::cards::pile::Pile;
+use crate::cards::suit::Suit;
+use crate::fluent::fluent_name::FluentName;
+use crate::Named;
+
+const BOARD_WIDTH: usize = 360;
+const BOARD_HEIGHT: usize = 300;
+const MAKEABLE_HEIGHT: usize = 90;
+const LINE: usize = 16;
+
+const STYLE: &str = "body { font-family: sans-serif; }
+.page { page-break-after: always; display: flex; flex-wrap: wrap; }
+.board { margin: 1em; border: 1px solid #999; }
+.board td { vertical-align: top; padding: 0.25em 0.75em; }
+.seat { font-weight: bold; }
+.red { color: #c00; }
+.makeable td, .makeable th { padding: 0 0.3em; text-align: center; }";
+
+/// Hand records for a set of BridgeBoards, as standalone HTML pages or SVG images with
+/// several boards on each page.
+///
+/// Suit symbols come from the Fluent `symbol` entries, and headings and ranks are shown in
+/// the language passed in. Each board can optionally be annotated with the high card points
+/// of each hand and, when a trick table has been added with it, the makeable contracts.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeBoard, HandRecord, US_ENGLISH};
+///
+/// let mut record = HandRecord::default();
+/// record.add(BridgeBoard::deal_board(1));
+/// record.add(BridgeBoard::deal_board(2));
+///
+/// let pages = record.html_pages(&US_ENGLISH);
+///
+/// assert_eq!(1, pages.len());
+/// assert!(pages[0].starts_with("<!DOCTYPE html>"));
+/// assert!(pages[0].contains("Board 2"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct HandRecord {
+    boards: Vec<(BridgeBoard, Option<TrickTable>)>,
+    pub boards_per_page: usize,
+    pub hcp: bool,
+    pub makeable: bool,
+}
+
+impl HandRecord {
+    pub fn new(boards_per_page: usize, hcp: bool, makeable: bool) -> HandRecord {
+        HandRecord {
+            boards: Vec::new(),
+            boards_per_page: boards_per_page.max(1),
+            hcp,
+            makeable,
+        }
+    }
+
+    pub fn add(&mut self, board: BridgeBoard) {
+        self.boards.push((board, None));
+    }
+
+    /// Adds a board along with its double dummy results, for the makeable contracts.
+    pub fn add_with_trick_table(&mut self, board: BridgeBoard, table: TrickTable) {
+        self.boards.push((board, Some(table)));
+    }
+
+    pub fn len(&self) -> usize {
+        self.boards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boards.is_empty()
+    }
+
+    /// Returns one standalone HTML document for each page of boards.
+    pub fn html_pages(&self, lid: &LanguageIdentifier) -> Vec<String> {
+        let title = escape(&FluentName::new("hand-records").long(lid));
+        self.pages()
+            .map(|page| {
+                let boards: String = page
+                    .iter()
+                    .map(|(board, table)| self.board_html(board, table.as_ref(), lid))
+                    .collect();
+                format!(
+                    "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n\
+                     <title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n\
+                     <div class=\"page\">\n{}</div>\n</body>\n</html>\n",
+                    lid, title, STYLE, boards
+                )
+            })
+            .collect()
+    }
+
+    /// Returns one SVG image for each page of boards, laid out two boards across.
+    pub fn svg_pages(&self, lid: &LanguageIdentifier) -> Vec<String> {
+        let height = self.svg_board_height();
+        self.pages()
+            .map(|page| {
+                let rows = (page.len() + 1) / 2;
+                let boards: String = page
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (board, table))| {
+                        let x = (i % 2) * BOARD_WIDTH;
+                        let y = (i / 2) * height;
+                        format!(
+                            "<g transform=\"translate({},{})\">\n{}</g>\n",
+                            x,
+                            y,
+                            self.board_svg(board, table.as_ref(), lid)
+                        )
+                    })
+                    .collect();
+                svg_document(BOARD_WIDTH * 2, rows * height, &boards)
+            })
+            .collect()
+    }
+
+    /// Returns an SVG image of a single board, by its position in the record.
+    pub fn svg(&self, index: usize, lid: &LanguageIdentifier) -> Option<String> {
+        let (board, table) = self.boards.get(index)?;
+        Some(svg_document(
+            BOARD_WIDTH,
+            self.svg_board_height(),
+            &self.board_svg(board, table.as_ref(), lid),
+        ))
+    }
+
+    fn pages(&self) -> std::slice::Chunks<'_, (BridgeBoard, Option<TrickTable>)> {
+        self.boards.chunks(self.boards_per_page.max(1))
+    }
+
+    fn svg_board_height(&self) -> usize {
+        if self.makeable {
+            BOARD_HEIGHT + MAKEABLE_HEIGHT
+        } else {
+            BOARD_HEIGHT
+        }
+    }
+
+    fn board_html(
+        &self,
+        board: &BridgeBoard,
+        table: Option<&TrickTable>,
+        lid: &LanguageIdentifier,
+    ) -> String {
+        let hand = |direction: BridgeDirection| self.hand_html(board, &direction, lid);
+        let compass = BridgeDirection::seats()
+            .iter()
+            .map(|d| seat_letter(d, lid))
+            .collect::<Vec<String>>();
+        let makeable = match (self.makeable, table) {
+            (true, Some(table)) => makeable_html(table, lid),
+            _ => String::new(),
+        };
+
+        format!(
+            "<table class=\"board\">\n\
+             <tr><td>{}</td><td>{}</td><td></td></tr>\n\
+             <tr><td>{}</td><td class=\"compass\">{}<br>{}&nbsp;&nbsp;{}<br>{}</td><td>{}</td></tr>\n\
+             <tr><td></td><td>{}</td><td>{}</td></tr>\n\
+             </table>\n",
+            header_lines(board, lid).join("<br>"),
+            hand(BridgeDirection::N),
+            hand(BridgeDirection::W),
+            compass[0],
+            compass[3],
+            compass[1],
+            compass[2],
+            hand(BridgeDirection::E),
+            hand(BridgeDirection::S),
+            makeable
+        )
+    }
+
+    fn hand_html(
+        &self,
+        board: &BridgeBoard,
+        direction: &BridgeDirection,
+        lid: &LanguageIdentifier,
+    ) -> String {
+        let hand = match board.get_hand(direction) {
+            Some(hand) => hand,
+            None => return String::new(),
+        };
+        let mut html = format!("<div class=\"seat\">{}", escape(&seat_name(direction, lid)));
+        if self.hcp {
+            html.push_str(&format!(" ({})", hand.hcp()));
+        }
+        html.push_str("</div>");
+
+        for (i, ranks) in suit_ranks(hand, lid).iter().enumerate() {
+            let class = if is_red(i) { "suit red" } else { "suit" };
+            html.push_str(&format!(
+                "<div><span class=\"{}\">{}</span> {}</div>",
+                class,
+                suit_symbol(i),
+                escape(ranks)
+            ));
+        }
+        html
+    }
+
+    fn board_svg(
+        &self,
+        board: &BridgeBoard,
+        table: Option<&TrickTable>,
+        lid: &LanguageIdentifier,
+    ) -> String {
+        let mut svg = format!(
+            "<rect x=\"1\" y=\"1\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#999\"/>\n",
+            BOARD_WIDTH - 2,
+            self.svg_board_height() - 2
+        );
+        for (i, line) in header_lines(board, lid).iter().enumerate() {
+            svg.push_str(&svg_text(10, 20 + i * LINE, line));
+        }
+
+        let positions = [
+            (BridgeDirection::N, 140, 20),
+            (BridgeDirection::W, 10, 110),
+            (BridgeDirection::E, 250, 110),
+            (BridgeDirection::S, 140, 200),
+        ];
+        for (direction, x, y) in positions.iter() {
+            let hand = match board.get_hand(direction) {
+                Some(hand) => hand,
+                None => continue,
+            };
+            let mut name = escape(&seat_name(direction, lid));
+            if self.hcp {
+                name.push_str(&format!(" ({})", hand.hcp()));
+            }
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" font-weight=\"bold\">{}</text>\n",
+                x, y, name
+            ));
+            for (i, ranks) in suit_ranks(hand, lid).iter().enumerate() {
+                let fill = if is_red(i) { "#c00" } else { "#000" };
+                svg.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\"><tspan fill=\"{}\">{}</tspan> {}</text>\n",
+                    x,
+                    y + (i + 1) * LINE,
+                    fill,
+                    suit_symbol(i),
+                    escape(ranks)
+                ));
+            }
+        }
+
+        if let (true, Some(table)) = (self.makeable, table) {
+            let top = BOARD_HEIGHT;
+            svg.push_str(&svg_text(
+                10,
+                top,
+                &escape(&FluentName::new("makeable").long(lid)),
+            ));
+            for (i, row) in makeable_rows(table).iter().enumerate() {
+                let (seat, levels) = row;
+                let cells: Vec<String> = levels.iter().map(|l| level_text(*l)).collect();
+                svg.push_str(&svg_text(
+                    10,
+                    top + (i + 1) * LINE,
+                    &format!("{} {}", seat_letter(seat, lid), cells.join(" ")),
+                ));
+            }
+        }
+        svg
+    }
+}
+
+impl Default for HandRecord {
+    fn default() -> Self {
+        HandRecord::new(4, false, false)
+    }
+}
+
+fn svg_document(width: usize, height: usize, content: &str) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         font-family=\"sans-serif\" font-size=\"14\">\n{}</svg>\n",
+        width, height, content
+    )
+}
+
+fn svg_text(x: usize, y: usize, text: &str) -> String {
+    format!("<text x=\"{}\" y=\"{}\">{}</text>\n", x, y, text)
+}
+
+/// The board number, dealer and vulnerability, already escaped.
+fn header_lines(board: &BridgeBoard, lid: &LanguageIdentifier) -> Vec<String> {
+    vec![
+        format!(
+            "{} {}",
+            escape(&FluentName::new("board").long(lid)),
+            board.number
+        ),
+        format!(
+            "{}: {}",
+            escape(&FluentName::new("dealer").long(lid)),
+            seat_letter(&board.dealer, lid)
+        ),
+        format!(
+            "{}: {}",
+            escape(&FluentName::new("vulnerable").long(lid)),
+            escape(&vulnerability_name(&board.vulnerability, lid))
+        ),
+    ]
+}
+
+fn makeable_html(table: &TrickTable, lid: &LanguageIdentifier) -> String {
+    let mut html = format!(
+        "<div>{}</div><table class=\"makeable\"><tr><th></th>",
+        escape(&FluentName::new("makeable").long(lid))
+    );
+    for strain in Strain::all().iter() {
+        let heading = match strain.suit_index() {
+            Some(suit) if is_red(suit) => format!("<span class=\"red\">{}</span>", strain),
+            _ => strain.to_string(),
+        };
+        html.push_str(&format!("<th>{}</th>", heading));
+    }
+    html.push_str("</tr>");
+    for (seat, levels) in makeable_rows(table) {
+        html.push_str(&format!("<tr><th>{}</th>", seat_letter(&seat, lid)));
+        for level in levels.iter() {
+            html.push_str(&format!("<td>{}</td>", level_text(*level)));
+        }
+        html.push_str("</tr>");
+    }
+    html.push_str("</table>");
+    html
+}
+
+/// The highest level each declarer can make in each strain, in trick table order.
+fn makeable_rows(table: &TrickTable) -> Vec<(BridgeDirection, Vec<usize>)> {
+    [
+        BridgeDirection::N,
+        BridgeDirection::S,
+        BridgeDirection::E,
+        BridgeDirection::W,
+    ]
+    .iter()
+    .map(|seat| {
+        let levels = Strain::all()
+            .iter()
+            .map(|strain| table.get(strain, seat).saturating_sub(6))
+            .collect();
+        (*seat, levels)
+    })
+    .collect()
+}
+
+fn level_text(level: usize) -> String {
+    match level {
+        0 => "-".to_string(),
+        _ => level.to_string(),
+    }
+}
+
+fn seat_name(direction: &BridgeDirection, lid: &LanguageIdentifier) -> String {
+    let name = match direction {
+        BridgeDirection::N => "north",
+        BridgeDirection::E => "east",
+        BridgeDirection::S => "south",
+        BridgeDirection::W => "west",
+        BridgeDirection::UNKNOWN => return String::new(),
+    };
+    FluentName::new(name).long(lid)
+}
+
+fn vulnerability_name(vulnerability: &Vulnerability, lid: &LanguageIdentifier) -> String {
+    let name = match vulnerability {
+        Vulnerability::NONE => "vulnerable-none",
+        Vulnerability::NS => "vulnerable-ns",
+        Vulnerability::EW => "vulnerable-ew",
+        Vulnerability::BOTH => "vulnerable-both",
+    };
+    FluentName::new(name).long(lid)
+}
+
+fn seat_letter(direction: &BridgeDirection, lid: &LanguageIdentifier) -> String {
+    escape(
+        &seat_name(direction, lid)
+            .chars()
+            .take(1)
+            .collect::<String>(),
+    )
+}
+
+fn suit_symbol(suit: usize) -> String {
+    Suit::new(BRIDGE_SUITS[suit]).symbol()
+}
+
+fn is_red(suit: usize) -> bool {
+    suit == 1 || suit == 2
+}
+
+/// The ranks held in each suit in the passed in language, or `-` for a void.
+fn suit_ranks(hand: &Pile, lid: &LanguageIdentifier) -> Vec<String> {
+    let mut ranks = vec![String::new(); 4];
+    for card in hand.sort().values() {
+        if let Some(suit) = suit_index(card) {
+            ranks[suit].push_str(&card.rank.index(lid));
+        }
+    }
+    for holding in ranks.iter_mut() {
+        if holding.is_empty() {
+            holding.push('-');
+        }
+    }
+    ranks
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_record_tests {
+    use super::*;
+    use crate::{GERMAN, US_ENGLISH};
+
+    const PBN_TEST_STRING: &str =
+        "S:Q42.Q52.AQT943.Q 97.AT93.652.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982";
+
+    fn record(boards: usize) -> HandRecord {
+        let mut record = HandRecord::new(2, true, true);
+        for i in 0..boards {
+            let mut board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+            board.set_number(i + 1);
+            let mut table = TrickTable::default();
+            table.set(&Strain::S, &BridgeDirection::N, 10);
+            record.add_with_trick_table(board, table);
+        }
+        record
+    }
+
+    #[test]
+    fn html_pages() {
+        let pages = record(3).html_pages(&US_ENGLISH);
+
+        assert_eq!(2, pages.len());
+        assert!(pages[0].contains("<title>Hand Records</title>"));
+        assert!(pages[0].contains("Board 1"));
+        assert!(pages[0].contains("Board 2"));
+        assert!(pages[1].contains("Board 3"));
+        assert!(pages[0].contains("Vulnerable: None"));
+        assert!(pages[0].contains("Vulnerable: North-South"));
+        assert!(pages[1].contains("Vulnerable: East-West"));
+        assert!(pages[0].contains("<div class=\"seat\">North (14)</div>"));
+        assert!(pages[0].contains("<span class=\"suit red\">♥</span> J76"));
+        assert!(pages[0].contains("Makeable contracts"));
+        assert!(pages[0].contains("<tr><th>N</th><td>4</td><td>-</td>"));
+    }
+
+    #[test]
+    fn html_pages__localized() {
+        let pages = record(1).html_pages(&GERMAN);
+
+        assert!(pages[0].contains("<html lang=\"de\">"));
+        assert!(pages[0].contains("Nord (14)"));
+        assert!(pages[0].contains("Teiler: N"));
+        assert!(pages[0].contains("Gefahr: Keiner"));
+        assert!(pages[0].contains("Erfüllbare Kontrakte"));
+    }
+
+    #[test]
+    fn html_pages__without_annotations() {
+        let mut record = HandRecord::default();
+        record.add(BridgeBoard::from_pbn_deal(PBN_TEST_STRING));
+
+        let page = &record.html_pages(&US_ENGLISH)[0];
+
+        assert!(page.contains("<div class=\"seat\">North</div>"));
+        assert!(!page.contains("Makeable"));
+    }
+
+    #[test]
+    fn svg() {
+        let record = record(1);
+
+        let svg = record.svg(0, &US_ENGLISH).unwrap();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("<tspan fill=\"#c00\">♦</tspan> AQT943"));
+        assert!(svg.contains("South (12)"));
+        assert!(svg.contains("N 4 - - - -"));
+        assert!(record.svg(1, &US_ENGLISH).is_none());
+    }
+
+    #[test]
+    fn svg_pages() {
+        let pages = record(3).svg_pages(&US_ENGLISH);
+
+        assert_eq!(2, pages.len());
+        assert_eq!(2, pages[0].matches("<g transform").count());
+        assert!(pages[0].contains("translate(360,0)"));
+    }
+
+    #[test]
+    fn escape() {
+        assert_eq!("&lt;b&gt; &amp; &quot;", super::escape("<b> & \""));
+    }
+}
@@ -0,0 +1,444 @@
+use std::collections::HashMap;
+
+use crate::cards::decks::bridge::contract::Contract;
+use crate::cards::decks::bridge::movement::{Movement, MovementKind};
+use crate::cards::decks::bridge::{BridgeBoard, BridgeSide};
+
+/// The upper end of each IMP band, in points of score difference. A difference past the last
+/// band is worth 24 IMPs.
+const IMP_BANDS: [isize; 24] = [
+    10, 40, 80, 120, 160, 210, 260, 310, 360, 420, 490, 590, 740, 890, 1090, 1290, 1490, 1740,
+    1990, 2240, 2490, 2990, 3490, 3990,
+];
+
+/// Converts a difference in score to International Match Points, keeping its sign.
+pub fn imps(difference: isize) -> isize {
+    let imps = IMP_BANDS
+        .iter()
+        .position(|band| difference.abs() <= *band)
+        .unwrap_or(IMP_BANDS.len()) as isize;
+    imps * difference.signum()
+}
+
+/// How results on each board are compared to rank the pairs.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Scoring {
+    /// A point for each other result beaten on the board and half a point for each tie.
+    MATCHPOINTS,
+    /// The IMPs won against each other result on the board, averaged.
+    IMPS,
+}
+
+/// The result of one board between two pairs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoardResult {
+    pub board: usize,
+    pub ns: usize,
+    pub ew: usize,
+    /// The contract played, or None if the board was passed out.
+    pub contract: Option<Contract>,
+    /// The tricks taken by declarer.
+    pub tricks: usize,
+    /// The score from North-South's point of view.
+    pub score: isize,
+}
+
+impl BoardResult {
+    /// Scores the contract at the board's vulnerability.
+    pub fn new(
+        board: &BridgeBoard,
+        ns: usize,
+        ew: usize,
+        contract: Option<Contract>,
+        tricks: usize,
+    ) -> BoardResult {
+        let score = match &contract {
+            Some(contract) => {
+                let vulnerable = board.is_vulnerable(&contract.declarer);
+                let score = contract.score(tricks, vulnerable);
+                if contract.declarer.side() == Some(BridgeSide::NS) {
+                    score
+                } else {
+                    -score
+                }
+            }
+            None => 0,
+        };
+        BoardResult {
+            board: board.number,
+            ns,
+            ew,
+            contract,
+            tricks,
+            score,
+        }
+    }
+
+    pub fn passed_out(board: &BridgeBoard, ns: usize, ew: usize) -> BoardResult {
+        BoardResult::new(board, ns, ew, None, 0)
+    }
+}
+
+/// A pair's place in the session.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ranking {
+    pub pair: usize,
+    pub boards: usize,
+    /// Total matchpoints or IMPs.
+    pub score: f64,
+    /// The share of the available matchpoints, or None when scoring by IMPs.
+    pub percentage: Option<f64>,
+}
+
+/// A duplicate session: the movement, the boards in play and the results as they come in.
+///
+/// Results are kept by board and pairs, and checked against the movement so that only
+/// pairs who meet over a board can score it. Each pair is ranked on all the boards it has
+/// played, comparing its result on each one with the others on the same board. In a
+/// Mitchell the North-South and East-West pairs are compared only within their own field,
+/// since they never play the same board in the same direction.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BoardResult, BridgeDirection, Contract, Movement, Scoring, Session};
+///
+/// let mut session = Session::new(Movement::howell(8, 1).unwrap());
+/// let board = session.board(1).unwrap().clone();
+/// let four_spades = Contract::from_pbn("4S", BridgeDirection::N);
+///
+/// assert!(session.add_result(BoardResult::new(&board, 8, 1, four_spades, 10)));
+/// assert!(session.add_result(BoardResult::new(&board, 6, 2, four_spades, 9)));
+///
+/// let rankings = session.rankings(&Scoring::MATCHPOINTS);
+/// let tops: Vec<usize> = rankings
+///     .iter()
+///     .filter(|r| r.percentage == Some(100.0))
+///     .map(|r| r.pair)
+///     .collect();
+///
+/// assert_eq!(vec![2, 8], tops);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Session {
+    pub movement: Movement,
+    boards: Vec<BridgeBoard>,
+    results: HashMap<(usize, usize, usize), BoardResult>,
+}
+
+impl Session {
+    /// Starts a session with freshly dealt boards for the movement.
+    pub fn new(movement: Movement) -> Session {
+        let boards = (1..=movement.board_count())
+            .map(BridgeBoard::deal_board)
+            .collect();
+        Session {
+            movement,
+            boards,
+            results: HashMap::new(),
+        }
+    }
+
+    /// Starts a session with boards that have already been dealt, returning None unless
+    /// there is one for each board number in the movement.
+    pub fn with_boards(movement: Movement, boards: Vec<BridgeBoard>) -> Option<Session> {
+        let mut numbers: Vec<usize> = boards.iter().map(|b| b.number).collect();
+        numbers.sort_unstable();
+        if numbers != (1..=movement.board_count()).collect::<Vec<usize>>() {
+            return None;
+        }
+        Some(Session {
+            movement,
+            boards,
+            results: HashMap::new(),
+        })
+    }
+
+    pub fn board(&self, number: usize) -> Option<&BridgeBoard> {
+        self.boards.iter().find(|b| b.number == number)
+    }
+
+    pub fn boards(&self) -> &Vec<BridgeBoard> {
+        &self.boards
+    }
+
+    /// Records a result, replacing any earlier one for the same board and pairs. Returns
+    /// false if the pairs don't meet over the board in the movement.
+    pub fn add_result(&mut self, result: BoardResult) -> bool {
+        if !self.movement.plays(result.board, result.ns, result.ew) {
+            return false;
+        }
+        self.results
+            .insert((result.board, result.ns, result.ew), result);
+        true
+    }
+
+    pub fn result(&self, board: usize, ns: usize, ew: usize) -> Option<&BoardResult> {
+        self.results.get(&(board, ns, ew))
+    }
+
+    /// The results on a board, in order of the North-South pair.
+    pub fn board_results(&self, board: usize) -> Vec<&BoardResult> {
+        let mut results: Vec<&BoardResult> =
+            self.results.values().filter(|r| r.board == board).collect();
+        results.sort_by_key(|r| r.ns);
+        results
+    }
+
+    pub fn result_count(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Ranks the pairs from best to worst.
+    pub fn rankings(&self, scoring: &Scoring) -> Vec<Ranking> {
+        let mut totals: HashMap<usize, (usize, f64, f64)> = HashMap::new();
+        for board in 1..=self.movement.board_count() {
+            let results = self.board_results(board);
+            for result in results.iter() {
+                let others: Vec<isize> = results
+                    .iter()
+                    .filter(|r| !std::ptr::eq(**r, *result))
+                    .map(|r| r.score)
+                    .collect();
+                let (ns, top) = match scoring {
+                    Scoring::MATCHPOINTS => (matchpoints(result.score, &others), others.len()),
+                    Scoring::IMPS => (cross_imps(result.score, &others), 0),
+                };
+                let ew = match scoring {
+                    Scoring::MATCHPOINTS => top as f64 - ns,
+                    Scoring::IMPS => -ns,
+                };
+                for (pair, score) in [(result.ns, ns), (result.ew, ew)].iter() {
+                    let total = totals.entry(*pair).or_insert((0, 0.0, 0.0));
+                    total.0 += 1;
+                    total.1 += score;
+                    total.2 += top as f64;
+                }
+            }
+        }
+
+        let mut rankings: Vec<Ranking> = (1..=self.movement.pairs)
+            .map(|pair| {
+                let (boards, score, top) = totals.get(&pair).copied().unwrap_or((0, 0.0, 0.0));
+                let percentage = match scoring {
+                    Scoring::MATCHPOINTS if top > 0.0 => Some(score / top * 100.0),
+                    Scoring::MATCHPOINTS => Some(0.0),
+                    Scoring::IMPS => None,
+                };
+                Ranking {
+                    pair,
+                    boards,
+                    score,
+                    percentage,
+                }
+            })
+            .collect();
+        rankings.sort_by(|a, b| {
+            let a_value = a.percentage.unwrap_or(a.score);
+            let b_value = b.percentage.unwrap_or(b.score);
+            b_value
+                .partial_cmp(&a_value)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.pair.cmp(&b.pair))
+        });
+        rankings
+    }
+
+    /// Rankings for the pairs that sat in one direction, which is how a Mitchell is won.
+    /// For other movements this is the same as `rankings`, limited to pairs that played
+    /// North-South (or East-West) on at least one board.
+    pub fn field_rankings(&self, scoring: &Scoring, north_south: bool) -> Vec<Ranking> {
+        self.rankings(scoring)
+            .into_iter()
+            .filter(|ranking| {
+                self.movement.assignments.iter().any(|a| {
+                    if north_south {
+                        a.ns == ranking.pair
+                    } else {
+                        a.ew == ranking.pair
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Pairs the next round of a Swiss movement from the current rankings. Returns false
+    /// if the movement isn't Swiss or the round can't be paired.
+    pub fn pair_next_round(&mut self, scoring: &Scoring) -> bool {
+        if self.movement.kind != MovementKind::SWISS {
+            return false;
+        }
+        let standings: Vec<usize> = self.rankings(scoring).iter().map(|r| r.pair).collect();
+        self.movement.pair_swiss_round(&standings)
+    }
+}
+
+fn matchpoints(score: isize, others: &[isize]) -> f64 {
+    others
+        .iter()
+        .map(|other| match score.cmp(other) {
+            std::cmp::Ordering::Greater => 1.0,
+            std::cmp::Ordering::Equal => 0.5,
+            std::cmp::Ordering::Less => 0.0,
+        })
+        .sum()
+}
+
+fn cross_imps(score: isize, others: &[isize]) -> f64 {
+    if others.is_empty() {
+        return 0.0;
+    }
+    let total: isize = others.iter().map(|other| imps(score - other)).sum();
+    total as f64 / others.len() as f64
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_session_tests {
+    use super::*;
+    use crate::cards::decks::bridge::BridgeDirection;
+
+    fn contract(s: &str, declarer: BridgeDirection) -> Option<Contract> {
+        Contract::from_pbn(s, declarer)
+    }
+
+    #[test]
+    fn imps() {
+        assert_eq!(0, super::imps(10));
+        assert_eq!(1, super::imps(20));
+        assert_eq!(6, super::imps(220));
+        assert_eq!(-10, super::imps(-450));
+        assert_eq!(11, super::imps(620 - 50));
+        assert_eq!(12, super::imps(620 + 100));
+        assert_eq!(24, super::imps(4000));
+    }
+
+    #[test]
+    fn board_result__new() {
+        let mut board = BridgeBoard::default();
+        board.set_number(2);
+
+        let north = BoardResult::new(&board, 1, 2, contract("4S", BridgeDirection::N), 10);
+        let east = BoardResult::new(&board, 1, 2, contract("4SX", BridgeDirection::E), 8);
+        let passed = BoardResult::passed_out(&board, 1, 2);
+
+        assert_eq!(620, north.score);
+        assert_eq!(300, east.score);
+        assert_eq!(2, passed.board);
+        assert_eq!(0, passed.score);
+    }
+
+    #[test]
+    fn add_result__checks_movement() {
+        let mut session = Session::new(Movement::mitchell(6, 2).unwrap());
+        let board = session.board(1).unwrap().clone();
+
+        assert!(session.add_result(BoardResult::passed_out(&board, 1, 4)));
+        assert!(!session.add_result(BoardResult::passed_out(&board, 4, 1)));
+        assert!(!session.add_result(BoardResult::passed_out(&board, 1, 5)));
+        assert!(session.add_result(BoardResult::new(
+            &board,
+            1,
+            4,
+            contract("3NT", BridgeDirection::S),
+            9
+        )));
+
+        assert_eq!(1, session.result_count());
+        assert_eq!(400, session.result(1, 1, 4).unwrap().score);
+    }
+
+    #[test]
+    fn with_boards() {
+        let movement = Movement::howell(3, 1).unwrap();
+        let boards: Vec<BridgeBoard> = (1..=3).map(BridgeBoard::deal_board).collect();
+
+        assert!(Session::with_boards(movement.clone(), boards.clone()).is_some());
+        assert!(Session::with_boards(movement, boards[..2].to_vec()).is_none());
+    }
+
+    /// A three table Mitchell with every pair in 3NT, making more tricks the lower the
+    /// North-South pair number.
+    fn mitchell_session() -> Session {
+        let mut session = Session::new(Movement::mitchell(6, 1).unwrap());
+        for assignment in session.movement.assignments.clone() {
+            let board = session.board(assignment.boards[0]).unwrap().clone();
+            let tricks = 12 - assignment.ns;
+            session.add_result(BoardResult::new(
+                &board,
+                assignment.ns,
+                assignment.ew,
+                contract("3NT", BridgeDirection::N),
+                tricks,
+            ));
+        }
+        session
+    }
+
+    #[test]
+    fn rankings__matchpoints() {
+        let session = mitchell_session();
+
+        let rankings = session.rankings(&Scoring::MATCHPOINTS);
+
+        assert_eq!(6, rankings.len());
+        assert_eq!(1, rankings[0].pair);
+        assert_eq!(Some(100.0), rankings[0].percentage);
+        assert_eq!(3, rankings[0].boards);
+        assert_eq!(6.0, rankings[0].score);
+        assert_eq!(Some(50.0), rankings[1].percentage);
+        assert!(rankings[1..5].iter().all(|r| r.pair == 2 || r.pair > 3));
+        assert_eq!(3, rankings[5].pair);
+        assert_eq!(Some(0.0), rankings[5].percentage);
+    }
+
+    #[test]
+    fn rankings__imps() {
+        let session = mitchell_session();
+
+        let rankings = session.rankings(&Scoring::IMPS);
+
+        // 3NT+2 against +1 is 1 IMP and against = is 2 IMPs, on all three boards.
+        assert_eq!(1, rankings[0].pair);
+        assert_eq!(4.5, rankings[0].score);
+        assert_eq!(None, rankings[0].percentage);
+        assert_eq!(-4.5, rankings[5].score);
+    }
+
+    #[test]
+    fn field_rankings() {
+        let session = mitchell_session();
+
+        let north_south = session.field_rankings(&Scoring::MATCHPOINTS, true);
+        let east_west = session.field_rankings(&Scoring::MATCHPOINTS, false);
+
+        assert_eq!(
+            vec![1, 2, 3],
+            north_south.iter().map(|r| r.pair).collect::<Vec<_>>()
+        );
+        assert_eq!(3, east_west.len());
+        assert!(east_west.iter().all(|r| r.percentage == Some(50.0)));
+    }
+
+    #[test]
+    fn pair_next_round() {
+        let mut session = Session::new(Movement::swiss(4, 2, 1).unwrap());
+
+        assert!(session.pair_next_round(&Scoring::IMPS));
+        let board = session.board(1).unwrap().clone();
+        session.add_result(BoardResult::new(
+            &board,
+            3,
+            4,
+            contract("4H", BridgeDirection::S),
+            10,
+        ));
+        session.add_result(BoardResult::passed_out(&board, 1, 2));
+        assert!(session.pair_next_round(&Scoring::IMPS));
+
+        // Pairs 2 and 3 are both 10 IMPs up, so they meet in the second round.
+        assert!(session.movement.plays(2, 2, 3));
+        assert!(session.movement.plays(2, 1, 4));
+        assert!(!session.pair_next_round(&Scoring::IMPS));
+    }
+}
@@ -0,0 +1,272 @@
+use crate::cards::card::Card;
+use crate::cards::decks::bridge::{
+    rank_index, suit_index, BridgeBoard, BridgeDirection, BridgeSide, Vulnerability, BRIDGE_RANKS,
+    BRIDGE_SUITS,
+};
+use crate::cards::pile::Pile;
+
+/// Rotations, swaps and suit permutations of a BridgeBoard, and a canonical form that is
+/// the same for every deal they turn into each other.
+///
+/// Rotating or swapping seats moves the dealer and vulnerability along with the hands, so
+/// the board stays the same problem from each player's point of view. Suit permutations
+/// only change the cards.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeBoard, BridgeDirection};
+///
+/// let board = BridgeBoard::deal();
+/// let rotated = board.rotate_to_south(&BridgeDirection::W);
+///
+/// assert_eq!(board.west, rotated.south);
+/// assert_eq!(board.canonical(), rotated.swap_majors().canonical());
+/// assert!(board.is_equivalent(&rotated.swap_minors()));
+/// ```
+impl BridgeBoard {
+    /// Turns the board clockwise by a number of seats, so that after one turn East holds
+    /// what North held.
+    pub fn rotate(&self, turns: usize) -> BridgeBoard {
+        self.move_seats(|direction| {
+            let mut direction = *direction;
+            for _ in 0..turns % 4 {
+                direction = direction.lho();
+            }
+            direction
+        })
+    }
+
+    /// Rotates the board so that the seat's hand is held by South.
+    pub fn rotate_to_south(&self, seat: &BridgeDirection) -> BridgeBoard {
+        let turns = match seat {
+            BridgeDirection::N => 2,
+            BridgeDirection::E => 1,
+            BridgeDirection::W => 3,
+            _ => 0,
+        };
+        self.rotate(turns)
+    }
+
+    /// Swaps the partnerships, so that North and East exchange hands, as do South and West.
+    pub fn swap_sides(&self) -> BridgeBoard {
+        self.move_seats(|direction| match direction {
+            BridgeDirection::N => BridgeDirection::E,
+            BridgeDirection::E => BridgeDirection::N,
+            BridgeDirection::S => BridgeDirection::W,
+            BridgeDirection::W => BridgeDirection::S,
+            BridgeDirection::UNKNOWN => BridgeDirection::UNKNOWN,
+        })
+    }
+
+    /// Changes the suit of every card, where `suits[i]` is the position in `BRIDGE_SUITS`
+    /// that suit `i` becomes. Returns None unless `suits` is a permutation of 0 to 3.
+    pub fn permute_suits(&self, suits: &[usize; 4]) -> Option<BridgeBoard> {
+        let mut sorted = *suits;
+        sorted.sort_unstable();
+        if sorted != [0, 1, 2, 3] {
+            return None;
+        }
+        let mut board = self.clone();
+        for direction in BridgeDirection::seats().iter() {
+            let hand = self.get_hand(direction)?;
+            let cards: Vec<Card> = hand
+                .values()
+                .filter_map(|card| {
+                    let suit = suits[suit_index(card)?];
+                    Some(Card::new(
+                        BRIDGE_RANKS[rank_index(card)?],
+                        BRIDGE_SUITS[suit],
+                    ))
+                })
+                .collect();
+            *board.get_hand_mut(direction)? = Pile::new_from_vector(cards).sort();
+        }
+        Some(board)
+    }
+
+    /// Exchanges spades and hearts.
+    pub fn swap_majors(&self) -> BridgeBoard {
+        self.permute_suits(&[1, 0, 2, 3])
+            .unwrap_or_else(|| self.clone())
+    }
+
+    /// Exchanges diamonds and clubs.
+    pub fn swap_minors(&self) -> BridgeBoard {
+        self.permute_suits(&[0, 1, 3, 2])
+            .unwrap_or_else(|| self.clone())
+    }
+
+    /// Returns the board with the lowest deal number out of every rotation and suit
+    /// permutation of this one, or None if it isn't a complete deal. Equivalent deals have
+    /// the same canonical form, whatever their number, dealer or vulnerability, which are
+    /// reset to those of board one.
+    pub fn canonical(&self) -> Option<BridgeBoard> {
+        let mut best: Option<(u128, BridgeBoard)> = None;
+        for permutation in suit_permutations().iter() {
+            let permuted = self.permute_suits(permutation)?;
+            for turns in 0..4 {
+                let mut board = permuted.rotate(turns);
+                board.set_number(1);
+                let number = board.deal_number()?;
+                if best.as_ref().map_or(true, |(lowest, _)| number < *lowest) {
+                    best = Some((number, board));
+                }
+            }
+        }
+        best.map(|(_, board)| board)
+    }
+
+    /// Returns true if the deals are the same up to rotation and suit permutation.
+    pub fn is_equivalent(&self, other: &BridgeBoard) -> bool {
+        match (self.canonical(), other.canonical()) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Moves each seat's hand, and the dealer and vulnerability, to another seat.
+    fn move_seats<F>(&self, to: F) -> BridgeBoard
+    where
+        F: Fn(&BridgeDirection) -> BridgeDirection,
+    {
+        let mut board = self.clone();
+        for direction in BridgeDirection::seats().iter() {
+            if let (Some(hand), Some(moved)) =
+                (self.get_hand(direction), board.get_hand_mut(&to(direction)))
+            {
+                *moved = hand.clone();
+            }
+        }
+        board.dealer = to(&self.dealer);
+        if to(&BridgeDirection::N).side() != Some(BridgeSide::NS) {
+            board.vulnerability = match self.vulnerability {
+                Vulnerability::NS => Vulnerability::EW,
+                Vulnerability::EW => Vulnerability::NS,
+                vulnerability => vulnerability,
+            };
+        }
+        board
+    }
+}
+
+/// All 24 orderings of the four suits.
+fn suit_permutations() -> Vec<[usize; 4]> {
+    let mut permutations = Vec::new();
+    for a in 0..4 {
+        for b in (0..4).filter(|b| *b != a) {
+            for c in (0..4).filter(|c| *c != a && *c != b) {
+                permutations.push([a, b, c, 6 - a - b - c]);
+            }
+        }
+    }
+    permutations
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod bridge_transform_tests {
+    use super::*;
+
+    const PBN_TEST_STRING: &str =
+        "S:Q42.Q52.AQT943.Q 97.AT93.652.T743 AJT85.J76.KJ.A65 K63.K84.87.KJ982";
+
+    #[test]
+    fn rotate() {
+        let mut board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+        board.set_number(2);
+
+        let rotated = board.rotate(1);
+
+        assert_eq!(board.north, rotated.east);
+        assert_eq!(board.east, rotated.south);
+        assert_eq!(board.south, rotated.west);
+        assert_eq!(board.west, rotated.north);
+        assert_eq!(BridgeDirection::S, rotated.dealer);
+        assert_eq!(Vulnerability::EW, rotated.vulnerability);
+        assert_eq!(board, board.rotate(4));
+        assert_eq!(board, rotated.rotate(3));
+    }
+
+    #[test]
+    fn rotate_to_south() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+
+        for seat in BridgeDirection::seats().iter() {
+            let rotated = board.rotate_to_south(seat);
+            assert_eq!(board.get_hand(seat), Some(&rotated.south));
+        }
+    }
+
+    #[test]
+    fn swap_sides() {
+        let mut board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+        board.set_number(3);
+
+        let swapped = board.swap_sides();
+
+        assert_eq!(board.north, swapped.east);
+        assert_eq!(board.east, swapped.north);
+        assert_eq!(board.south, swapped.west);
+        assert_eq!(board.west, swapped.south);
+        assert_eq!(BridgeDirection::W, swapped.dealer);
+        assert_eq!(Vulnerability::NS, swapped.vulnerability);
+        assert_eq!(board, swapped.swap_sides());
+    }
+
+    #[test]
+    fn swap_majors_and_minors() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+
+        assert_eq!(
+            "S:Q52.Q42.Q.AQT943 AT93.97.T743.652 J76.AJT85.A65.KJ K84.K63.KJ982.87",
+            board.swap_majors().swap_minors().to_pbn_deal()
+        );
+        assert_eq!(board, board.swap_majors().swap_majors());
+    }
+
+    #[test]
+    fn permute_suits() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+
+        let permuted = board.permute_suits(&[3, 0, 1, 2]).unwrap();
+
+        assert_eq!(
+            "S:Q52.AQT943.Q.Q42 AT93.652.T743.97 J76.KJ.A65.AJT85 K84.87.KJ982.K63",
+            permuted.to_pbn_deal()
+        );
+        assert!(permuted.is_valid());
+        assert!(board.permute_suits(&[0, 0, 1, 2]).is_none());
+    }
+
+    #[test]
+    fn suit_permutations() {
+        let permutations = super::suit_permutations();
+
+        assert_eq!(24, permutations.len());
+        assert_eq!([0, 1, 2, 3], permutations[0]);
+    }
+
+    #[test]
+    fn canonical() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+        let canonical = board.canonical().unwrap();
+
+        for turns in 0..4 {
+            let rotated = board.rotate(turns).permute_suits(&[2, 3, 0, 1]).unwrap();
+            assert_eq!(Some(&canonical), rotated.canonical().as_ref());
+        }
+        assert_eq!(canonical, canonical.canonical().unwrap());
+        assert!(canonical.is_valid());
+        assert_eq!(1, canonical.number);
+    }
+
+    #[test]
+    fn is_equivalent() {
+        let board = BridgeBoard::from_pbn_deal(PBN_TEST_STRING);
+
+        assert!(board.is_equivalent(&board.rotate(2).swap_majors()));
+        assert!(!board.is_equivalent(&board.swap_sides()));
+        assert!(!board.is_equivalent(&BridgeBoard::from_deal_number(0).unwrap()));
+        assert!(!board.is_equivalent(&BridgeBoard::default()));
+    }
+}
@@ -0,0 +1,729 @@
+use crate::cards::card::Card;
+use crate::cards::decks::bridge::{suit_index, BridgeDirection, BridgeSide, BRIDGE_SUITS};
+use crate::cards::pile::Pile;
+use crate::cards::rank::*;
+use crate::cards::suit::TRUMP;
+use crate::Named;
+
+/// The number of points needed to win a game of Euchre.
+pub const EUCHRE_GAME: usize = 10;
+
+/// The ranks of the plain suits from highest to lowest. In trumps the jacks move to the top.
+const EUCHRE_RANKS: [&str; 6] = [ACE, KING, QUEEN, JACK, TEN, NINE];
+
+/// Position in `BRIDGE_SUITS` of the other suit of the same colour: spades and clubs are
+/// black, hearts and diamonds red.
+fn same_colour(suit: usize) -> usize {
+    3 - suit
+}
+
+/// How strong a card is in a trick with the passed in trumps and suit led, where cards that
+/// are neither are worth nothing.
+fn power(card: &Card, trump: usize, led: usize) -> usize {
+    let suit = EuchreTrick::suit_of(card, trump);
+    if suit == Some(trump) {
+        let order = EuchreTrick::trump_order(trump);
+        return 100 - order.iter().position(|c| c == card).unwrap_or(100);
+    }
+    if suit == Some(led) {
+        let rank = EUCHRE_RANKS.iter().position(|r| *r == card.rank.name());
+        return 50 - rank.unwrap_or(50);
+    }
+    0
+}
+
+/// A trick of Euchre: the seats in the order they played and their cards. When a player
+/// goes alone their partner sits out, so the trick only has three cards.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EuchreTrick {
+    pub leader: BridgeDirection,
+    pub plays: Vec<(BridgeDirection, Card)>,
+}
+
+impl EuchreTrick {
+    /// The suit, by its position in `BRIDGE_SUITS`, that the card belongs to once trumps are
+    /// known. The jack of the same colour as trumps, the left bower, and the joker, the best
+    /// bower, are both trumps.
+    pub fn suit_of(card: &Card, trump: usize) -> Option<usize> {
+        if card.rank.name() == BIG_JOKER {
+            return Some(trump);
+        }
+        let suit = suit_index(card)?;
+        if card.rank.name() == JACK && suit == same_colour(trump) {
+            Some(trump)
+        } else {
+            Some(suit)
+        }
+    }
+
+    /// The trumps from highest to lowest: the joker, the jack of trumps (the right bower), the
+    /// jack of the same colour (the left bower), then the ace down to the nine.
+    pub fn trump_order(trump: usize) -> Vec<Card> {
+        let mut cards = vec![
+            Card::new(BIG_JOKER, TRUMP),
+            Card::new(JACK, BRIDGE_SUITS[trump]),
+            Card::new(JACK, BRIDGE_SUITS[same_colour(trump)]),
+        ];
+        cards.extend(
+            EUCHRE_RANKS
+                .iter()
+                .filter(|rank| **rank != JACK)
+                .map(|rank| Card::new(rank, BRIDGE_SUITS[trump])),
+        );
+        cards
+    }
+
+    pub fn new(leader: BridgeDirection) -> EuchreTrick {
+        EuchreTrick {
+            leader,
+            plays: Vec::new(),
+        }
+    }
+
+    /// The suit led, counting the left bower and joker as trumps.
+    pub fn led_suit(&self, trump: usize) -> Option<usize> {
+        self.plays
+            .first()
+            .and_then(|(_, card)| EuchreTrick::suit_of(card, trump))
+    }
+
+    /// Returns the seat whose card is winning the trick so far.
+    pub fn winner(&self, trump: usize) -> Option<BridgeDirection> {
+        let led = self.led_suit(trump)?;
+        self.plays
+            .iter()
+            .max_by_key(|(_, card)| power(card, trump, led))
+            .map(|(seat, _)| *seat)
+    }
+}
+
+/// A single deal of Euchre, from making trumps through the five tricks.
+///
+/// Each player gets five cards and the top card of the rest, the upcard, is turned over.
+/// Starting on the dealer's left each player may pass or order it up, making its suit trumps
+/// and sending it to the dealer, who discards a card in its place. If all four pass it is
+/// turned down, and on a second round each player may pass or call any other suit. If the
+/// joker is turned up it can't be ordered up, and on the first round the players name any
+/// suit instead. The maker may go alone, when their partner sits out the hand.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeDirection, EuchreHand, Pile};
+///
+/// let mut hand = EuchreHand::deal(BridgeDirection::N, &Pile::euchre_deck());
+///
+/// assert_eq!(BridgeDirection::E, hand.to_act());
+/// assert!(hand.is_bidding());
+///
+/// while hand.is_bidding() {
+///     hand.pass();
+/// }
+/// assert!(hand.is_passed_out());
+/// assert_eq!(None, hand.result());
+/// ```
+#[derive(Clone, Debug)]
+pub struct EuchreHand {
+    pub dealer: BridgeDirection,
+    hands: [Pile; 4],
+    kitty: Pile,
+    passes: usize,
+    trump: Option<usize>,
+    maker: Option<BridgeDirection>,
+    alone: bool,
+    discarding: bool,
+    tricks: Vec<EuchreTrick>,
+}
+
+impl EuchreHand {
+    /// Starts a hand with the cards each seat holds, in N E S W order, and the rest of the
+    /// pack with the upcard on top.
+    pub fn new(dealer: BridgeDirection, hands: [Pile; 4], kitty: Pile) -> EuchreHand {
+        EuchreHand {
+            dealer,
+            hands,
+            kitty,
+            passes: 0,
+            trump: None,
+            maker: None,
+            alone: false,
+            discarding: false,
+            tricks: Vec::new(),
+        }
+    }
+
+    /// Shuffles the pack and deals five cards to each player.
+    pub fn deal(dealer: BridgeDirection, pack: &Pile) -> EuchreHand {
+        let mut cards = pack.shuffle();
+        let mut hands: [Pile; 4] = Default::default();
+        for hand in hands.iter_mut() {
+            *hand = cards.draw(5).unwrap_or_default();
+            hand.sort_in_place();
+        }
+        EuchreHand::new(dealer, hands, cards)
+    }
+
+    pub fn hand(&self, seat: &BridgeDirection) -> Option<&Pile> {
+        self.hands.get(seat_position(seat)?)
+    }
+
+    pub fn upcard(&self) -> Option<&Card> {
+        self.kitty.first()
+    }
+
+    /// Position in `BRIDGE_SUITS` of trumps, once they've been made.
+    pub fn trump(&self) -> Option<usize> {
+        self.trump
+    }
+
+    pub fn maker(&self) -> Option<BridgeDirection> {
+        self.maker
+    }
+
+    pub fn is_alone(&self) -> bool {
+        self.alone
+    }
+
+    /// The maker's partner when the maker goes alone.
+    pub fn sitting_out(&self) -> Option<BridgeDirection> {
+        self.maker.filter(|_| self.alone).map(|m| m.partner())
+    }
+
+    pub fn is_bidding(&self) -> bool {
+        self.trump.is_none() && !self.is_passed_out()
+    }
+
+    /// Returns true once all four players have passed in both rounds.
+    pub fn is_passed_out(&self) -> bool {
+        self.passes == 8
+    }
+
+    /// One while the upcard can be ordered up, two once it has been turned down.
+    pub fn round(&self) -> usize {
+        if self.passes < 4 {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// The seat that must pass, make trumps, discard or play next.
+    pub fn to_act(&self) -> BridgeDirection {
+        if self.is_bidding() {
+            self.seat_after_dealer(self.passes % 4)
+        } else if self.discarding {
+            self.dealer
+        } else {
+            self.to_play()
+        }
+    }
+
+    pub fn pass(&mut self) -> bool {
+        if !self.is_bidding() {
+            return false;
+        }
+        self.passes += 1;
+        true
+    }
+
+    /// Orders up the upcard, making its suit trumps. Returns false if it's not the first
+    /// round or the upcard is the joker.
+    pub fn order_up(&mut self, alone: bool) -> bool {
+        let suit = match self.upcard() {
+            Some(card) if card.rank.name() != BIG_JOKER => suit_index(card),
+            _ => None,
+        };
+        match suit {
+            Some(suit) if self.is_bidding() && self.round() == 1 => {
+                self.make(suit, alone, true);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Names trumps, by position in `BRIDGE_SUITS`. On the second round any suit but the
+    /// upcard's can be called; on the first only when the joker was turned up, which the
+    /// dealer then picks up.
+    pub fn call(&mut self, suit: usize, alone: bool) -> bool {
+        if !self.is_bidding() || suit >= 4 {
+            return false;
+        }
+        let upcard = self.upcard().cloned();
+        let joker = upcard
+            .as_ref()
+            .map_or(false, |card| card.rank.name() == BIG_JOKER);
+        let allowed = match self.round() {
+            1 => joker,
+            _ => upcard.as_ref().and_then(suit_index) != Some(suit),
+        };
+        if allowed {
+            self.make(suit, alone, self.round() == 1);
+        }
+        allowed
+    }
+
+    fn make(&mut self, suit: usize, alone: bool, pick_up: bool) {
+        let maker = self.to_act();
+        self.trump = Some(suit);
+        self.maker = Some(maker);
+        self.alone = alone;
+
+        // The dealer doesn't pick up the upcard when sitting out their partner's lone hand.
+        if pick_up && self.sitting_out() != Some(self.dealer) {
+            if let (Some(card), Some(position)) =
+                (self.kitty.draw_first(), seat_position(&self.dealer))
+            {
+                self.hands[position].add(card);
+                self.discarding = true;
+            }
+        }
+    }
+
+    /// The dealer puts a card face down after picking up the upcard.
+    pub fn discard(&mut self, card: &Card) -> bool {
+        let position = match seat_position(&self.dealer) {
+            Some(position) if self.discarding => position,
+            _ => return false,
+        };
+        match self.hands[position].remove_card(card) {
+            Some(card) => {
+                self.kitty.add(card);
+                self.discarding = false;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.trump.is_some() && !self.discarding && !self.is_complete()
+    }
+
+    /// All of the tricks played so far, including the one in progress.
+    pub fn tricks(&self) -> &Vec<EuchreTrick> {
+        &self.tricks
+    }
+
+    /// Returns the trick in progress, if any cards have been played to it.
+    pub fn current_trick(&self) -> Option<&EuchreTrick> {
+        self.tricks.last().filter(|t| !self.is_trick_complete(t))
+    }
+
+    /// The seat that led, or is about to lead, the current trick. The first trick is led
+    /// from the dealer's left.
+    pub fn leader(&self) -> BridgeDirection {
+        match self.tricks.last() {
+            None => self.next_player(&self.dealer),
+            Some(trick) if self.is_trick_complete(trick) => self.trick_winner(trick),
+            Some(trick) => trick.leader,
+        }
+    }
+
+    pub fn to_play(&self) -> BridgeDirection {
+        match self.current_trick().and_then(|t| t.plays.last()) {
+            Some((seat, _)) => self.next_player(seat),
+            None => self.leader(),
+        }
+    }
+
+    /// Returns the cards the player to play may play: any card when leading, otherwise the
+    /// cards of the suit led, with the left bower counted as a trump, if they hold any.
+    pub fn legal_plays(&self) -> Pile {
+        let (hand, trump) = match (self.hand(&self.to_play()), self.trump) {
+            (Some(hand), Some(trump)) if self.is_playing() => (hand, trump),
+            _ => return Pile::default(),
+        };
+        let led = self.current_trick().and_then(|t| t.led_suit(trump));
+        let following: Vec<Card> = hand
+            .values()
+            .filter(|c| led.is_some() && EuchreTrick::suit_of(c, trump) == led)
+            .cloned()
+            .collect();
+
+        if following.is_empty() {
+            hand.clone()
+        } else {
+            Pile::new_from_vector(following)
+        }
+    }
+
+    pub fn is_legal(&self, card: &Card) -> bool {
+        self.legal_plays().contains(card)
+    }
+
+    /// Plays a card for the seat whose turn it is. Returns false, leaving the hand unchanged,
+    /// if the card isn't a legal play.
+    pub fn play(&mut self, card: &Card) -> bool {
+        if !self.is_legal(card) {
+            return false;
+        }
+        let seat = self.to_play();
+        if self.current_trick().is_none() {
+            self.tricks.push(EuchreTrick::new(seat));
+        }
+        if let Some(position) = seat_position(&seat) {
+            self.hands[position].remove_card(card);
+        }
+        if let Some(trick) = self.tricks.last_mut() {
+            trick.plays.push((seat, card.clone()));
+        }
+        true
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.tricks.len() == 5 && self.tricks.iter().all(|t| self.is_trick_complete(t))
+    }
+
+    pub fn tricks_won(&self, side: &BridgeSide) -> usize {
+        self.tricks
+            .iter()
+            .filter(|t| self.is_trick_complete(t))
+            .filter(|t| self.trick_winner(t).side().as_ref() == Some(side))
+            .count()
+    }
+
+    /// The side that scores and how many points, once the hand is over. The makers score
+    /// one point for three or four tricks and two for all five, or four going alone. If they
+    /// take fewer than three they are euchred, and the defenders score two.
+    pub fn result(&self) -> Option<(BridgeSide, usize)> {
+        if !self.is_complete() {
+            return None;
+        }
+        let makers = self.maker?.side()?;
+        match self.tricks_won(&makers) {
+            5 if self.alone => Some((makers, 4)),
+            5 => Some((makers, 2)),
+            3 | 4 => Some((makers, 1)),
+            _ => Some((makers.opponents(), 2)),
+        }
+    }
+
+    fn seat_after_dealer(&self, n: usize) -> BridgeDirection {
+        self.dealer
+            .clockwise()
+            .get((n + 1) % 4)
+            .copied()
+            .unwrap_or(BridgeDirection::UNKNOWN)
+    }
+
+    /// The next seat on the left that is taking part in the hand.
+    fn next_player(&self, seat: &BridgeDirection) -> BridgeDirection {
+        let next = seat.lho();
+        if Some(next) == self.sitting_out() {
+            next.lho()
+        } else {
+            next
+        }
+    }
+
+    fn is_trick_complete(&self, trick: &EuchreTrick) -> bool {
+        let players = if self.alone { 3 } else { 4 };
+        trick.plays.len() == players
+    }
+
+    fn trick_winner(&self, trick: &EuchreTrick) -> BridgeDirection {
+        self.trump
+            .and_then(|trump| trick.winner(trump))
+            .unwrap_or(BridgeDirection::UNKNOWN)
+    }
+}
+
+fn seat_position(seat: &BridgeDirection) -> Option<usize> {
+    BridgeDirection::seats().iter().position(|d| d == seat)
+}
+
+/// A game of Euchre between North-South and East-West, played to `EUCHRE_GAME` points with
+/// the deal passing to the left after each hand.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{BridgeDirection, BridgeSide, EuchreGame};
+///
+/// let mut game = EuchreGame::new(BridgeDirection::S);
+/// let mut hand = game.deal();
+/// while hand.is_bidding() {
+///     hand.pass();
+/// }
+///
+/// assert!(game.record(&hand));
+/// assert_eq!(BridgeDirection::W, game.dealer);
+/// assert_eq!(0, game.score(&BridgeSide::NS));
+/// assert_eq!(None, game.winner());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct EuchreGame {
+    pub dealer: BridgeDirection,
+    scores: [usize; 2],
+}
+
+impl EuchreGame {
+    pub fn new(dealer: BridgeDirection) -> EuchreGame {
+        EuchreGame {
+            dealer,
+            scores: [0, 0],
+        }
+    }
+
+    /// Deals the next hand from `Pile::euchre_deck()`.
+    pub fn deal(&self) -> EuchreHand {
+        EuchreHand::deal(self.dealer, &Pile::euchre_deck())
+    }
+
+    pub fn score(&self, side: &BridgeSide) -> usize {
+        self.scores[side_position(side)]
+    }
+
+    /// Adds the result of a finished or passed out hand and moves the deal to the left.
+    /// Returns false if the hand is still going on, or the game is already over.
+    pub fn record(&mut self, hand: &EuchreHand) -> bool {
+        if self.winner().is_some() || !(hand.is_complete() || hand.is_passed_out()) {
+            return false;
+        }
+        if let Some((side, points)) = hand.result() {
+            self.scores[side_position(&side)] += points;
+        }
+        self.dealer = self.dealer.lho();
+        true
+    }
+
+    /// The side that has reached `EUCHRE_GAME` points.
+    pub fn winner(&self) -> Option<BridgeSide> {
+        [BridgeSide::NS, BridgeSide::EW]
+            .iter()
+            .find(|side| self.score(side) >= EUCHRE_GAME)
+            .copied()
+    }
+}
+
+fn side_position(side: &BridgeSide) -> usize {
+    match side {
+        BridgeSide::NS => 0,
+        BridgeSide::EW => 1,
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod euchre_tests {
+    use super::*;
+    use crate::cards::suit::*;
+
+    fn pile(cards: &[(&'static str, &'static str)]) -> Pile {
+        Pile::new_from_vector(
+            cards
+                .iter()
+                .map(|(rank, suit)| Card::new(rank, suit))
+                .collect(),
+        )
+    }
+
+    /// North deals. East holds the bowers, and the nine of hearts is turned up.
+    fn hand() -> EuchreHand {
+        let hands = [
+            pile(&[
+                (ACE, SPADES),
+                (KING, SPADES),
+                (ACE, CLUBS),
+                (KING, CLUBS),
+                (TEN, DIAMONDS),
+            ]),
+            pile(&[
+                (JACK, HEARTS),
+                (JACK, DIAMONDS),
+                (ACE, HEARTS),
+                (KING, HEARTS),
+                (QUEEN, HEARTS),
+            ]),
+            pile(&[
+                (QUEEN, SPADES),
+                (JACK, SPADES),
+                (QUEEN, CLUBS),
+                (JACK, CLUBS),
+                (NINE, DIAMONDS),
+            ]),
+            pile(&[
+                (TEN, SPADES),
+                (NINE, SPADES),
+                (TEN, CLUBS),
+                (NINE, CLUBS),
+                (ACE, DIAMONDS),
+            ]),
+        ];
+        let kitty = pile(&[
+            (NINE, HEARTS),
+            (TEN, HEARTS),
+            (KING, DIAMONDS),
+            (QUEEN, DIAMONDS),
+        ]);
+        EuchreHand::new(BridgeDirection::N, hands, kitty)
+    }
+
+    #[test]
+    fn trick__suit_of() {
+        let left = Card::new(JACK, DIAMONDS);
+
+        assert_eq!(Some(1), EuchreTrick::suit_of(&left, 1));
+        assert_eq!(Some(2), EuchreTrick::suit_of(&left, 0));
+        assert_eq!(
+            Some(3),
+            EuchreTrick::suit_of(&Card::new(BIG_JOKER, TRUMP), 3)
+        );
+        assert_eq!(Some(0), EuchreTrick::suit_of(&Card::new(JACK, CLUBS), 0));
+    }
+
+    #[test]
+    fn trick__trump_order() {
+        let order = EuchreTrick::trump_order(3);
+
+        assert_eq!(8, order.len());
+        assert_eq!(Card::new(JACK, CLUBS), order[1]);
+        assert_eq!(Card::new(JACK, SPADES), order[2]);
+        assert_eq!(Card::new(NINE, CLUBS), order[7]);
+    }
+
+    #[test]
+    fn trick__winner() {
+        let mut trick = EuchreTrick::new(BridgeDirection::N);
+        trick.plays = vec![
+            (BridgeDirection::N, Card::new(ACE, HEARTS)),
+            (BridgeDirection::E, Card::new(JACK, DIAMONDS)),
+            (BridgeDirection::S, Card::new(KING, HEARTS)),
+            (BridgeDirection::W, Card::new(ACE, SPADES)),
+        ];
+
+        assert_eq!(Some(BridgeDirection::E), trick.winner(1));
+        assert_eq!(Some(BridgeDirection::W), trick.winner(0));
+        assert_eq!(Some(BridgeDirection::N), trick.winner(3));
+    }
+
+    #[test]
+    fn hand__order_up() {
+        let mut hand = hand();
+
+        assert_eq!(BridgeDirection::E, hand.to_act());
+        assert!(hand.order_up(false));
+        assert_eq!(Some(1), hand.trump());
+        assert_eq!(Some(BridgeDirection::E), hand.maker());
+        assert_eq!(BridgeDirection::N, hand.to_act());
+        assert_eq!(6, hand.hand(&BridgeDirection::N).unwrap().len());
+        assert!(!hand.is_playing());
+
+        assert!(!hand.play(&Card::new(JACK, HEARTS)));
+        assert!(!hand.discard(&Card::new(NINE, SPADES)));
+        assert!(hand.discard(&Card::new(TEN, DIAMONDS)));
+        assert!(hand.is_playing());
+        assert_eq!(BridgeDirection::E, hand.to_play());
+    }
+
+    #[test]
+    fn hand__call() {
+        let mut hand = hand();
+
+        assert!(!hand.call(0, false));
+        for _ in 0..4 {
+            assert!(hand.pass());
+        }
+        assert_eq!(2, hand.round());
+        assert!(!hand.order_up(false));
+        assert!(!hand.call(1, false));
+        assert!(hand.call(0, false));
+        assert_eq!(Some(BridgeDirection::E), hand.maker());
+        assert!(hand.is_playing());
+        assert_eq!(5, hand.hand(&BridgeDirection::N).unwrap().len());
+    }
+
+    #[test]
+    fn hand__joker_upcard() {
+        let mut hand = hand();
+        hand.kitty = pile(&[(BIG_JOKER, TRUMP), (TEN, HEARTS)]);
+
+        assert!(!hand.order_up(false));
+        assert!(hand.call(3, false));
+        assert!(hand
+            .hand(&BridgeDirection::N)
+            .unwrap()
+            .contains(&Card::new(BIG_JOKER, TRUMP)));
+    }
+
+    #[test]
+    fn hand__follow_suit_with_left_bower() {
+        let mut hand = hand();
+        hand.order_up(false);
+        hand.discard(&Card::new(TEN, DIAMONDS));
+
+        // East leads the left bower, which is a heart, so North must follow with the nine.
+        assert!(hand.play(&Card::new(JACK, DIAMONDS)));
+        assert_eq!(BridgeDirection::S, hand.to_play());
+        assert!(hand.play(&Card::new(NINE, DIAMONDS)));
+        assert!(hand.play(&Card::new(ACE, DIAMONDS)));
+        assert_eq!(BridgeDirection::N, hand.to_play());
+        assert_eq!(1, hand.legal_plays().len());
+        assert!(!hand.play(&Card::new(ACE, SPADES)));
+        assert!(hand.play(&Card::new(NINE, HEARTS)));
+
+        assert_eq!(1, hand.tricks_won(&BridgeSide::EW));
+        assert_eq!(BridgeDirection::E, hand.to_play());
+    }
+
+    #[test]
+    fn hand__alone_march() {
+        let mut hand = hand();
+        assert!(hand.order_up(true));
+        hand.discard(&Card::new(TEN, DIAMONDS));
+
+        assert_eq!(Some(BridgeDirection::W), hand.sitting_out());
+        while !hand.is_complete() {
+            assert_ne!(BridgeDirection::W, hand.to_play());
+            let card = hand.legal_plays().get(0).unwrap().clone();
+            assert!(hand.play(&card));
+        }
+
+        assert!(hand.tricks().iter().all(|t| t.plays.len() == 3));
+        assert_eq!(Some((BridgeSide::EW, 4)), hand.result());
+    }
+
+    #[test]
+    fn hand__partner_of_dealer_alone() {
+        let mut hand = hand();
+        hand.pass();
+
+        assert_eq!(BridgeDirection::S, hand.to_act());
+        assert!(hand.order_up(true));
+        assert!(hand.is_playing());
+        assert_eq!(5, hand.hand(&BridgeDirection::N).unwrap().len());
+        assert_eq!(BridgeDirection::E, hand.to_play());
+    }
+
+    #[test]
+    fn game() {
+        let mut game = EuchreGame::new(BridgeDirection::N);
+        let mut hand = hand();
+        hand.order_up(true);
+        hand.discard(&Card::new(TEN, DIAMONDS));
+
+        assert!(!game.record(&hand));
+        while !hand.is_complete() {
+            let card = hand.legal_plays().get(0).unwrap().clone();
+            hand.play(&card);
+        }
+        for _ in 0..3 {
+            assert!(game.record(&hand));
+        }
+
+        assert_eq!(12, game.score(&BridgeSide::EW));
+        assert_eq!(Some(BridgeSide::EW), game.winner());
+        assert_eq!(BridgeDirection::W, game.dealer);
+        assert!(!game.record(&hand));
+    }
+
+    #[test]
+    fn deal() {
+        let hand = EuchreHand::deal(BridgeDirection::W, &Pile::euchre_deck());
+
+        for seat in BridgeDirection::seats() {
+            assert_eq!(5, hand.hand(&seat).unwrap().len());
+        }
+        assert_eq!(5, hand.kitty.len());
+        assert_eq!(BridgeDirection::N, hand.to_act());
+    }
+}
@@ -1 +1,4 @@
 pub mod bridge;
+pub mod euchre;
+pub mod pinochle;
+pub mod skat;
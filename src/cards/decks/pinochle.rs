@@ -0,0 +1,291 @@
+use crate::cards::decks::bridge::suit_index;
+use crate::cards::pile::Pile;
+use crate::cards::rank::*;
+use crate::Named;
+
+/// The ranks of a Pinochle suit from highest to lowest.
+const PINOCHLE_RANKS: [&str; 6] = [ACE, TEN, KING, QUEEN, JACK, NINE];
+
+/// A combination of cards scored in the meld. Runs, pinochles and arounds carry how many of
+/// them there are, where two is a double. Marriages carry their suit, by its position in
+/// `BRIDGE_SUITS`, and are royal when it is trumps.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Meld {
+    /// The ace, ten, king, queen and jack of trumps.
+    RUN(usize),
+    /// The king and queen of a suit.
+    MARRIAGE(usize),
+    /// The nine of trumps.
+    DIX,
+    /// The queen of spades and jack of diamonds.
+    PINOCHLE(usize),
+    /// A card of the rank in every suit. Only aces, kings, queens and jacks count.
+    AROUND(&'static str, usize),
+}
+
+impl Meld {
+    /// Finds every meld in a hand with the passed in trumps.
+    ///
+    /// A card can be part of melds of different kinds, such as the queen of spades in a
+    /// pinochle and in queens around, but can't be used twice in the same kind. Runs,
+    /// marriages and the dix are one kind, so the king and queen of a run only make a royal
+    /// marriage as well if there's another pair of them.
+    pub fn find(hand: &Pile, trump: usize) -> Vec<Meld> {
+        let counts = counts(hand);
+        let count = |rank: &str, suit: usize| counts[suit][rank_position(rank)];
+        let mut melds = Vec::new();
+
+        let runs = [ACE, TEN, KING, QUEEN, JACK]
+            .iter()
+            .map(|rank| count(rank, trump))
+            .min()
+            .unwrap_or(0);
+        if runs > 0 {
+            melds.push(Meld::RUN(runs));
+        }
+        for suit in 0..4 {
+            let used = if suit == trump { runs } else { 0 };
+            let marriages = count(KING, suit).min(count(QUEEN, suit)) - used;
+            melds.extend((0..marriages).map(|_| Meld::MARRIAGE(suit)));
+        }
+        melds.extend((0..count(NINE, trump)).map(|_| Meld::DIX));
+
+        let pinochles = count(QUEEN, 0).min(count(JACK, 2));
+        if pinochles > 0 {
+            melds.push(Meld::PINOCHLE(pinochles));
+        }
+
+        for rank in [ACE, KING, QUEEN, JACK].iter() {
+            let arounds = (0..4).map(|suit| count(rank, suit)).min().unwrap_or(0);
+            if arounds > 0 {
+                melds.push(Meld::AROUND(rank, arounds));
+            }
+        }
+        melds
+    }
+}
+
+/// The number of each card, by suit in `BRIDGE_SUITS` order and rank in `PINOCHLE_RANKS`
+/// order.
+fn counts(hand: &Pile) -> [[usize; 6]; 4] {
+    let mut counts = [[0; 6]; 4];
+    for card in hand.values() {
+        let rank = PINOCHLE_RANKS.iter().position(|r| *r == card.rank.name());
+        if let (Some(suit), Some(rank)) = (suit_index(card), rank) {
+            counts[suit][rank] += 1;
+        }
+    }
+    counts
+}
+
+fn rank_position(rank: &str) -> usize {
+    PINOCHLE_RANKS.iter().position(|r| *r == rank).unwrap_or(0)
+}
+
+/// The points for each meld and for the cards taken in tricks. Single and double arounds
+/// are in ace, king, queen, jack order.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{Meld, MeldTable, Pile};
+///
+/// let hand = Pile::pinochle_deck()
+///     .pile_by_index(&["AS", "TS", "KS", "QS", "JS", "QS", "JD", "9S", "KH", "QH", "AC", "9D"])
+///     .unwrap();
+///
+/// // Spades are trumps.
+/// assert_eq!(
+///     vec![Meld::RUN(1), Meld::MARRIAGE(1), Meld::DIX, Meld::PINOCHLE(1)],
+///     Meld::find(&hand, 0)
+/// );
+/// assert_eq!(15 + 2 + 1 + 4, MeldTable::partnership().meld(&hand, 0));
+/// assert_eq!(150 + 20 + 10 + 40, MeldTable::single_deck().meld(&hand, 0));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MeldTable {
+    pub run: usize,
+    pub double_run: usize,
+    pub royal_marriage: usize,
+    pub marriage: usize,
+    pub dix: usize,
+    pub pinochle: usize,
+    pub double_pinochle: usize,
+    pub arounds: [usize; 4],
+    pub double_arounds: [usize; 4],
+    /// The points for each counter taken in tricks.
+    pub counter: usize,
+    /// The points for taking the last trick.
+    pub last_trick: usize,
+}
+
+impl MeldTable {
+    /// Returns the number of counters, the aces, tens and kings, in a pile of cards.
+    pub fn counters(pile: &Pile) -> usize {
+        pile.values()
+            .filter(|card| [ACE, TEN, KING].contains(&card.rank.name()))
+            .count()
+    }
+
+    /// The traditional values for two and three handed Pinochle, where the counters are worth
+    /// ten each.
+    pub fn single_deck() -> MeldTable {
+        MeldTable {
+            run: 150,
+            double_run: 1500,
+            royal_marriage: 40,
+            marriage: 20,
+            dix: 10,
+            pinochle: 40,
+            double_pinochle: 300,
+            arounds: [100, 80, 60, 40],
+            double_arounds: [1000, 800, 600, 400],
+            counter: 10,
+            last_trick: 10,
+        }
+    }
+
+    /// The values for four handed partnership Pinochle, where each counter is a point.
+    pub fn partnership() -> MeldTable {
+        MeldTable {
+            run: 15,
+            double_run: 150,
+            royal_marriage: 4,
+            marriage: 2,
+            dix: 1,
+            pinochle: 4,
+            double_pinochle: 30,
+            arounds: [10, 8, 6, 4],
+            double_arounds: [100, 80, 60, 40],
+            counter: 1,
+            last_trick: 1,
+        }
+    }
+
+    /// The points for a single meld with the passed in trumps.
+    pub fn points(&self, meld: &Meld, trump: usize) -> usize {
+        match meld {
+            Meld::RUN(1) => self.run,
+            Meld::RUN(_) => self.double_run,
+            Meld::MARRIAGE(suit) if *suit == trump => self.royal_marriage,
+            Meld::MARRIAGE(_) => self.marriage,
+            Meld::DIX => self.dix,
+            Meld::PINOCHLE(1) => self.pinochle,
+            Meld::PINOCHLE(_) => self.double_pinochle,
+            Meld::AROUND(rank, count) => {
+                let position = [ACE, KING, QUEEN, JACK]
+                    .iter()
+                    .position(|r| r == rank)
+                    .unwrap_or(0);
+                match count {
+                    1 => self.arounds[position],
+                    _ => self.double_arounds[position],
+                }
+            }
+        }
+    }
+
+    /// The total meld in a hand with the passed in trumps.
+    pub fn meld(&self, hand: &Pile, trump: usize) -> usize {
+        Meld::find(hand, trump)
+            .iter()
+            .map(|meld| self.points(meld, trump))
+            .sum()
+    }
+
+    /// The points for the cards a side has taken in tricks, and for the last trick if they
+    /// won it.
+    pub fn trick_points(&self, won: &Pile, last_trick: bool) -> usize {
+        let bonus = if last_trick { self.last_trick } else { 0 };
+        MeldTable::counters(won) * self.counter + bonus
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod pinochle_tests {
+    use super::*;
+
+    fn hand(indexes: &[&str]) -> Pile {
+        Pile::pinochle_deck().pile_by_index(indexes).unwrap()
+    }
+
+    #[test]
+    fn find__double_run() {
+        let hand = hand(&[
+            "AH", "AH", "TH", "TH", "KH", "KH", "QH", "QH", "JH", "JH", "9H", "KS",
+        ]);
+
+        assert_eq!(vec![Meld::RUN(2), Meld::DIX], Meld::find(&hand, 1));
+        assert_eq!(150 + 1, MeldTable::partnership().meld(&hand, 1));
+    }
+
+    #[test]
+    fn find__run_with_royal_marriage() {
+        let hand = hand(&[
+            "AH", "TH", "KH", "KH", "QH", "QH", "JH", "KC", "QC", "9C", "9S", "9D",
+        ]);
+
+        assert_eq!(
+            vec![Meld::RUN(1), Meld::MARRIAGE(1), Meld::MARRIAGE(3)],
+            Meld::find(&hand, 1)
+        );
+        assert_eq!(15 + 4 + 2, MeldTable::partnership().meld(&hand, 1));
+        // With clubs trumps the hearts are two marriages and the clubs a royal one.
+        assert_eq!(
+            vec![
+                Meld::MARRIAGE(1),
+                Meld::MARRIAGE(1),
+                Meld::MARRIAGE(3),
+                Meld::DIX
+            ],
+            Meld::find(&hand, 3)
+        );
+    }
+
+    #[test]
+    fn find__arounds_and_pinochle() {
+        let hand = hand(&[
+            "AS", "AH", "AD", "AC", "QS", "QS", "QH", "QD", "QC", "JD", "JD", "9C",
+        ]);
+
+        assert_eq!(
+            vec![
+                Meld::PINOCHLE(2),
+                Meld::AROUND(ACE, 1),
+                Meld::AROUND(QUEEN, 1)
+            ],
+            Meld::find(&hand, 1)
+        );
+        assert_eq!(30 + 10 + 6, MeldTable::partnership().meld(&hand, 1));
+        assert_eq!(300 + 100 + 60, MeldTable::single_deck().meld(&hand, 1));
+    }
+
+    #[test]
+    fn find__nothing() {
+        let hand = hand(&[
+            "AS", "TS", "KS", "9S", "AH", "TH", "JH", "9H", "AD", "TD", "KD", "JC",
+        ]);
+
+        assert!(Meld::find(&hand, 2).is_empty());
+        assert_eq!(0, MeldTable::single_deck().meld(&hand, 2));
+    }
+
+    #[test]
+    fn points__double_around() {
+        let table = MeldTable::partnership();
+
+        assert_eq!(80, table.points(&Meld::AROUND(KING, 2), 0));
+        assert_eq!(4, table.points(&Meld::AROUND(JACK, 1), 0));
+    }
+
+    #[test]
+    fn counters() {
+        let won = hand(&["AS", "TS", "KS", "QS", "JS", "9S", "AH", "KD"]);
+
+        assert_eq!(5, MeldTable::counters(&won));
+        assert_eq!(5, MeldTable::partnership().trick_points(&won, false));
+        assert_eq!(60, MeldTable::single_deck().trick_points(&won, true));
+        assert_eq!(24, MeldTable::counters(&Pile::pinochle_deck()));
+    }
+}
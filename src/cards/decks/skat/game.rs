@@ -0,0 +1,332 @@
+use crate::cards::decks::skat::{skat_rank_index, skat_suit_index, SkatGame};
+use crate::cards::pile::Pile;
+
+/// The base value of a suit game, by the suit's position in `SKAT_SUITS`.
+const SUIT_VALUES: [usize; 4] = [12, 11, 10, 9];
+
+/// The base value of a grand.
+const GRAND_VALUE: usize = 24;
+
+/// The fixed values of a null game, plain, hand, ouvert and ouvert hand.
+const NULL_VALUES: [usize; 4] = [23, 35, 46, 59];
+
+/// The lowest bid.
+pub const LOWEST_BID: usize = 18;
+
+/// A Skat game as announced by the declarer: the kind of game, whether it is played from
+/// the hand without picking up the Skat, any Schneider or Schwarz announcement, and whether
+/// the declarer's cards are played open.
+///
+/// A suit or grand game is worth its base value, from 9 for bells or diamonds up to 24 for
+/// a grand, times a multiplier. The multiplier counts the matadors, the trumps from the
+/// highest jack down that the declarer holds or is missing in an unbroken run, plus one
+/// for the game and one for each of hand, Schneider, Schneider announced, Schwarz, Schwarz
+/// announced and ouvert. Announcing Schwarz includes announcing Schneider, and an ouvert
+/// suit or grand includes both. A null game has a fixed value.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{Pile, SkatContract, SkatGame};
+///
+/// // Hearts with the top two jacks, from the hand.
+/// let cards = Pile::skat_deck()
+///     .pile_by_index(&["UA", "UL", "DH", "TH", "KH", "9H", "8H", "DA", "TA", "DB", "7B", "8L"])
+///     .unwrap();
+/// let mut contract = SkatContract::new(SkatGame::SUIT(2));
+/// contract.hand = true;
+///
+/// assert_eq!(2, contract.matadors(&cards));
+/// // With 2, game 3, hand 4, times 10.
+/// assert_eq!(40, contract.value(&cards, false, false));
+/// assert!(contract.is_overbid(44, &cards, false, false));
+/// assert_eq!(40, contract.score(40, &cards, 75, 7));
+/// assert_eq!(-100, contract.score(46, &cards, 75, 7));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SkatContract {
+    pub game: SkatGame,
+    pub hand: bool,
+    pub schneider: bool,
+    pub schwarz: bool,
+    pub ouvert: bool,
+}
+
+impl SkatContract {
+    pub fn new(game: SkatGame) -> SkatContract {
+        SkatContract {
+            game,
+            hand: false,
+            schneider: false,
+            schwarz: false,
+            ouvert: false,
+        }
+    }
+
+    /// Every value a game can have that can be bid, from `LOWEST_BID` up: 18, 20, 22, 23,
+    /// 24, 27, 30 and so on.
+    pub fn bid_values() -> Vec<usize> {
+        let mut values: Vec<usize> = SUIT_VALUES
+            .iter()
+            .flat_map(|base| (2..=18).map(move |m| base * m))
+            .chain((2..=11).map(|m| GRAND_VALUE * m))
+            .chain(NULL_VALUES.iter().copied())
+            .filter(|value| *value >= LOWEST_BID)
+            .collect();
+        values.sort_unstable();
+        values.dedup();
+        values
+    }
+
+    /// The next bid after the passed in one, or the lowest bid if nothing has been bid.
+    pub fn next_bid(bid: usize) -> Option<usize> {
+        SkatContract::bid_values()
+            .into_iter()
+            .find(|value| *value > bid)
+    }
+
+    /// Returns false for announcements that aren't allowed: Schneider, Schwarz, and an
+    /// ouvert suit or grand can only be announced when playing from the hand, and not in a
+    /// null game.
+    pub fn is_valid(&self) -> bool {
+        match self.game {
+            SkatGame::NULL => !self.schneider && !self.schwarz,
+            _ => self.hand || !(self.schneider || self.schwarz || self.ouvert),
+        }
+    }
+
+    pub fn base_value(&self) -> usize {
+        match self.game {
+            SkatGame::SUIT(suit) => SUIT_VALUES.get(suit).copied().unwrap_or(0),
+            SkatGame::GRAND => GRAND_VALUE,
+            SkatGame::NULL => NULL_VALUES[self.hand as usize + 2 * self.ouvert as usize],
+        }
+    }
+
+    /// The number of matadors with or against in the declarer's cards, their hand and the
+    /// Skat together. A null game has none.
+    pub fn matadors(&self, cards: &Pile) -> usize {
+        let held: Vec<(usize, usize)> = cards
+            .values()
+            .filter_map(|card| Some((skat_suit_index(card)?, skat_rank_index(card)?)))
+            .collect();
+        let trumps: Vec<bool> = self
+            .game
+            .trumps()
+            .iter()
+            .map(|trump| held.contains(trump))
+            .collect();
+        match trumps.first() {
+            Some(with) => trumps.iter().take_while(|t| *t == with).count(),
+            None => 0,
+        }
+    }
+
+    /// What the matadors, game and levels won or announced multiply the base value by.
+    pub fn multiplier(&self, matadors: usize, schneider: bool, schwarz: bool) -> usize {
+        let schwarz_announced = self.schwarz || self.ouvert;
+        let schneider_announced = self.schneider || schwarz_announced;
+        let levels = [
+            self.hand,
+            schneider || schneider_announced || schwarz,
+            schneider_announced,
+            schwarz || schwarz_announced,
+            schwarz_announced,
+            self.ouvert,
+        ];
+        matadors + 1 + levels.iter().filter(|level| **level).count()
+    }
+
+    /// The value of the game with the declarer's cards, and whether either side was made
+    /// Schneider or Schwarz.
+    pub fn value(&self, cards: &Pile, schneider: bool, schwarz: bool) -> usize {
+        match self.game {
+            SkatGame::NULL => self.base_value(),
+            _ => self.base_value() * self.multiplier(self.matadors(cards), schneider, schwarz),
+        }
+    }
+
+    /// Returns true if the game isn't worth as much as the bid.
+    pub fn is_overbid(&self, bid: usize, cards: &Pile, schneider: bool, schwarz: bool) -> bool {
+        self.value(cards, schneider, schwarz) < bid
+    }
+
+    /// Returns true if the declarer won with the card points and tricks they took: more than
+    /// 60 points, and any Schneider or Schwarz they announced, or no tricks at all in null.
+    pub fn is_won(&self, declarer_points: usize, declarer_tricks: usize) -> bool {
+        match self.game {
+            SkatGame::NULL => declarer_tricks == 0,
+            _ => {
+                let schwarz_announced = self.schwarz || self.ouvert;
+                let schneider_announced = self.schneider || schwarz_announced;
+                declarer_points > 60
+                    && (!schneider_announced || declarer_points >= 90)
+                    && (!schwarz_announced || declarer_tricks == 10)
+            }
+        }
+    }
+
+    /// The declarer's score: the game value if they won, or minus twice the value if they
+    /// lost. If the game was overbid it is lost, and valued at the lowest multiple of the
+    /// base value that reaches the bid.
+    pub fn score(
+        &self,
+        bid: usize,
+        cards: &Pile,
+        declarer_points: usize,
+        declarer_tricks: usize,
+    ) -> isize {
+        let schneider = declarer_points >= 90 || declarer_points <= 30;
+        let schwarz = declarer_tricks == 10 || declarer_tricks == 0;
+        let value = self.value(cards, schneider, schwarz);
+        if value < bid {
+            let base = self.base_value().max(1);
+            return -2 * ((bid + base - 1) / base * base) as isize;
+        }
+        if self.is_won(declarer_points, declarer_tricks) {
+            value as isize
+        } else {
+            -2 * value as isize
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod skat_game_tests {
+    use super::*;
+
+    fn cards(indexes: &[&str]) -> Pile {
+        Pile::skat_deck().pile_by_index(indexes).unwrap()
+    }
+
+    fn french(indexes: &[&str]) -> Pile {
+        Pile::french_skat_deck().pile_by_index(indexes).unwrap()
+    }
+
+    #[test]
+    fn bid_values() {
+        let values = SkatContract::bid_values();
+
+        assert_eq!(
+            vec![18, 20, 22, 23, 24, 27, 30, 33, 35, 36, 40, 44, 45, 46, 48, 50],
+            values[..16].to_vec()
+        );
+        assert_eq!(Some(&264), values.last());
+        assert_eq!(Some(18), SkatContract::next_bid(0));
+        assert_eq!(Some(23), SkatContract::next_bid(22));
+        assert_eq!(None, SkatContract::next_bid(264));
+    }
+
+    #[test]
+    fn matadors__with_and_without() {
+        let with = cards(&["UA", "UL", "UH", "DB", "7B"]);
+        let without = cards(&["UB", "DA", "TA"]);
+        let game = SkatContract::new(SkatGame::SUIT(0));
+
+        assert_eq!(3, game.matadors(&with));
+        assert_eq!(3, game.matadors(&without));
+        // Against four jacks and the ace and ten of clubs.
+        assert_eq!(6, game.matadors(&cards(&["KA", "7H"])));
+        assert_eq!(11, game.matadors(&Pile::skat_deck()));
+        assert_eq!(
+            4,
+            SkatContract::new(SkatGame::GRAND).matadors(&Pile::skat_deck())
+        );
+        assert_eq!(0, SkatContract::new(SkatGame::NULL).matadors(&with));
+    }
+
+    #[test]
+    fn matadors__french() {
+        let cards = french(&["JC", "JS", "JH", "JD", "AS", "TS", "KS"]);
+
+        assert_eq!(7, SkatContract::new(SkatGame::SUIT(1)).matadors(&cards));
+        assert_eq!(4, SkatContract::new(SkatGame::GRAND).matadors(&cards));
+    }
+
+    #[test]
+    fn value() {
+        let cards = cards(&["UA", "UB", "DA", "TA", "KA"]);
+        let mut grand = SkatContract::new(SkatGame::GRAND);
+
+        // With 1, game 2.
+        assert_eq!(48, grand.value(&cards, false, false));
+        // With 1, game 2, Schneider 3.
+        assert_eq!(72, grand.value(&cards, true, false));
+        // Schwarz counts Schneider as well.
+        assert_eq!(96, grand.value(&cards, true, true));
+
+        grand.hand = true;
+        grand.ouvert = true;
+        // With 1, game, hand, Schneider, announced, Schwarz, announced, ouvert: 8.
+        assert_eq!(24 * 8, grand.value(&cards, false, false));
+    }
+
+    #[test]
+    fn value__null() {
+        let mut null = SkatContract::new(SkatGame::NULL);
+        let cards = cards(&["7A"]);
+
+        assert_eq!(23, null.value(&cards, false, false));
+        null.hand = true;
+        assert_eq!(35, null.value(&cards, true, true));
+        null.ouvert = true;
+        assert_eq!(59, null.value(&cards, false, false));
+        null.hand = false;
+        assert_eq!(46, null.value(&cards, false, false));
+    }
+
+    #[test]
+    fn is_valid() {
+        let mut suit = SkatContract::new(SkatGame::SUIT(3));
+
+        assert!(suit.is_valid());
+        suit.schneider = true;
+        assert!(!suit.is_valid());
+        suit.hand = true;
+        assert!(suit.is_valid());
+
+        let mut null = SkatContract::new(SkatGame::NULL);
+        null.ouvert = true;
+        assert!(null.is_valid());
+        null.schwarz = true;
+        assert!(!null.is_valid());
+    }
+
+    #[test]
+    fn score() {
+        // Diamonds without 1: 9 x 2 = 18.
+        let cards = cards(&["UL", "DB", "7B"]);
+        let diamonds = SkatContract::new(SkatGame::SUIT(3));
+
+        assert_eq!(18, diamonds.score(18, &cards, 61, 5));
+        assert_eq!(-36, diamonds.score(18, &cards, 60, 5));
+        // Schneider lifts the value to 27, covering a bid of 27.
+        assert_eq!(27, diamonds.score(27, &cards, 95, 8));
+        // Overbid: lost at the next multiple of 9 from 20, 27.
+        assert_eq!(-54, diamonds.score(20, &cards, 80, 7));
+        // Made Schneider, the declarer loses three times 9 twice over.
+        assert_eq!(-54, diamonds.score(18, &cards, 20, 2));
+    }
+
+    #[test]
+    fn score__announced() {
+        let cards = cards(&["UA", "UL"]);
+        let mut hearts = SkatContract::new(SkatGame::SUIT(2));
+        hearts.hand = true;
+        hearts.schneider = true;
+
+        // With 2, game, hand, Schneider, announced: 6 x 10.
+        assert_eq!(60, hearts.score(18, &cards, 92, 8));
+        assert_eq!(-120, hearts.score(18, &cards, 85, 8));
+    }
+
+    #[test]
+    fn score__null() {
+        let null = SkatContract::new(SkatGame::NULL);
+        let cards = cards(&["7A"]);
+
+        assert_eq!(23, null.score(23, &cards, 0, 0));
+        assert_eq!(-46, null.score(23, &cards, 10, 1));
+        assert_eq!(-92, null.score(24, &cards, 0, 0));
+    }
+}
@@ -0,0 +1,213 @@
+use crate::cards::card::Card;
+use crate::cards::pile::Pile;
+use crate::cards::rank::*;
+use crate::cards::suit::*;
+use crate::Named;
+
+pub mod game;
+pub mod play;
+
+/// The Skat suits from highest to lowest, each with its German and French name: acorns or
+/// clubs, leaves or spades, hearts, and bells or diamonds.
+pub const SKAT_SUITS: [[&str; 2]; 4] = [
+    [EICHEL, CLUBS],
+    [LAUB, SPADES],
+    [HERZ, HEARTS],
+    [SHELLEN, DIAMONDS],
+];
+
+/// The ranks of a Skat suit from highest to lowest in suit and grand games, each with its
+/// German and French name. The Unter, or jack, is a trump in those games.
+pub const SKAT_RANKS: [[&str; 2]; 8] = [
+    [DAUS, ACE],
+    [TEN, TEN],
+    [KING, KING],
+    [OBER, QUEEN],
+    [UNTER, JACK],
+    [NINE, NINE],
+    [EIGHT, EIGHT],
+    [SEVEN, SEVEN],
+];
+
+/// Position in `SKAT_RANKS` of the Unter, or jack.
+pub const UNTER_INDEX: usize = 4;
+
+/// The points for each rank in `SKAT_RANKS` order.
+const SKAT_CARD_POINTS: [usize; 8] = [11, 10, 4, 3, 2, 0, 0, 0];
+
+/// The ranks from highest to lowest in a null game, as positions in `SKAT_RANKS`: the ten
+/// drops to its natural place between the jack and the nine.
+const NULL_ORDER: [usize; 8] = [0, 2, 3, 4, 1, 5, 6, 7];
+
+/// Returns the position of the Card's Suit in `SKAT_SUITS`, for either German or French
+/// suits, so acorns and clubs are 0 and bells and diamonds 3.
+pub fn skat_suit_index(card: &Card) -> Option<usize> {
+    SKAT_SUITS
+        .iter()
+        .position(|names| names.contains(&card.suit.name()))
+}
+
+/// Returns the position of the Card's Rank in `SKAT_RANKS`, so the Daus or ace is 0 and the
+/// seven 7.
+pub fn skat_rank_index(card: &Card) -> Option<usize> {
+    SKAT_RANKS
+        .iter()
+        .position(|names| names.contains(&card.rank.name()))
+}
+
+/// The kind of Skat game the declarer plays.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SkatGame {
+    /// The suit, by its position in `SKAT_SUITS`, and the four jacks are trumps.
+    SUIT(usize),
+    /// Only the four jacks are trumps.
+    GRAND,
+    /// There are no trumps, and the declarer must lose every trick.
+    NULL,
+}
+
+impl SkatGame {
+    /// Returns the points a pile of Skat cards is worth: eleven for each ace, ten for each ten,
+    /// four for each king, three for each queen and two for each jack, out of 120 in all.
+    pub fn card_points(pile: &Pile) -> usize {
+        pile.values()
+            .filter_map(skat_rank_index)
+            .map(|rank| SKAT_CARD_POINTS[rank])
+            .sum()
+    }
+
+    pub fn is_trump(&self, card: &Card) -> bool {
+        let jack = skat_rank_index(card) == Some(UNTER_INDEX);
+        match self {
+            SkatGame::SUIT(suit) => jack || skat_suit_index(card) == Some(*suit),
+            SkatGame::GRAND => jack,
+            SkatGame::NULL => false,
+        }
+    }
+
+    /// The trumps from highest to lowest, as positions in `SKAT_SUITS` and `SKAT_RANKS`:
+    /// the jacks in suit order, then in a suit game the rest of the trump suit.
+    pub fn trumps(&self) -> Vec<(usize, usize)> {
+        let mut trumps: Vec<(usize, usize)> = match self {
+            SkatGame::NULL => return Vec::new(),
+            _ => (0..4).map(|suit| (suit, UNTER_INDEX)).collect(),
+        };
+        if let SkatGame::SUIT(suit) = self {
+            trumps.extend(
+                (0..SKAT_RANKS.len())
+                    .filter(|rank| *rank != UNTER_INDEX)
+                    .map(|rank| (*suit, rank)),
+            );
+        }
+        trumps
+    }
+
+    /// Returns true if the card belongs to the same suit as the card led, where in suit and
+    /// grand games the jacks belong to the trumps rather than to their own suit.
+    pub fn follows(&self, card: &Card, led: &Card) -> bool {
+        match (self.is_trump(card), self.is_trump(led)) {
+            (true, true) => true,
+            (false, false) => skat_suit_index(card) == skat_suit_index(led),
+            _ => false,
+        }
+    }
+
+    /// How strong a card is in a trick to which `led` was led: trumps beat cards of the suit
+    /// led, which beat anything else.
+    pub fn power(&self, card: &Card, led: &Card) -> usize {
+        let (suit, rank) = match (skat_suit_index(card), skat_rank_index(card)) {
+            (Some(suit), Some(rank)) => (suit, rank),
+            _ => return 0,
+        };
+        if let Some(position) = self.trumps().iter().position(|t| *t == (suit, rank)) {
+            return 100 - position;
+        }
+        if !self.follows(card, led) {
+            return 0;
+        }
+        match self {
+            SkatGame::NULL => 50 - NULL_ORDER.iter().position(|r| *r == rank).unwrap_or(50),
+            _ => 50 - rank,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod skat_tests {
+    use super::*;
+
+    #[test]
+    fn skat_suit_index() {
+        assert_eq!(Some(0), super::skat_suit_index(&Card::new(SEVEN, EICHEL)));
+        assert_eq!(Some(0), super::skat_suit_index(&Card::new(SEVEN, CLUBS)));
+        assert_eq!(Some(3), super::skat_suit_index(&Card::new(UNTER, SHELLEN)));
+        assert_eq!(None, super::skat_suit_index(&Card::new(BIG_JOKER, TRUMP)));
+    }
+
+    #[test]
+    fn card_points() {
+        assert_eq!(120, SkatGame::card_points(&Pile::skat_deck()));
+        assert_eq!(120, SkatGame::card_points(&Pile::french_skat_deck()));
+        assert_eq!(
+            11 + 3 + 2,
+            SkatGame::card_points(&Pile::new_from_vector(vec![
+                Card::new(DAUS, HERZ),
+                Card::new(OBER, LAUB),
+                Card::new(UNTER, EICHEL),
+                Card::new(NINE, EICHEL),
+            ]))
+        );
+    }
+
+    #[test]
+    fn is_trump() {
+        let jack = Card::new(JACK, DIAMONDS);
+        let ace = Card::new(ACE, HEARTS);
+
+        assert!(SkatGame::SUIT(2).is_trump(&jack));
+        assert!(SkatGame::SUIT(2).is_trump(&ace));
+        assert!(SkatGame::GRAND.is_trump(&jack));
+        assert!(!SkatGame::GRAND.is_trump(&ace));
+        assert!(!SkatGame::NULL.is_trump(&jack));
+    }
+
+    #[test]
+    fn follows() {
+        let jack = Card::new(UNTER, EICHEL);
+        let seven = Card::new(SEVEN, EICHEL);
+        let hearts = Card::new(SEVEN, HERZ);
+
+        assert!(!SkatGame::SUIT(2).follows(&jack, &seven));
+        assert!(SkatGame::SUIT(2).follows(&jack, &hearts));
+        assert!(!SkatGame::GRAND.follows(&jack, &seven));
+        assert!(SkatGame::NULL.follows(&jack, &seven));
+    }
+
+    #[test]
+    fn power() {
+        let led = Card::new(SEVEN, LAUB);
+        let ten = Card::new(TEN, LAUB);
+        let unter = Card::new(UNTER, LAUB);
+        let daus = Card::new(DAUS, SHELLEN);
+
+        assert!(SkatGame::GRAND.power(&ten, &led) > SkatGame::GRAND.power(&led, &led));
+        assert!(SkatGame::GRAND.power(&unter, &led) > SkatGame::GRAND.power(&ten, &led));
+        assert!(SkatGame::NULL.power(&unter, &led) > SkatGame::NULL.power(&ten, &led));
+        assert!(SkatGame::SUIT(3).power(&daus, &led) > SkatGame::SUIT(3).power(&ten, &led));
+        assert_eq!(0, SkatGame::GRAND.power(&daus, &led));
+        assert!(
+            SkatGame::GRAND.power(&Card::new(UNTER, EICHEL), &led)
+                > SkatGame::GRAND.power(&unter, &led)
+        );
+    }
+
+    #[test]
+    fn trumps() {
+        assert_eq!(11, SkatGame::SUIT(1).trumps().len());
+        assert_eq!((1, 0), SkatGame::SUIT(1).trumps()[4]);
+        assert_eq!(4, SkatGame::GRAND.trumps().len());
+        assert!(SkatGame::NULL.trumps().is_empty());
+    }
+}
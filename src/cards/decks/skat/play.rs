@@ -0,0 +1,435 @@
+use crate::cards::card::Card;
+use crate::cards::decks::skat::game::SkatContract;
+use crate::cards::decks::skat::SkatGame;
+use crate::cards::pile::Pile;
+
+/// A single trick: the seat that led to it and the cards in the order they were played.
+/// Seats are numbered from 0 for forehand, who leads to the first trick, to 2 for rearhand.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkatTrick {
+    pub leader: usize,
+    pub cards: Vec<Card>,
+}
+
+impl SkatTrick {
+    pub fn new(leader: usize) -> SkatTrick {
+        SkatTrick {
+            leader,
+            cards: Vec::new(),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.cards.len() == 3
+    }
+
+    /// Returns the seat that played the card at the passed in position of the trick.
+    pub fn player(&self, position: usize) -> usize {
+        (self.leader + position) % 3
+    }
+
+    /// Returns the seat whose card is winning the trick so far in the passed in game.
+    pub fn winner(&self, game: &SkatGame) -> Option<usize> {
+        let led = self.cards.first()?;
+        let (position, _) = self
+            .cards
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, card)| game.power(card, led))?;
+        Some(self.player(position))
+    }
+}
+
+/// Plays out a hand of Skat, from the deal through the ten tricks.
+///
+/// Each of the three players gets ten cards and the last two form the Skat. The declarer
+/// either picks up the Skat and discards two cards in its place before announcing the game,
+/// or plays from the hand, leaving the Skat untouched. Forehand leads to the first trick,
+/// every card is checked against the follow suit rule, where in suit and grand games the
+/// jacks are trumps rather than part of their own suit, and the winner of each trick leads
+/// to the next one. The Skat counts towards the declarer's card points.
+///
+/// # Usage:
+/// ```
+/// use cardpack::{Pile, SkatContract, SkatGame, SkatPlay};
+///
+/// let mut play = SkatPlay::deal(&Pile::skat_deck());
+///
+/// assert!(play.pick_up(1));
+/// let discards = Pile::new_from_vector(play.hand(1).unwrap().cards()[..2].to_vec());
+/// assert!(play.discard(&discards));
+/// assert!(play.announce(SkatContract::new(SkatGame::GRAND)));
+///
+/// while !play.is_complete() {
+///     let card = play.legal_plays().get(0).unwrap().clone();
+///     assert!(play.play(&card));
+/// }
+/// assert_eq!(120, play.declarer_points() + play.defender_points());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SkatPlay {
+    hands: [Pile; 3],
+    skat: Pile,
+    declarer: Option<usize>,
+    contract: Option<SkatContract>,
+    picked_up: bool,
+    declarer_cards: Pile,
+    tricks: Vec<SkatTrick>,
+}
+
+impl SkatPlay {
+    /// Starts a hand with the cards each seat holds, forehand first, and the Skat.
+    pub fn new(hands: [Pile; 3], skat: Pile) -> SkatPlay {
+        SkatPlay {
+            hands,
+            skat,
+            declarer: None,
+            contract: None,
+            picked_up: false,
+            declarer_cards: Pile::default(),
+            tricks: Vec::new(),
+        }
+    }
+
+    /// Shuffles a 32 card pack, German or French suited, and deals ten cards to each player
+    /// and two to the Skat.
+    pub fn deal(pack: &Pile) -> SkatPlay {
+        let mut cards = pack.shuffle();
+        let mut hands: [Pile; 3] = Default::default();
+        for hand in hands.iter_mut() {
+            *hand = cards.draw(10).unwrap_or_default();
+            hand.sort_in_place();
+        }
+        SkatPlay::new(hands, cards)
+    }
+
+    /// Returns the cards a seat has left to play.
+    pub fn hand(&self, seat: usize) -> Option<&Pile> {
+        self.hands.get(seat)
+    }
+
+    pub fn skat(&self) -> &Pile {
+        &self.skat
+    }
+
+    pub fn declarer(&self) -> Option<usize> {
+        self.declarer
+    }
+
+    pub fn contract(&self) -> Option<&SkatContract> {
+        self.contract.as_ref()
+    }
+
+    /// The declarer takes the Skat into their hand. Returns false once a declarer has
+    /// picked it up or a game has been announced.
+    pub fn pick_up(&mut self, declarer: usize) -> bool {
+        if declarer > 2 || self.picked_up || self.contract.is_some() {
+            return false;
+        }
+        let skat = std::mem::take(&mut self.skat);
+        self.hands[declarer].append(&skat);
+        self.hands[declarer].sort_in_place();
+        self.declarer = Some(declarer);
+        self.picked_up = true;
+        true
+    }
+
+    /// The declarer puts two cards from their hand back as the Skat.
+    pub fn discard(&mut self, cards: &Pile) -> bool {
+        let declarer = match self.declarer {
+            Some(declarer) if self.picked_up && self.contract.is_none() => declarer,
+            _ => return false,
+        };
+        if cards.len() != 2 || !self.skat.is_empty() || !self.hands[declarer].contains_all(cards) {
+            return false;
+        }
+        for card in cards.values() {
+            self.hands[declarer].remove_card(card);
+        }
+        self.skat = cards.clone();
+        true
+    }
+
+    /// Announces the game. After picking up the Skat the declarer must have discarded and
+    /// can't play a hand game. To play from the hand, call `announce_hand()` instead.
+    pub fn announce(&mut self, contract: SkatContract) -> bool {
+        match self.declarer {
+            Some(declarer) if self.picked_up && self.skat.len() == 2 && !contract.hand => {
+                self.start(declarer, contract)
+            }
+            _ => false,
+        }
+    }
+
+    /// Announces a hand game for a declarer who hasn't picked up the Skat.
+    pub fn announce_hand(&mut self, declarer: usize, contract: SkatContract) -> bool {
+        if declarer > 2 || self.picked_up || !contract.hand {
+            return false;
+        }
+        self.start(declarer, contract)
+    }
+
+    fn start(&mut self, declarer: usize, contract: SkatContract) -> bool {
+        if self.contract.is_some() || !contract.is_valid() {
+            return false;
+        }
+        let mut cards = self.hands[declarer].clone();
+        cards.append(&self.skat);
+        self.declarer_cards = cards;
+        self.declarer = Some(declarer);
+        self.contract = Some(contract);
+        true
+    }
+
+    /// All of the tricks played so far, including the one in progress.
+    pub fn tricks(&self) -> &Vec<SkatTrick> {
+        &self.tricks
+    }
+
+    /// Returns the trick in progress, if any cards have been played to it.
+    pub fn current_trick(&self) -> Option<&SkatTrick> {
+        self.tricks.last().filter(|t| !t.is_complete())
+    }
+
+    /// The seat that led, or is about to lead, the current trick.
+    pub fn leader(&self) -> usize {
+        match self.tricks.last() {
+            None => 0,
+            Some(trick) if trick.is_complete() => self.trick_winner(trick),
+            Some(trick) => trick.leader,
+        }
+    }
+
+    pub fn to_play(&self) -> usize {
+        match self.current_trick() {
+            Some(trick) => trick.player(trick.cards.len()),
+            None => self.leader(),
+        }
+    }
+
+    /// Returns the cards the player to play may play: any card when leading, otherwise the
+    /// cards that follow the card led if they hold any.
+    pub fn legal_plays(&self) -> Pile {
+        let (hand, game) = match (self.hand(self.to_play()), &self.contract) {
+            (Some(hand), Some(contract)) => (hand, contract.game),
+            _ => return Pile::default(),
+        };
+        let led = match self.current_trick().and_then(|t| t.cards.first()) {
+            Some(led) => led,
+            None => return hand.clone(),
+        };
+        let following: Vec<Card> = hand
+            .values()
+            .filter(|c| game.follows(c, led))
+            .cloned()
+            .collect();
+
+        if following.is_empty() {
+            hand.clone()
+        } else {
+            Pile::new_from_vector(following)
+        }
+    }
+
+    pub fn is_legal(&self, card: &Card) -> bool {
+        self.legal_plays().contains(card)
+    }
+
+    /// Plays a card for the seat whose turn it is. Returns false, leaving the play unchanged,
+    /// if the card isn't a legal play or no game has been announced.
+    pub fn play(&mut self, card: &Card) -> bool {
+        if !self.is_legal(card) {
+            return false;
+        }
+        let seat = self.to_play();
+        if self.current_trick().is_none() {
+            self.tricks.push(SkatTrick::new(seat));
+        }
+        self.hands[seat].remove_card(card);
+        if let Some(trick) = self.tricks.last_mut() {
+            trick.cards.push(card.clone());
+        }
+        true
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.tricks.len() == 10 && self.tricks.iter().all(|t| t.is_complete())
+    }
+
+    /// The cards in the tricks a seat has won.
+    pub fn won(&self, seat: usize) -> Pile {
+        let cards: Vec<Card> = self
+            .tricks
+            .iter()
+            .filter(|t| t.is_complete() && self.trick_winner(t) == seat)
+            .flat_map(|t| t.cards.iter().cloned())
+            .collect();
+        Pile::new_from_vector(cards)
+    }
+
+    pub fn declarer_tricks(&self) -> usize {
+        self.tricks
+            .iter()
+            .filter(|t| t.is_complete() && Some(self.trick_winner(t)) == self.declarer)
+            .count()
+    }
+
+    /// The card points in the declarer's tricks and the Skat.
+    pub fn declarer_points(&self) -> usize {
+        match self.declarer {
+            Some(declarer) => {
+                SkatGame::card_points(&self.won(declarer)) + SkatGame::card_points(&self.skat)
+            }
+            None => 0,
+        }
+    }
+
+    /// The card points in the tricks won by the two defenders together.
+    pub fn defender_points(&self) -> usize {
+        (0..3)
+            .filter(|seat| Some(*seat) != self.declarer)
+            .map(|seat| SkatGame::card_points(&self.won(seat)))
+            .sum()
+    }
+
+    /// The declarer's score against the passed in bid, once the hand is over.
+    pub fn score(&self, bid: usize) -> Option<isize> {
+        if !self.is_complete() {
+            return None;
+        }
+        let contract = self.contract.as_ref()?;
+        Some(contract.score(
+            bid,
+            &self.declarer_cards,
+            self.declarer_points(),
+            self.declarer_tricks(),
+        ))
+    }
+
+    fn trick_winner(&self, trick: &SkatTrick) -> usize {
+        self.contract
+            .as_ref()
+            .and_then(|contract| trick.winner(&contract.game))
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod skat_play_tests {
+    use super::*;
+
+    fn cards(indexes: &[&str]) -> Pile {
+        Pile::skat_deck().pile_by_index(indexes).unwrap()
+    }
+
+    fn card(index: &str) -> Card {
+        Pile::skat_deck().card_by_index(index).unwrap().clone()
+    }
+
+    /// Forehand holds all four jacks.
+    fn play() -> SkatPlay {
+        let hands = [
+            cards(&["UA", "UL", "UH", "UB", "DA", "TA", "KA", "OA", "9A", "8A"]),
+            cards(&["7A", "DL", "TL", "KL", "OL", "9L", "8L", "7L", "DH", "TH"]),
+            cards(&["KH", "OH", "9H", "8H", "7H", "DB", "TB", "KB", "OB", "9B"]),
+        ];
+        SkatPlay::new(hands, cards(&["8B", "7B"]))
+    }
+
+    #[test]
+    fn deal() {
+        let play = SkatPlay::deal(&Pile::french_skat_deck());
+
+        for seat in 0..3 {
+            assert_eq!(10, play.hand(seat).unwrap().len());
+        }
+        assert_eq!(2, play.skat().len());
+        assert_eq!(None, play.hand(3));
+    }
+
+    #[test]
+    fn pick_up_and_discard() {
+        let mut play = play();
+
+        assert!(!play.announce(SkatContract::new(SkatGame::GRAND)));
+        assert!(play.pick_up(0));
+        assert!(!play.pick_up(1));
+        assert_eq!(12, play.hand(0).unwrap().len());
+        assert!(!play.announce(SkatContract::new(SkatGame::GRAND)));
+        assert!(!play.discard(&cards(&["7L", "8B"])));
+        assert!(play.discard(&cards(&["8A", "8B"])));
+        assert!(!play.discard(&cards(&["9A", "7B"])));
+
+        assert!(play.announce(SkatContract::new(SkatGame::SUIT(0))));
+        assert_eq!(cards(&["8A", "8B"]), *play.skat());
+        assert_eq!(10, play.hand(0).unwrap().len());
+        assert_eq!(0, play.to_play());
+    }
+
+    #[test]
+    fn announce_hand() {
+        let mut play = play();
+        let mut grand = SkatContract::new(SkatGame::GRAND);
+
+        assert!(!play.announce_hand(0, grand.clone()));
+        grand.hand = true;
+        assert!(play.announce_hand(0, grand.clone()));
+        assert!(!play.announce_hand(1, grand));
+        assert!(!play.pick_up(1));
+        assert_eq!(Some(0), play.declarer());
+    }
+
+    #[test]
+    fn follow_suit__jacks_are_trumps() {
+        let mut play = play();
+        let mut grand = SkatContract::new(SkatGame::GRAND);
+        grand.hand = true;
+        play.announce_hand(0, grand);
+
+        // A jack leads the trumps, which middlehand doesn't hold.
+        assert!(play.play(&card("UA")));
+        assert_eq!(10, play.legal_plays().len());
+        assert!(play.play(&card("7A")));
+        assert!(play.play(&card("9B")));
+        assert_eq!(0, play.to_play());
+
+        // Middlehand's only acorn went on the jack, so anything can be played to the ace.
+        assert!(play.play(&card("DA")));
+        assert_eq!(9, play.legal_plays().len());
+    }
+
+    #[test]
+    fn follow_suit__null() {
+        let mut play = play();
+        play.pick_up(2);
+        play.discard(&cards(&["8B", "7B"]));
+        play.announce(SkatContract::new(SkatGame::NULL));
+
+        // In null the jack of acorns is an acorn, and the ten is below the jack.
+        assert!(play.play(&card("UA")));
+        assert_eq!(cards(&["7A"]), play.legal_plays());
+        assert!(play.play(&card("7A")));
+        assert!(play.play(&card("KH")));
+        assert_eq!(0, play.to_play());
+        assert_eq!(1, play.tricks().len());
+        assert_eq!(Some(0), play.tricks()[0].winner(&SkatGame::NULL));
+    }
+
+    #[test]
+    fn tally() {
+        let mut play = play();
+        let mut grand = SkatContract::new(SkatGame::GRAND);
+        grand.hand = true;
+        play.announce_hand(0, grand);
+
+        while !play.is_complete() {
+            let card = play.legal_plays().get(0).unwrap().clone();
+            assert!(play.play(&card));
+        }
+
+        assert_eq!(120, play.declarer_points() + play.defender_points());
+        assert_eq!(play.declarer_tricks() * 3, play.won(0).len());
+        assert!(play.score(18).is_some());
+    }
+}
@@ -69,6 +69,10 @@ impl Pack {
         Pack::new(Pile::french_deck_with_jokers())
     }
 
+    pub fn french_skat_deck() -> Pack {
+        Pack::new(Pile::french_skat_deck())
+    }
+
     pub fn pinochle_deck() -> Pack {
         Pack::new(Pile::pinochle_deck())
     }
@@ -143,11 +143,11 @@ impl Pile {
         println!();
         print!("   Shuffle Deck:           ");
         let shuffled = self.shuffle();
-        print!("{}", shuffled.to_string());
+        print!("{}", shuffled);
 
         println!();
         print!("   Sort Deck:              ");
-        print!("{}", shuffled.sort().to_string());
+        print!("{}", shuffled.sort());
 
         println!();
     }
@@ -183,8 +183,8 @@ impl Pile {
     }
 
     fn fold_in(&mut self, suits: Vec<Suit>, ranks: Vec<Rank>) {
-        for (_, suit) in suits.iter().enumerate() {
-            for (_, rank) in ranks.iter().enumerate() {
+        for suit in suits.iter() {
+            for rank in ranks.iter() {
                 self.add(Card::new_from_structs(*rank, *suit));
             }
         }
@@ -364,6 +364,15 @@ impl Pile {
         pile
     }
 
+    pub fn french_skat_deck() -> Pile {
+        let suits = Suit::from_array(&[CLUBS, SPADES, HEARTS, DIAMONDS]);
+        let ranks = Rank::generate_french_skat_ranks();
+
+        let mut cards: Pile = Pile::default();
+        cards.fold_in(suits, ranks);
+        cards
+    }
+
     fn pinochle_pile() -> Pile {
         let suits = Suit::generate_french_suits();
         let ranks = Rank::generate_pinochle_ranks();
@@ -407,13 +416,13 @@ impl Pile {
         let (_, major_arcana_suit) = arcana_suits_enumerator.next().unwrap();
 
         // Generate Major Arcana
-        for (_, rank) in major_arcana_ranks.iter().enumerate() {
+        for rank in major_arcana_ranks.iter() {
             cards.add(Card::new_from_structs(*rank, *major_arcana_suit));
         }
 
         // Generate Minor Arcana
         for (_, suit) in arcana_suits_enumerator {
-            for (_, rank) in minor_arcana_ranks.iter().enumerate() {
+            for rank in minor_arcana_ranks.iter() {
                 cards.add(Card::new_from_structs(*rank, *suit));
             }
         }
@@ -842,6 +851,7 @@ mod card_deck_tests {
             Pile::french_deck(),
             Pile::french_deck_with_jokers(),
             Pile::skat_deck(),
+            Pile::french_skat_deck(),
             Pile::spades_deck(),
             Pile::tarot_deck(),
         ];
@@ -183,6 +183,10 @@ impl Rank {
     pub fn generate_skat_ranks() -> Vec<Rank> {
         Rank::from_array(&[DAUS, KING, OBER, UNTER, TEN, NINE, EIGHT, SEVEN])
     }
+
+    pub fn generate_french_skat_ranks() -> Vec<Rank> {
+        Rank::from_array(&[ACE, KING, QUEEN, JACK, TEN, NINE, EIGHT, SEVEN])
+    }
 }
 
 impl fmt::Display for Rank {
@@ -198,7 +202,7 @@ impl Named for Rank {
 }
 
 #[cfg(test)]
-#[allow(non_snake_case)]
+#[allow(non_snake_case, clippy::vec_init_then_push)]
 mod rank_tests {
     use super::*;
     use crate::{GERMAN, US_ENGLISH};
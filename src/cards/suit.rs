@@ -97,7 +97,7 @@ impl Named for Suit {
 }
 
 #[cfg(test)]
-#[allow(non_snake_case)]
+#[allow(non_snake_case, clippy::vec_init_then_push)]
 mod suit_tests {
     use super::*;
     use crate::{GERMAN, US_ENGLISH};
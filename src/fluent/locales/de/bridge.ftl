@@ -0,0 +1,16 @@
+# Bridge
+north-long = Nord
+east-long = Ost
+south-long = Süd
+west-long = West
+
+board-long = Board
+dealer-long = Teiler
+vulnerable-long = Gefahr
+vulnerable-none-long = Keiner
+vulnerable-ns-long = Nord-Süd
+vulnerable-ew-long = Ost-West
+vulnerable-both-long = Alle
+hcp-long = Figurenpunkte
+makeable-long = Erfüllbare Kontrakte
+hand-records-long = Handzettel
@@ -0,0 +1,16 @@
+# Bridge
+north-long = North
+east-long = East
+south-long = South
+west-long = West
+
+board-long = Board
+dealer-long = Dealer
+vulnerable-long = Vulnerable
+vulnerable-none-long = None
+vulnerable-ns-long = North-South
+vulnerable-ew-long = East-West
+vulnerable-both-long = All
+hcp-long = HCP
+makeable-long = Makeable contracts
+hand-records-long = Hand Records
@@ -4,7 +4,35 @@ mod cards;
 pub mod fluent;
 
 pub use cards::card::Card;
-pub use cards::decks::bridge::BridgeBoard;
+pub use cards::decks::bridge::auction::{Auction, Call};
+pub use cards::decks::bridge::bidding::{BidChoice, BiddingRule, BiddingSystem, STANDARD_RULES};
+pub use cards::decks::bridge::bluechip::{
+    BasicRobot, BlueChipClient, Robot, TableManager, TableResult, TableView,
+};
+pub use cards::decks::bridge::contract::{Contract, Doubling, Strain};
+pub use cards::decks::bridge::database::{DealDatabase, DealQuery, RECORD_SIZE};
+pub use cards::decks::bridge::dds::{DoubleDummy, DoubleDummySolution, TrickTable};
+pub use cards::decks::bridge::dealset::{DealSet, SEED_SIZE};
+pub use cards::decks::bridge::diagram::DiagramOptions;
+pub use cards::decks::bridge::distribution::Distribution;
+pub use cards::decks::bridge::evaluation::HandEvaluation;
+pub use cards::decks::bridge::generator::{DealGenerator, Predicate, ScriptError, ShapePattern};
+pub use cards::decks::bridge::handviewer::{Handviewer, HANDVIEWER_URL};
+pub use cards::decks::bridge::machine::DealFormat;
+pub use cards::decks::bridge::minibridge::{Minibridge, MinibridgeContract};
+pub use cards::decks::bridge::movement::{Assignment, Movement, MovementKind};
+pub use cards::decks::bridge::numbering::DEAL_COUNT;
+pub use cards::decks::bridge::par::{Par, ParContract};
+pub use cards::decks::bridge::play::{BridgePlay, Trick};
+pub use cards::decks::bridge::rbn::RbnRecord;
+pub use cards::decks::bridge::record::HandRecord;
+pub use cards::decks::bridge::session::{imps, BoardResult, Ranking, Scoring, Session};
+pub use cards::decks::bridge::{BridgeBoard, BridgeDirection, BridgeSide, Vulnerability};
+pub use cards::decks::euchre::{EuchreGame, EuchreHand, EuchreTrick, EUCHRE_GAME};
+pub use cards::decks::pinochle::{Meld, MeldTable};
+pub use cards::decks::skat::game::{SkatContract, LOWEST_BID};
+pub use cards::decks::skat::play::{SkatPlay, SkatTrick};
+pub use cards::decks::skat::SkatGame;
 pub use cards::pack::Pack;
 pub use cards::pile::Pile;
 pub use cards::rank::*;